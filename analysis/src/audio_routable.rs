@@ -32,50 +32,61 @@ pub fn get_output_nodes(a: &HIRCObject) -> Option<Vec<u32>> {
     })
 }
 
+/// Edges for the objects that trigger playback rather than route audio. Events
+/// link to their actions and actions link to the object they control.
+pub fn get_action_edges(a: &HIRCObject) -> Option<Vec<u32>> {
+    Some(match &a.body {
+        HIRCObjectBody::Event(b) => b.actions.clone(),
+        HIRCObjectBody::Action(b) if b.external_id.as_hash() != 0 => vec![b.external_id.as_hash()],
+        HIRCObjectBody::Action(_) => vec![],
+        _ => return None,
+    })
+}
+
 impl AudioRoutable for CAkSound {
     fn outputs_to(&self) -> Vec<u32> {
-        if self.node_base_params.override_bus_id != 0 {
-            vec![self.node_base_params.override_bus_id]
+        if self.node_base_params.override_bus_id.as_hash() != 0 {
+            vec![self.node_base_params.override_bus_id.as_hash()]
         } else {
-            vec![self.node_base_params.direct_parent_id]
+            vec![self.node_base_params.direct_parent_id.as_hash()]
         }
     }
 }
 
 impl AudioRoutable for CAkRanSeqCntr {
     fn outputs_to(&self) -> Vec<u32> {
-        if self.node_base_params.override_bus_id != 0 {
-            vec![self.node_base_params.override_bus_id]
+        if self.node_base_params.override_bus_id.as_hash() != 0 {
+            vec![self.node_base_params.override_bus_id.as_hash()]
         } else {
-            vec![self.node_base_params.direct_parent_id]
+            vec![self.node_base_params.direct_parent_id.as_hash()]
         }
     }
 }
 
 impl AudioRoutable for CAkSwitchCntr {
     fn outputs_to(&self) -> Vec<u32> {
-        if self.node_base_params.override_bus_id != 0 {
-            vec![self.node_base_params.override_bus_id]
+        if self.node_base_params.override_bus_id.as_hash() != 0 {
+            vec![self.node_base_params.override_bus_id.as_hash()]
         } else {
-            vec![self.node_base_params.direct_parent_id]
+            vec![self.node_base_params.direct_parent_id.as_hash()]
         }
     }
 }
 
 impl AudioRoutable for CAkActorMixer {
     fn outputs_to(&self) -> Vec<u32> {
-        if self.node_base_params.override_bus_id != 0 {
-            vec![self.node_base_params.override_bus_id]
+        if self.node_base_params.override_bus_id.as_hash() != 0 {
+            vec![self.node_base_params.override_bus_id.as_hash()]
         } else {
-            vec![self.node_base_params.direct_parent_id]
+            vec![self.node_base_params.direct_parent_id.as_hash()]
         }
     }
 }
 
 impl AudioRoutable for CAkBus {
     fn outputs_to(&self) -> Vec<u32> {
-        if self.initial_values.override_bus_id != 0 {
-            vec![self.initial_values.override_bus_id]
+        if self.initial_values.override_bus_id.as_hash() != 0 {
+            vec![self.initial_values.override_bus_id.as_hash()]
         } else {
             vec![]
         }
@@ -84,58 +95,58 @@ impl AudioRoutable for CAkBus {
 
 impl AudioRoutable for CAkLayerCntr {
     fn outputs_to(&self) -> Vec<u32> {
-        if self.node_base_params.override_bus_id != 0 {
-            vec![self.node_base_params.override_bus_id]
+        if self.node_base_params.override_bus_id.as_hash() != 0 {
+            vec![self.node_base_params.override_bus_id.as_hash()]
         } else {
-            vec![self.node_base_params.direct_parent_id]
+            vec![self.node_base_params.direct_parent_id.as_hash()]
         }
     }
 }
 
 impl AudioRoutable for CAkMusicSegment {
     fn outputs_to(&self) -> Vec<u32> {
-        if self.music_node_params.node_base_params.override_bus_id != 0 {
-            vec![self.music_node_params.node_base_params.override_bus_id]
+        if self.music_node_params.node_base_params.override_bus_id.as_hash() != 0 {
+            vec![self.music_node_params.node_base_params.override_bus_id.as_hash()]
         } else {
-            vec![self.music_node_params.node_base_params.direct_parent_id]
+            vec![self.music_node_params.node_base_params.direct_parent_id.as_hash()]
         }
     }
 }
 
 impl AudioRoutable for CAkMusicTrack {
     fn outputs_to(&self) -> Vec<u32> {
-        if self.node_base_params.override_bus_id != 0 {
-            vec![self.node_base_params.override_bus_id]
+        if self.node_base_params.override_bus_id.as_hash() != 0 {
+            vec![self.node_base_params.override_bus_id.as_hash()]
         } else {
-            vec![self.node_base_params.direct_parent_id]
+            vec![self.node_base_params.direct_parent_id.as_hash()]
         }
     }
 }
 
 impl AudioRoutable for CAkMusicSwitchCntr {
     fn outputs_to(&self) -> Vec<u32> {
-        if self.music_trans_node_params.music_node_params.node_base_params.override_bus_id != 0 {
-            vec![self.music_trans_node_params.music_node_params.node_base_params.override_bus_id]
+        if self.music_trans_node_params.music_node_params.node_base_params.override_bus_id.as_hash() != 0 {
+            vec![self.music_trans_node_params.music_node_params.node_base_params.override_bus_id.as_hash()]
         } else {
-            vec![self.music_trans_node_params.music_node_params.node_base_params.direct_parent_id]
+            vec![self.music_trans_node_params.music_node_params.node_base_params.direct_parent_id.as_hash()]
         }
     }
 }
 
 impl AudioRoutable for CAkMusicRanSeqCntr {
     fn outputs_to(&self) -> Vec<u32> {
-        if self.music_trans_node_params.music_node_params.node_base_params.override_bus_id != 0 {
-            vec![self.music_trans_node_params.music_node_params.node_base_params.override_bus_id]
+        if self.music_trans_node_params.music_node_params.node_base_params.override_bus_id.as_hash() != 0 {
+            vec![self.music_trans_node_params.music_node_params.node_base_params.override_bus_id.as_hash()]
         } else {
-            vec![self.music_trans_node_params.music_node_params.node_base_params.direct_parent_id]
+            vec![self.music_trans_node_params.music_node_params.node_base_params.direct_parent_id.as_hash()]
         }
     }
 }
 
 impl AudioRoutable for CAkAuxBus {
     fn outputs_to(&self) -> Vec<u32> {
-        if self.initial_values.override_bus_id != 0 {
-            vec![self.initial_values.override_bus_id]
+        if self.initial_values.override_bus_id.as_hash() != 0 {
+            vec![self.initial_values.override_bus_id.as_hash()]
         } else {
             vec![]
         }
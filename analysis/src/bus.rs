@@ -0,0 +1,143 @@
+use std::collections;
+
+use wwise_format::*;
+
+/// Collects every bus ID objects in the soundbank route to through their
+/// `override_bus_id`, including the parent buses of the buses themselves.
+pub fn referenced_bus_ids(soundbank: &Soundbank) -> collections::BTreeSet<u32> {
+    let mut result = collections::BTreeSet::new();
+
+    soundbank.visit(&mut |o| {
+        if let Some(bus_id) = output_bus_id(o) {
+            result.insert(bus_id);
+        }
+    });
+
+    result
+}
+
+/// Collects the IDs of the `CAkBus` and `CAkAuxBus` objects in the soundbank.
+pub fn defined_bus_ids(soundbank: &Soundbank) -> collections::BTreeSet<u32> {
+    let mut result = collections::BTreeSet::new();
+
+    soundbank.visit(&mut |o| {
+        if let HIRCObjectBody::Bus(_) | HIRCObjectBody::AuxiliaryBus(_) = o.body {
+            result.insert(o.id.as_hash());
+        }
+    });
+
+    result
+}
+
+/// Lists `(object, bus)` pairs for the objects that route to a bus that isn't
+/// defined in this soundbank. These buses usually live in the init bank.
+pub fn undefined_bus_routes(soundbank: &Soundbank) -> Vec<(u32, u32)> {
+    let defined = defined_bus_ids(soundbank);
+    let mut result = vec![];
+
+    soundbank.visit(&mut |o| {
+        if let Some(bus_id) = output_bus_id(o).filter(|b| !defined.contains(b)) {
+            result.push((o.id.as_hash(), bus_id));
+        }
+    });
+
+    result
+}
+
+/// The bus an object outputs to through its `override_bus_id`, `None` if it
+/// doesn't route to a bus of its own.
+pub fn output_bus_id(object: &HIRCObject) -> Option<u32> {
+    let bus_id = match &object.body {
+        HIRCObjectBody::Bus(b) => b.initial_values.override_bus_id.as_hash(),
+        HIRCObjectBody::AuxiliaryBus(b) => b.initial_values.override_bus_id.as_hash(),
+        body => body.node_base_params()?.override_bus_id.as_hash(),
+    };
+
+    Some(bus_id).filter(|b| *b != 0)
+}
+
+/// A bus being ducked by another bus, see `ducking_graph`.
+#[derive(Debug)]
+pub struct Ducking {
+    pub ducking_bus_id: u32,
+    pub volume: f32,
+    pub fade_out_time: i32,
+    pub fade_in_time: i32,
+}
+
+/// Maps every ducked bus to the buses ducking it along with the volumes and
+/// fades they apply.
+pub fn ducking_graph(soundbank: &Soundbank) -> collections::BTreeMap<u32, Vec<Ducking>> {
+    let mut result = collections::BTreeMap::<u32, Vec<Ducking>>::new();
+
+    soundbank.visit(&mut |o| {
+        let rules = match &o.body {
+            HIRCObjectBody::Bus(b) => b.ducking_rules(),
+            HIRCObjectBody::AuxiliaryBus(b) => b.ducking_rules(),
+            _ => return,
+        };
+
+        for rule in rules {
+            result.entry(rule.bus_id).or_default().push(Ducking {
+                ducking_bus_id: o.id.as_hash(),
+                volume: rule.duck_volume,
+                fade_out_time: rule.fade_out_time,
+                fade_in_time: rule.fade_in_time,
+            });
+        }
+    });
+
+    result
+}
+
+#[cfg(test)]
+mod test {
+    use wwise_format::*;
+    use wwise_fixtures as fixtures;
+
+    use super::*;
+
+    fn soundbank(objects: &[Vec<u8>]) -> Soundbank {
+        let bytes = fixtures::minimal_bank(objects);
+        parse_soundbank(&bytes).unwrap()
+    }
+
+    #[test]
+    fn collects_bus_routes() {
+        let soundbank = soundbank(&[
+            // A bus routing to a bus from another soundbank
+            fixtures::hirc_object(8, 10, &fixtures::bus(20)),
+            // An aux bus on the master bus
+            fixtures::hirc_object(18, 40, &fixtures::bus(0)),
+            fixtures::hirc_object(2, 1, &fixtures::sound(100, 10, 0, &[])),
+            // Inherits its parent's bus
+            fixtures::hirc_object(2, 2, &fixtures::sound(101, 0, 1, &[])),
+            fixtures::hirc_object(2, 3, &fixtures::sound(102, 30, 0, &[])),
+        ]);
+
+        assert_eq!(referenced_bus_ids(&soundbank), [10, 20, 30].into_iter().collect());
+        assert_eq!(defined_bus_ids(&soundbank), [10, 40].into_iter().collect());
+        assert_eq!(undefined_bus_routes(&soundbank), vec![(10, 20), (3, 30)]);
+    }
+
+    #[test]
+    fn maps_ducked_buses() {
+        let soundbank = soundbank(&[
+            fixtures::hirc_object(8, 10, &fixtures::bus_with_ducks(0, &[(20, -6.0, 500, 1000)])),
+            fixtures::hirc_object(8, 20, &fixtures::bus(10)),
+            fixtures::hirc_object(18, 30, &fixtures::bus_with_ducks(10, &[
+                (20, -12.0, 0, 250),
+                (40, -3.0, 100, 100),
+            ])),
+        ]);
+
+        let graph = ducking_graph(&soundbank);
+        let ducks = |bus_id| graph[&bus_id].iter()
+            .map(|d| (d.ducking_bus_id, d.volume, d.fade_out_time, d.fade_in_time))
+            .collect::<Vec<_>>();
+
+        assert_eq!(graph.keys().copied().collect::<Vec<_>>(), vec![20, 40]);
+        assert_eq!(ducks(20), vec![(10, -6.0, 500, 1000), (30, -12.0, 0, 250)]);
+        assert_eq!(ducks(40), vec![(30, -3.0, 100, 100)]);
+    }
+}
@@ -2,11 +2,66 @@ use std::collections;
 
 use crate::fnv;
 
-pub type FNVDictionary = collections::HashMap<u32, String>;
+pub use wwise_format::FNVDictionary;
 
+/// Names that hash to the same ID.
+pub type Collision = (u32, Vec<String>);
+
+/// Parses a dictionary with one name per line. On hash collisions the last
+/// name wins, use `parse_dictionary_checked` to find out about them.
 pub fn parse_dictionary(input: &str) -> FNVDictionary {
-    input.lines()
-        .filter(|l| !l.is_empty() || !l.starts_with('#'))
+    names(input)
         .map(|l| (fnv::create_hash(l), l.to_string()))
         .collect()
 }
+
+/// Like `parse_dictionary` but also reports every hash claimed by more than one
+/// name, sorted by hash. Names only differing in case hash identically and
+/// aren't considered a collision.
+pub fn parse_dictionary_checked(input: &str) -> (FNVDictionary, Vec<Collision>) {
+    let mut claimed = collections::HashMap::<u32, Vec<String>>::new();
+    for name in names(input) {
+        let names = claimed.entry(fnv::create_hash(name)).or_default();
+        if !names.iter().any(|n| n.eq_ignore_ascii_case(name)) {
+            names.push(name.to_string());
+        }
+    }
+
+    let mut collisions = claimed.into_iter()
+        .filter(|(_, names)| names.len() > 1)
+        .collect::<Vec<_>>();
+    collisions.sort_by_key(|(hash, _)| *hash);
+
+    (parse_dictionary(input), collisions)
+}
+
+/// The names listed in a dictionary, skipping blank lines and `#` comments.
+pub fn names(input: &str) -> impl Iterator<Item = &str> {
+    input.lines().filter(|l| !l.is_empty() && !l.starts_with('#'))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn skips_comments_and_blank_lines() {
+        let dictionary = parse_dictionary("# Events\nPlay_c407001000\n\n#Play_Footstep\nPlay_Footstep\n");
+
+        assert_eq!(dictionary.len(), 2);
+        assert_eq!(dictionary.get(&1834890111).map(String::as_str), Some("Play_c407001000"));
+        assert!(dictionary.values().all(|n| !n.is_empty() && !n.starts_with('#')));
+        assert!(!dictionary.contains_key(&fnv::create_hash("")));
+    }
+
+    #[test]
+    fn reports_collisions() {
+        let input = "Play_729289\nPlay_1885016\nPLAY_1885016\nPlay_c407001000\n";
+        let (dictionary, collisions) = parse_dictionary_checked(input);
+
+        assert_eq!(dictionary.len(), 2);
+        assert_eq!(collisions.len(), 1);
+        assert_eq!(collisions[0].0, 1585576301);
+        assert_eq!(collisions[0].1, vec!["Play_729289".to_string(), "Play_1885016".to_string()]);
+    }
+}
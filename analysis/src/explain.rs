@@ -0,0 +1,193 @@
+use wwise_format::*;
+
+use crate::audio_routable::get_output_nodes;
+use crate::dictionary::FNVDictionary;
+use crate::label::get_type_label;
+
+/// Assembles a human-readable summary of a single HIRC object, for example:
+/// `Sound 123 ('Footstep'), streamed Vorbis source 456, routed to bus 'SFX' (789), volume -3 dB, 2 RTPCs on Volume/LPF.`
+pub fn explain(
+    soundbank: &Soundbank,
+    id: u32,
+    dictionary: Option<&FNVDictionary>,
+) -> String {
+    let object = soundbank.hirc()
+        .and_then(|h| h.objects.iter().find(|o| o.id.as_hash() == id));
+
+    let object = match object {
+        Some(o) => o,
+        None => return format!("Object {} is not defined in this soundbank.", id),
+    };
+
+    let mut parts = vec![
+        format!("{} {}", get_type_label(object), describe_id(id, dictionary)),
+    ];
+
+    match &object.body {
+        HIRCObjectBody::Sound(s) => parts.push(describe_source(&s.bank_source_data)),
+        HIRCObjectBody::MusicTrack(t) => parts.push(format!("{} source(s)", t.sources.len())),
+        HIRCObjectBody::Event(e) => parts.push(format!("{} action(s)", e.actions.len())),
+        HIRCObjectBody::Action(a) => parts.push(format!(
+            "action type {:#06x} targeting {}",
+            a.action_type,
+            describe_id(a.external_id.as_hash(), dictionary),
+        )),
+        _ => {},
+    }
+
+    if let Some(route) = describe_route(object, dictionary) {
+        parts.push(route);
+    }
+
+    let (props, rtpcs) = match &object.body {
+        HIRCObjectBody::Bus(b) => (
+            Some(&b.initial_values.bus_initial_params.prop_bundle),
+            Some(&b.initial_values.initial_rtpc),
+        ),
+        HIRCObjectBody::AuxiliaryBus(b) => (
+            Some(&b.initial_values.bus_initial_params.prop_bundle),
+            Some(&b.initial_values.initial_rtpc),
+        ),
+        body => match body.node_base_params() {
+            Some(p) => (
+                Some(&p.node_initial_params.prop_initial_values),
+                Some(&p.initial_rtpc),
+            ),
+            None => (None, None),
+        },
+    };
+
+    let volume = props.and_then(|p| p.iter().find_map(|p| match p {
+        PropBundle::Volume(v) => Some(*v),
+        _ => None,
+    }));
+    if let Some(volume) = volume {
+        parts.push(format!("volume {} dB", volume));
+    }
+
+    if let Some(rtpcs) = rtpcs.filter(|r| !r.rtpcs.is_empty()) {
+        let parameters = rtpcs.rtpcs.iter()
+            .map(|r| rtpc_parameter_label(r.param_id))
+            .collect::<Vec<_>>();
+
+        parts.push(format!("{} RTPCs on {}", rtpcs.rtpcs.len(), parameters.join("/")));
+    }
+
+    format!("{}.", parts.join(", "))
+}
+
+fn describe_id(id: u32, dictionary: Option<&FNVDictionary>) -> String {
+    match dictionary.and_then(|d| d.get(&id)) {
+        Some(name) => format!("{} ('{}')", id, name),
+        None => id.to_string(),
+    }
+}
+
+fn describe_source(source: &AkBankSourceData) -> String {
+    if source.is_external_source() {
+        return format!("external source {}", source.media_information.source_id);
+    }
+
+    let source_type = match source.source_type {
+        SourceType::Embedded => "embedded",
+        SourceType::PrefetchStreaming => "prefetch-streamed",
+        SourceType::Streaming => "streamed",
+    };
+
+    format!(
+        "{} {} source {}",
+        source_type,
+        codec_label(&source.plugin),
+        source.media_information.source_id,
+    )
+}
+
+fn codec_label(plugin: &PluginId) -> String {
+    match plugin {
+        PluginId::PCM => "PCM".to_string(),
+        PluginId::ADPCM => "ADPCM".to_string(),
+        PluginId::VORBIS => "Vorbis".to_string(),
+        PluginId::OPUS | PluginId::OPUSNX | PluginId::OPUSWEM1 | PluginId::OPUSWEM2 => "Opus".to_string(),
+        plugin => format!("{:?}", plugin),
+    }
+}
+
+fn describe_route(object: &HIRCObject, dictionary: Option<&FNVDictionary>) -> Option<String> {
+    let output = get_output_nodes(object)?.into_iter().next()?;
+
+    let routes_to_bus = match &object.body {
+        HIRCObjectBody::Bus(_) | HIRCObjectBody::AuxiliaryBus(_) => true,
+        body => body.node_base_params()
+            .map(|p| p.override_bus_id.as_hash() != 0)
+            .unwrap_or(false),
+    };
+
+    let name = match dictionary.and_then(|d| d.get(&output)) {
+        Some(name) => format!("'{}' ({})", name, output),
+        None => output.to_string(),
+    };
+
+    Some(match routes_to_bus {
+        true => format!("routed to bus {}", name),
+        false => format!("child of {}", name),
+    })
+}
+
+/// Names for the most common AkRTPC_ParameterID values.
+fn rtpc_parameter_label(param_id: u8) -> String {
+    match param_id {
+        0x00 => "Volume".to_string(),
+        0x01 => "LFE".to_string(),
+        0x02 => "Pitch".to_string(),
+        0x03 => "LPF".to_string(),
+        0x04 => "HPF".to_string(),
+        0x05 => "BusVolume".to_string(),
+        0x06 => "InitialDelay".to_string(),
+        0x07 => "MakeUpGain".to_string(),
+        _ => format!("param {}", param_id),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use wwise_format::*;
+    use wwise_fixtures as fixtures;
+
+    use super::*;
+
+    #[test]
+    fn explains_a_sound() {
+        // A Volume and an LPF curve in place of the empty InitialRTPC
+        let rtpc = |param_id| {
+            let mut rtpc = fixtures::initial_rtpc(0x10)[2..].to_vec();
+            rtpc[6] = param_id;
+            rtpc
+        };
+
+        let mut sound = fixtures::sound(456, 789, 0, &[(0x00, -3.0)]);
+        // Streamed
+        sound[4] = 0x2;
+        sound.truncate(sound.len() - 2);
+        sound.extend(2u16.to_le_bytes());
+        sound.extend(rtpc(0x00));
+        sound.extend(rtpc(0x03));
+
+        let bytes = fixtures::minimal_bank(&[fixtures::hirc_object(2, 123, &sound)]);
+        let soundbank = parse_soundbank(&bytes).unwrap();
+
+        let dictionary = FNVDictionary::from([
+            (123, "Footstep".to_string()),
+            (789, "SFX".to_string()),
+        ]);
+
+        assert_eq!(
+            explain(&soundbank, 123, Some(&dictionary)),
+            "Sound 123 ('Footstep'), streamed Vorbis source 456, routed to bus 'SFX' (789), volume -3 dB, 2 RTPCs on Volume/LPF.",
+        );
+        assert_eq!(
+            explain(&soundbank, 123, None),
+            "Sound 123, streamed Vorbis source 456, routed to bus 789, volume -3 dB, 2 RTPCs on Volume/LPF.",
+        );
+        assert_eq!(explain(&soundbank, 1, None), "Object 1 is not defined in this soundbank.");
+    }
+}
@@ -0,0 +1,143 @@
+use std::collections;
+
+use wwise_format::*;
+
+use crate::audio_routable::get_output_nodes;
+
+/// Maps every object that audio is routed to onto the objects routing to it,
+/// in HIRC order.
+pub fn output_children(soundbank: &Soundbank) -> collections::HashMap<u32, Vec<u32>> {
+    let mut result = collections::HashMap::<u32, Vec<u32>>::new();
+
+    soundbank.visit(&mut |object| {
+        for output in get_output_nodes(object).into_iter().flatten() {
+            if output != 0 {
+                result.entry(output).or_default().push(object.id.as_hash());
+            }
+        }
+    });
+
+    result
+}
+
+/// Walks the outputs of `id` up to the master bus, closest first. The chain
+/// ends early on an object that isn't in the soundbank, like a bus from the
+/// init bank, which is still included.
+pub fn parent_chain(soundbank: &Soundbank, id: u32) -> Vec<u32> {
+    chain(&objects_by_id(soundbank), id)
+}
+
+/// The buses audio from `id` ends up passing through, from the bus it's
+/// mixed into first up to the master bus. Objects without an override bus
+/// inherit the output of their parent, so the parent chain is followed and
+/// anything but buses is left out. An object that isn't in the soundbank ends
+/// the route and is included, as it's usually a bus from the init bank. The
+/// route is cut short where a malformed bank loops back on itself.
+pub fn effective_route(soundbank: &Soundbank, id: u32) -> Vec<u32> {
+    let objects = objects_by_id(soundbank);
+
+    chain(&objects, id).into_iter()
+        .filter(|p| match objects.get(p) {
+            Some(o) => matches!(o.body, HIRCObjectBody::Bus(_) | HIRCObjectBody::AuxiliaryBus(_)),
+            None => true,
+        })
+        .collect()
+}
+
+/// The depth of every routable object in the output hierarchy, counted in
+/// hops up to the topmost bus. A bus outside of the soundbank counts as a hop
+/// as well. Objects that don't route anywhere, like events, are left out.
+pub fn depths(soundbank: &Soundbank) -> collections::HashMap<u32, usize> {
+    let objects = objects_by_id(soundbank);
+
+    objects.iter()
+        .filter(|(_, o)| get_output_nodes(o).is_some())
+        .map(|(id, _)| (*id, chain(&objects, *id).len()))
+        .collect()
+}
+
+fn objects_by_id(soundbank: &Soundbank) -> collections::HashMap<u32, &HIRCObject> {
+    soundbank.hirc().into_iter()
+        .flat_map(|h| h.objects.iter())
+        .map(|o| (o.id.as_hash(), o))
+        .collect()
+}
+
+fn chain(objects: &collections::HashMap<u32, &HIRCObject>, id: u32) -> Vec<u32> {
+    let mut result = vec![];
+    let mut current = id;
+    while let Some(object) = objects.get(&current) {
+        let parent = match get_output_nodes(object).and_then(|o| o.first().copied()) {
+            Some(p) if p != 0 => p,
+            _ => break,
+        };
+
+        // Don't loop forever on malformed banks
+        if parent == id || result.contains(&parent) {
+            break;
+        }
+
+        result.push(parent);
+        current = parent;
+    }
+
+    result
+}
+
+#[cfg(test)]
+mod test {
+    use wwise_format::*;
+    use wwise_fixtures as fixtures;
+
+    use super::*;
+
+    #[test]
+    fn parent_chain_follows_outputs() {
+        let bytes = fixtures::minimal_bank(&[
+            fixtures::hirc_object(8, 1, &fixtures::bus(0)),
+            fixtures::hirc_object(8, 2, &fixtures::bus(1)),
+            fixtures::hirc_object(7, 3, &fixtures::actor_mixer(&[4])),
+            fixtures::hirc_object(2, 4, &fixtures::sound(100, 0, 3, &[])),
+            // Overrides the actor mixer's output
+            fixtures::hirc_object(2, 5, &fixtures::sound(200, 2, 3, &[])),
+            fixtures::hirc_object(2, 6, &fixtures::sound(300, 0x1234, 0, &[])),
+        ]);
+        let soundbank = parse_soundbank(&bytes).unwrap();
+
+        assert_eq!(parent_chain(&soundbank, 4), vec![3]);
+        assert_eq!(parent_chain(&soundbank, 5), vec![2, 1]);
+        assert_eq!(parent_chain(&soundbank, 6), vec![0x1234]);
+        assert!(parent_chain(&soundbank, 1).is_empty());
+
+        let children = output_children(&soundbank);
+        assert_eq!(children[&1], vec![2]);
+        assert_eq!(children[&3], vec![4]);
+        assert!(!children.contains_key(&0));
+
+        assert_eq!(effective_route(&soundbank, 4), Vec::<u32>::new());
+        assert_eq!(effective_route(&soundbank, 5), vec![2, 1]);
+        assert_eq!(effective_route(&soundbank, 6), vec![0x1234]);
+
+        let depths = depths(&soundbank);
+        assert_eq!(depths[&1], 0);
+        assert_eq!(depths[&5], 2);
+        assert_eq!(depths[&6], 1);
+        assert_eq!(depths.len(), 6);
+    }
+
+    #[test]
+    fn effective_route_skips_parents_and_stops_on_loops() {
+        let bytes = fixtures::minimal_bank(&[
+            fixtures::hirc_object(8, 1, &fixtures::bus(0)),
+            fixtures::hirc_object(2, 2, &fixtures::sound(100, 1, 0, &[])),
+            fixtures::hirc_object(2, 3, &fixtures::sound(200, 0, 2, &[])),
+            // These two are each other's parent
+            fixtures::hirc_object(2, 4, &fixtures::sound(300, 0, 5, &[])),
+            fixtures::hirc_object(2, 5, &fixtures::sound(400, 0, 4, &[])),
+        ]);
+        let soundbank = parse_soundbank(&bytes).unwrap();
+
+        assert_eq!(effective_route(&soundbank, 3), vec![1]);
+        assert!(effective_route(&soundbank, 4).is_empty());
+    }
+}
@@ -1,7 +1,5 @@
 use wwise_format::*;
 
-use crate::dictionary::*;
-
 pub fn get_label(
     a: &HIRCObject,
     dictionary: Option<&FNVDictionary>
@@ -15,7 +13,33 @@ pub fn get_label(
     }
 }
 
-fn get_type_label(a: &HIRCObject) -> &'static str {
+/// Every label `get_type_label` can return.
+pub const TYPE_LABELS: [&str; 22] = [
+    "State",
+    "Sound",
+    "Action",
+    "Event",
+    "RandomSequenceContainer",
+    "SwitchContainer",
+    "ActorMixer",
+    "Bus",
+    "LayerContainer",
+    "MusicSegment",
+    "MusicTrack",
+    "MusicSwitchContainer",
+    "MusicRandomSequenceContainer",
+    "Attenuation",
+    "DialogueEvent",
+    "EffectShareSet",
+    "EffectCustom",
+    "AuxiliaryBus",
+    "LFOModulator",
+    "EnvelopeModulator",
+    "AudioDevice",
+    "TimeModulator",
+];
+
+pub fn get_type_label(a: &HIRCObject) -> &'static str {
     match a.body {
         HIRCObjectBody::State(_) => "State",
         HIRCObjectBody::Sound(_) => "Sound",
@@ -41,3 +65,28 @@ fn get_type_label(a: &HIRCObject) -> &'static str {
         HIRCObjectBody::TimeModulator(_) => "TimeModulator",
     }
 }
+
+#[cfg(test)]
+mod test {
+    use wwise_format::*;
+    use wwise_fixtures as fixtures;
+
+    use super::*;
+
+    #[test]
+    fn type_labels_cover_every_object_type() {
+        let objects = fixtures::every_object_type().into_iter()
+            .enumerate()
+            .map(|(i, (body_type, body))| fixtures::hirc_object(body_type, i as u32 + 1, &body))
+            .collect::<Vec<_>>();
+
+        let bytes = fixtures::minimal_bank(&objects);
+        let soundbank = parse_soundbank(&bytes).unwrap();
+
+        let labels = soundbank.hirc().unwrap().objects.iter()
+            .map(get_type_label)
+            .collect::<Vec<_>>();
+
+        assert_eq!(labels, TYPE_LABELS);
+    }
+}
@@ -2,3 +2,9 @@ pub mod label;
 pub mod fnv;
 pub mod dictionary;
 pub mod audio_routable;
+pub mod bus;
+pub mod explain;
+pub mod named;
+pub mod strings;
+pub mod hierarchy;
+pub mod sources;
@@ -0,0 +1,81 @@
+use std::collections;
+
+use wwise_format::*;
+
+use crate::dictionary::FNVDictionary;
+
+/// A soundbank paired with the names of its objects, resolved once up front so
+/// interactive tools don't have to go through the dictionary on every lookup.
+pub struct NamedSoundbank {
+    pub soundbank: Soundbank,
+    names: collections::HashMap<u32, String>,
+}
+
+impl NamedSoundbank {
+    /// Resolves names for every HIRC object and STID entry. Objects that
+    /// already carry their name as a string ID keep that name.
+    pub fn new(soundbank: Soundbank, dictionary: &FNVDictionary) -> Self {
+        let mut names = collections::HashMap::new();
+
+        soundbank.visit(&mut |o| {
+            let id = o.id.as_hash();
+            let name = match &o.id {
+                ObjectId::String(s) => Some(s),
+                ObjectId::Hash(_) => dictionary.get(&id),
+                ObjectId::Hash64(_) => None,
+            };
+
+            if let Some(name) = name {
+                names.insert(id, name.clone());
+            }
+        });
+
+        for section in soundbank.sections.iter() {
+            if let SectionBody::STID(s) = &section.body {
+                for entry in s.entries.iter() {
+                    names.insert(entry.bnk_id, String::from_utf8_lossy(&entry.name).to_string());
+                }
+            }
+        }
+
+        Self { soundbank, names }
+    }
+
+    pub fn name_of(&self, id: u32) -> Option<&str> {
+        self.names.get(&id).map(|n| n.as_str())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use wwise_format::*;
+    use wwise_fixtures as fixtures;
+
+    use super::*;
+
+    #[test]
+    fn resolves_names_up_front() {
+        let footstep = create_hash("Footstep");
+
+        let mut bytes = fixtures::bkhd(0x8C, 0xB4D, 0x10, 0);
+        bytes.extend(fixtures::stid(&[(0xB4D, b"Music")]));
+        bytes.extend(fixtures::hirc(&[
+            fixtures::hirc_object(2, footstep, &fixtures::sound(100, 0, 0, &[])),
+            fixtures::hirc_object(2, 2, &fixtures::sound(101, 0, 0, &[])),
+            fixtures::hirc_object(2, 3, &fixtures::sound(102, 0, 0, &[])),
+        ]));
+        let mut soundbank = parse_soundbank(&bytes).unwrap();
+
+        // Already named, regardless of the dictionary
+        let explosion = ObjectId::String("Explosion".to_string());
+        soundbank.hirc_mut().unwrap().objects[1].id = explosion.clone();
+
+        let dictionary = FNVDictionary::from([(footstep, "Footstep".to_string())]);
+        let named = NamedSoundbank::new(soundbank, &dictionary);
+
+        assert_eq!(named.name_of(footstep), Some("Footstep"));
+        assert_eq!(named.name_of(explosion.as_hash()), Some("Explosion"));
+        assert_eq!(named.name_of(3), None);
+        assert_eq!(named.name_of(0xB4D), Some("Music"));
+    }
+}
@@ -0,0 +1,236 @@
+use std::collections;
+use std::fmt;
+
+use wwise_format::*;
+
+/// Collects the ID of every WEM the soundbank's HIRC refers to, whether it's
+/// embedded or streamed from a loose file.
+pub fn referenced_sources(soundbank: &Soundbank) -> collections::HashSet<u32> {
+    let mut result = collections::HashSet::new();
+
+    soundbank.visit(&mut |object| {
+        let (sources, media) = match &object.body {
+            HIRCObjectBody::Sound(s) => (std::slice::from_ref(&s.bank_source_data), &[][..]),
+            HIRCObjectBody::MusicTrack(t) => {
+                result.extend(t.playlist.iter().map(|p| p.source_id));
+                (t.sources.as_slice(), &[][..])
+            },
+            HIRCObjectBody::EffectShareSet(f) => (&[][..], f.fx_base_initial_values.media.as_slice()),
+            HIRCObjectBody::EffectCustom(f) => (&[][..], f.fx_base_initial_values.media.as_slice()),
+            HIRCObjectBody::AudioDevice(f) => (&[][..], f.fx_base_initial_values.media.as_slice()),
+            _ => return,
+        };
+
+        result.extend(sources.iter()
+            .filter(|s| s.has_media())
+            .map(|s| s.media_information.source_id));
+        result.extend(media.iter().map(|m| m.source_id));
+    });
+
+    result
+}
+
+/// The referenced sources split by their stream type. Prefetch-streamed
+/// sources count as streamed even though the DIDX holds their start. Media
+/// without a stream type, such as effect plugin media, goes by whether the
+/// soundbank's DIDX carries it.
+#[derive(Debug, Default)]
+pub struct SourceReport {
+    pub embedded: collections::BTreeSet<u32>,
+    /// Sources that have to be found elsewhere, usually as loose .wem files.
+    pub streamed: collections::BTreeSet<u32>,
+}
+
+impl fmt::Display for SourceReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} sources, {} embedded, {} streamed",
+            self.embedded.len() + self.streamed.len(),
+            self.embedded.len(),
+            self.streamed.len(),
+        )
+    }
+}
+
+pub fn source_report(soundbank: &Soundbank) -> SourceReport {
+    let in_didx = soundbank.didx()
+        .map(|d| d.descriptors.iter().map(|d| d.id).collect::<collections::HashSet<_>>())
+        .unwrap_or_default();
+
+    let mut source_types = collections::HashMap::new();
+    soundbank.visit(&mut |object| {
+        let sources = match &object.body {
+            HIRCObjectBody::Sound(s) => std::slice::from_ref(&s.bank_source_data),
+            HIRCObjectBody::MusicTrack(t) => t.sources.as_slice(),
+            _ => return,
+        };
+
+        for source in sources.iter().filter(|s| s.has_media()) {
+            source_types.insert(source.media_information.source_id, source.source_type.clone());
+        }
+    });
+
+    let mut result = SourceReport::default();
+    for source in referenced_sources(soundbank) {
+        let embedded = match source_types.get(&source) {
+            Some(source_type) => matches!(source_type, SourceType::Embedded),
+            None => in_didx.contains(&source),
+        };
+
+        match embedded {
+            true => result.embedded.insert(source),
+            false => result.streamed.insert(source),
+        };
+    }
+
+    result
+}
+
+/// Where a sound's media is loaded from, going by both its source type and
+/// whether the soundbank's DIDX has an entry for it.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub enum SourceStorage {
+    /// Loaded into memory along with the soundbank.
+    InMemory,
+    /// Streamed from a loose file.
+    Streamed,
+    /// Streamed, but the start of the file is in the soundbank so playback
+    /// doesn't have to wait for the stream.
+    PrefetchStreamed,
+    /// Marked as embedded but not in the DIDX, the media has to come from
+    /// another soundbank.
+    Missing,
+}
+
+impl fmt::Display for SourceStorage {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            SourceStorage::InMemory => "in_memory",
+            SourceStorage::Streamed => "streamed",
+            SourceStorage::PrefetchStreamed => "prefetch_streamed",
+            SourceStorage::Missing => "missing",
+        })
+    }
+}
+
+/// The source of a single sound as declared in the HIRC.
+#[derive(Debug)]
+pub struct SoundSource {
+    pub sound_id: u32,
+    pub source_id: u32,
+    pub source_type: SourceType,
+    /// Whether the DIDX has an entry for the source, which for streamed
+    /// sources is the prefetched part.
+    pub in_didx: bool,
+}
+
+impl SoundSource {
+    pub fn storage(&self) -> SourceStorage {
+        match (&self.source_type, self.in_didx) {
+            (SourceType::Embedded, true) => SourceStorage::InMemory,
+            (SourceType::Embedded, false) => SourceStorage::Missing,
+            (_, true) => SourceStorage::PrefetchStreamed,
+            (_, false) => SourceStorage::Streamed,
+        }
+    }
+}
+
+/// Collects the source of every sound that plays a WEM, keyed by the sound's
+/// ID.
+pub fn sound_sources(soundbank: &Soundbank) -> collections::BTreeMap<u32, SoundSource> {
+    let didx = soundbank.didx()
+        .map(|d| d.descriptors.iter().map(|d| d.id).collect::<collections::HashSet<_>>())
+        .unwrap_or_default();
+
+    let mut result = collections::BTreeMap::new();
+    soundbank.visit(&mut |object| {
+        let source = match &object.body {
+            HIRCObjectBody::Sound(s) if s.bank_source_data.has_media() => &s.bank_source_data,
+            _ => return,
+        };
+
+        let source_id = source.media_information.source_id;
+        result.insert(object.id.as_hash(), SoundSource {
+            sound_id: object.id.as_hash(),
+            source_id,
+            source_type: source.source_type.clone(),
+            in_didx: didx.contains(&source_id),
+        });
+    });
+
+    result
+}
+
+#[cfg(test)]
+mod test {
+    use wwise_format::*;
+    use wwise_fixtures as fixtures;
+
+    use super::*;
+
+    #[test]
+    fn splits_embedded_and_streamed() {
+        // Stream the music track's source
+        let mut music_track = fixtures::music_track(200);
+        music_track[9] = 0x2;
+
+        let mut bytes = fixtures::bkhd(0x8C, 0xB4D, 0x10, 0);
+        bytes.extend(fixtures::media(&[(100, vec![0xAA; 0x10])], 0x10));
+        bytes.extend(fixtures::hirc(&[
+            fixtures::hirc_object(2, 1, &fixtures::sound(100, 0, 0, &[])),
+            fixtures::hirc_object(11, 2, &music_track),
+            // Tone generator, no media
+            fixtures::hirc_object(2, 3, &fixtures::sound_with_plugin(0x00660002, &[], 300, 0, 0)),
+            fixtures::hirc_object(16, 4, &fixtures::fx_base_initial_values(0x00870003)),
+        ]));
+        let soundbank = parse_soundbank(&bytes).unwrap();
+
+        let sources = referenced_sources(&soundbank);
+        assert_eq!(sources, [100, 200].into_iter().collect());
+
+        let report = source_report(&soundbank);
+        assert_eq!(report.embedded.iter().copied().collect::<Vec<_>>(), vec![100]);
+        assert_eq!(report.streamed.iter().copied().collect::<Vec<_>>(), vec![200]);
+        assert_eq!(report.to_string(), "2 sources, 1 embedded, 1 streamed");
+    }
+
+    #[test]
+    fn classifies_sound_storage() {
+        let sound = |source_id, source_type| {
+            let mut body = fixtures::sound(source_id, 0, 0, &[]);
+            body[4] = source_type;
+            body
+        };
+
+        let mut bytes = fixtures::bkhd(0x8C, 0xB4D, 0x10, 0);
+        bytes.extend(fixtures::media(&[(100, vec![0xAA; 0x10]), (300, vec![0xBB; 0x10])], 0x10));
+        bytes.extend(fixtures::hirc(&[
+            fixtures::hirc_object(2, 1, &sound(100, 0x0)),
+            fixtures::hirc_object(2, 2, &sound(200, 0x2)),
+            fixtures::hirc_object(2, 3, &sound(300, 0x1)),
+            fixtures::hirc_object(2, 4, &sound(400, 0x0)),
+            fixtures::hirc_object(2, 5, &fixtures::sound_with_plugin(0x00660002, &[], 500, 0, 0)),
+        ]));
+        let soundbank = parse_soundbank(&bytes).unwrap();
+
+        let sources = sound_sources(&soundbank);
+        let storage = sources.values()
+            .map(|s| (s.sound_id, s.storage()))
+            .collect::<Vec<_>>();
+
+        assert_eq!(storage, vec![
+            (1, SourceStorage::InMemory),
+            (2, SourceStorage::Streamed),
+            (3, SourceStorage::PrefetchStreamed),
+            (4, SourceStorage::Missing),
+        ]);
+        assert_eq!(sources[&3].source_id, 300);
+        assert_eq!(SourceStorage::PrefetchStreamed.to_string(), "prefetch_streamed");
+
+        // The prefetched start of 300 is in the DIDX but it's still streamed
+        let report = source_report(&soundbank);
+        assert_eq!(report.embedded.iter().copied().collect::<Vec<_>>(), vec![100, 400]);
+        assert_eq!(report.streamed.iter().copied().collect::<Vec<_>>(), vec![200, 300]);
+    }
+}
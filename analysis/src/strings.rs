@@ -0,0 +1,95 @@
+use wwise_format::*;
+
+/// Where a string returned by `strings` was found.
+#[derive(Debug)]
+pub enum Location {
+    /// Name of the soundbank with this ID in the STID.
+    BankName { bank_id: u32 },
+    /// The platform named by the PLAT.
+    Platform,
+    /// DLL name of a plugin in the INIT.
+    PluginDll { plugin_id: String },
+    /// Name of a marker on a music segment.
+    MarkerName { segment_id: u32, marker_id: u32 },
+}
+
+/// Collects every human-readable string stored in the soundbank.
+pub fn strings(soundbank: &Soundbank) -> Vec<(Location, String)> {
+    let mut result = vec![];
+
+    for section in soundbank.sections.iter() {
+        match &section.body {
+            SectionBody::STID(s) => {
+                for entry in s.entries.iter() {
+                    result.push((
+                        Location::BankName { bank_id: entry.bnk_id },
+                        String::from_utf8_lossy(&entry.name).to_string(),
+                    ));
+                }
+            },
+            SectionBody::PLAT(p) => {
+                result.push((Location::Platform, p.string.to_string_lossy().to_string()));
+            },
+            SectionBody::INIT(i) => {
+                for plugin in i.plugins.iter() {
+                    result.push((
+                        Location::PluginDll { plugin_id: format!("{:?}", plugin.plugin_id) },
+                        plugin.dll_name.to_string_lossy().to_string(),
+                    ));
+                }
+            },
+            _ => {},
+        }
+    }
+
+    soundbank.visit(&mut |o| {
+        if let HIRCObjectBody::MusicSegment(s) = &o.body {
+            for marker in s.markers.iter().filter(|m| !m.string.is_empty()) {
+                result.push((
+                    Location::MarkerName { segment_id: o.id.as_hash(), marker_id: marker.id },
+                    marker.string.to_string_lossy().to_string(),
+                ));
+            }
+        }
+    });
+
+    result
+}
+
+#[cfg(test)]
+mod test {
+    use wwise_format::*;
+    use wwise_fixtures as fixtures;
+
+    use super::*;
+
+    #[test]
+    fn collects_every_string() {
+        let mut init = 1u32.to_le_bytes().to_vec();
+        init.extend(0x00690003u32.to_le_bytes());
+        init.extend(15u32.to_le_bytes());
+        init.extend(b"AkParametricEQ\0");
+
+        let mut plat = 8u32.to_le_bytes().to_vec();
+        plat.extend(b"Windows\0");
+
+        let mut bytes = fixtures::bkhd(0x8C, 0xB4D, 0x10, 0);
+        bytes.extend(fixtures::section(b"INIT", &init));
+        bytes.extend(fixtures::section(b"PLAT", &plat));
+        bytes.extend(fixtures::stid(&[(0xB4D, b"Music")]));
+        bytes.extend(fixtures::hirc(&[
+            // Has an unnamed marker and one named "End"
+            fixtures::hirc_object(10, 10, &fixtures::music_segment(&[])),
+        ]));
+        let soundbank = parse_soundbank(&bytes).unwrap();
+
+        let strings = strings(&soundbank);
+        assert_eq!(strings.len(), 4);
+        assert!(matches!(&strings[0], (Location::PluginDll { plugin_id }, s)
+            if plugin_id == "WwiseParametricEQ" && s == "AkParametricEQ"));
+        assert!(matches!(&strings[1], (Location::Platform, s) if s == "Windows"));
+        assert!(matches!(&strings[2], (Location::BankName { bank_id: 0xB4D }, s) if s == "Music"));
+        assert!(matches!(&strings[3], (Location::MarkerName { segment_id: 10, marker_id: 0x51 }, s)
+            if s == "End"));
+    }
+}
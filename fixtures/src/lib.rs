@@ -0,0 +1,682 @@
+//! Hand-assembled soundbank bytes for the unit tests. There are no sample banks
+//! in the repository so these mirror the layout the game's banks use.
+
+pub fn section(magic: &[u8; 4], body: &[u8]) -> Vec<u8> {
+    let mut result = magic.to_vec();
+    result.extend((body.len() as u32).to_le_bytes());
+    result.extend(body);
+    result
+}
+
+pub fn bkhd(version: u32, bank_id: u32, wem_alignment: u32, padding: usize) -> Vec<u8> {
+    let mut body = vec![];
+    body.extend(version.to_le_bytes());
+    body.extend(bank_id.to_le_bytes());
+    // SFX language
+    body.extend(0x5DB1C0A7u32.to_le_bytes());
+    body.extend(wem_alignment.to_le_bytes());
+    body.extend(0u32.to_le_bytes());
+    body.extend(vec![0u8; padding]);
+    section(b"BKHD", &body)
+}
+
+pub fn hirc(objects: &[Vec<u8>]) -> Vec<u8> {
+    let mut body = (objects.len() as u32).to_le_bytes().to_vec();
+    for object in objects {
+        body.extend(object);
+    }
+    section(b"HIRC", &body)
+}
+
+/// A bank with just a BKHD and a HIRC holding `objects`.
+pub fn minimal_bank(objects: &[Vec<u8>]) -> Vec<u8> {
+    let mut result = bkhd(0x8C, 0xB4D, 0x10, 0);
+    result.extend(hirc(objects));
+    result
+}
+
+pub fn hirc_object(body_type: u8, id: u32, body: &[u8]) -> Vec<u8> {
+    let mut result = vec![body_type];
+    result.extend((body.len() as u32 + 4).to_le_bytes());
+    result.extend(id.to_le_bytes());
+    result.extend(body);
+    result
+}
+
+/// Builds a DIDX and DATA section pair for the supplied WEMs, aligning every
+/// WEM but the last to wem_alignment.
+pub fn media(wems: &[(u32, Vec<u8>)], wem_alignment: usize) -> Vec<u8> {
+    let mut descriptors = vec![];
+    let mut data = vec![];
+    for (i, (id, wem)) in wems.iter().enumerate() {
+        descriptors.extend(id.to_le_bytes());
+        descriptors.extend((data.len() as u32).to_le_bytes());
+        descriptors.extend((wem.len() as u32).to_le_bytes());
+        data.extend(wem);
+
+        if i != wems.len() - 1 {
+            data.resize(data.len().div_ceil(wem_alignment) * wem_alignment, 0);
+        }
+    }
+
+    let mut result = section(b"DIDX", &descriptors);
+    result.extend(section(b"DATA", &data));
+    result
+}
+
+pub fn sound(source_id: u32, override_bus_id: u32, parent_id: u32, props: &[(u8, f32)]) -> Vec<u8> {
+    // AkBankSourceData: VORBIS, embedded
+    let mut body = bank_source_data(0x00040001, &[], source_id);
+    body.extend(node_base_params(override_bus_id, parent_id, props));
+    body
+}
+
+pub fn sound_with_plugin(
+    plugin: u32,
+    params: &[u8],
+    source_id: u32,
+    override_bus_id: u32,
+    parent_id: u32,
+) -> Vec<u8> {
+    let mut body = bank_source_data(plugin, params, source_id);
+    body.extend(node_base_params(override_bus_id, parent_id, &[]));
+    body
+}
+
+/// A sound whose state chunk has a group for every entry in `state_counts`.
+pub fn sound_with_states(state_counts: &[usize]) -> Vec<u8> {
+    let mut body = sound(100, 1, 0, &[]);
+    // Swap out the empty StateChunk and InitialRTPC at the end
+    body.truncate(body.len() - 4);
+
+    body.push(0x0);
+    body.push(state_counts.len() as u8);
+    for (group, count) in state_counts.iter().enumerate() {
+        body.extend((group as u32 + 1).to_le_bytes());
+        body.push(0x0);
+        body.push(*count as u8);
+        for state in 0..*count as u32 {
+            body.extend(state.to_le_bytes());
+            body.extend((state + 0x1000).to_le_bytes());
+        }
+    }
+
+    body.extend(0u16.to_le_bytes());
+    body
+}
+
+/// Only source plugins (type 2) get a params blob, mirroring the game's banks.
+pub fn bank_source_data(plugin: u32, params: &[u8], source_id: u32) -> Vec<u8> {
+    let mut body = plugin.to_le_bytes().to_vec();
+    body.push(0x0);
+    body.extend(source_id.to_le_bytes());
+    body.extend(0x10u32.to_le_bytes());
+    body.push(0x0);
+    if plugin & 0x0F == 0x2 {
+        body.extend((params.len() as u32).to_le_bytes());
+        body.extend(params);
+    }
+    body
+}
+
+pub fn node_base_params(override_bus_id: u32, parent_id: u32, props: &[(u8, f32)]) -> Vec<u8> {
+    let mut body = vec![];
+    // NodeInitialFxParams
+    body.extend([0x0, 0x0]);
+    // override_attachment_params
+    body.push(0x0);
+    body.extend(override_bus_id.to_le_bytes());
+    body.extend(parent_id.to_le_bytes());
+    // unknown_flags
+    body.push(0x0);
+    body.extend(prop_bundle(props));
+    // PropRangedModifiers
+    body.push(0x0);
+    // PositioningParams
+    body.push(0x0);
+    // AuxParams
+    body.push(0x0);
+    body.extend(0u32.to_le_bytes());
+    // AdvSettingsParams
+    body.extend([0x0, 0x0, 0x0, 0x0, 0x0, 0x0]);
+    // StateChunk
+    body.extend([0x0, 0x0]);
+    // InitialRTPC
+    body.extend(0u16.to_le_bytes());
+    body
+}
+
+pub fn prop_bundle(props: &[(u8, f32)]) -> Vec<u8> {
+    let mut body = vec![props.len() as u8];
+    body.extend(props.iter().map(|p| p.0));
+    for (_, value) in props {
+        body.extend(value.to_le_bytes());
+    }
+    body
+}
+
+pub fn bus(override_bus_id: u32) -> Vec<u8> {
+    bus_with_ducks(override_bus_id, &[])
+}
+
+/// A bus ducking the buses in `ducks` by `(bus_id, volume, fade_out, fade_in)`.
+pub fn bus_with_ducks(override_bus_id: u32, ducks: &[(u32, f32, i32, i32)]) -> Vec<u8> {
+    let mut body = vec![];
+    body.extend(override_bus_id.to_le_bytes());
+    if override_bus_id == 0 {
+        // device_share_set_id
+        body.extend(0u32.to_le_bytes());
+    }
+    // BusInitialParams
+    body.extend(prop_bundle(&[]));
+    body.push(0x0);
+    body.push(0x0);
+    body.extend(0u32.to_le_bytes());
+    body.push(0x0);
+    body.extend(0u16.to_le_bytes());
+    body.extend(0u32.to_le_bytes());
+    body.push(0x0);
+    // recovery_time, max_duck_volume
+    body.extend(0i32.to_le_bytes());
+    body.extend(0f32.to_le_bytes());
+    body.extend((ducks.len() as u32).to_le_bytes());
+    for (bus_id, volume, fade_out, fade_in) in ducks {
+        body.extend(bus_id.to_le_bytes());
+        body.extend(volume.to_le_bytes());
+        body.extend(fade_out.to_le_bytes());
+        body.extend(fade_in.to_le_bytes());
+        // Linear, Volume
+        body.extend([0x4, 0x0]);
+    }
+    // BusInitialFxParams
+    body.push(0x0);
+    body.extend(0u32.to_le_bytes());
+    body.push(0x0);
+    // override_attachment_params, InitialRTPC, StateChunk
+    body.push(0x0);
+    body.extend(0u16.to_le_bytes());
+    body.extend([0x0, 0x0]);
+    body
+}
+
+pub fn event(actions: &[u32]) -> Vec<u8> {
+    let mut body = vec![actions.len() as u8];
+    for action in actions {
+        body.extend(action.to_le_bytes());
+    }
+    body
+}
+
+pub fn action(action_type: u16, target: u32, params: &[u8]) -> Vec<u8> {
+    let mut body = vec![];
+    body.extend(action_type.to_le_bytes());
+    body.extend(target.to_le_bytes());
+    body.push(0x0);
+    // Props and ranged modifiers
+    body.extend([0x0, 0x0]);
+    body.extend(params);
+    body
+}
+
+pub fn action_play(target: u32, bank_id: u32) -> Vec<u8> {
+    let mut params = vec![0x4];
+    params.extend(bank_id.to_le_bytes());
+    action(0x0403, target, &params)
+}
+
+/// A RandomizerModifier spreading one unit around `base`.
+pub fn randomizer_modifier(base: f32) -> Vec<u8> {
+    [base, base - 1.0, base + 1.0].iter()
+        .flat_map(|v| v.to_le_bytes())
+        .collect()
+}
+
+pub fn except_params(objects: &[u32]) -> Vec<u8> {
+    let mut params = vec![objects.len() as u8];
+    for object in objects {
+        params.extend(object.to_le_bytes());
+        params.push(0x0);
+    }
+    params
+}
+
+/// A small but complete bank: a master bus, a sound routed to it, a sound
+/// parented to the first one, a play action and an event triggering it.
+pub fn soundbank(wem_alignment: u32) -> Vec<u8> {
+    let wems = vec![
+        (100, vec![0xAA; 0x15]),
+        (200, vec![0xBB; 0x33]),
+    ];
+
+    let unpadded = 8 * 3 + 0x14 + wems.len() * 0xC;
+    let padding = unpadded.next_multiple_of(wem_alignment as usize) - unpadded;
+
+    let mut result = bkhd(0x8C, 0xB4D, wem_alignment, padding);
+    result.extend(media(&wems, wem_alignment as usize));
+    result.extend(hirc(&[
+        hirc_object(0x08, 1, &bus(0)),
+        hirc_object(0x02, 2, &sound(100, 1, 0, &[(0x00, -3.0)])),
+        hirc_object(0x02, 3, &sound(200, 0, 2, &[])),
+        hirc_object(0x03, 4, &action_play(2, 0xB4D)),
+        hirc_object(0x04, 5, &event(&[4])),
+    ]));
+    result
+}
+
+/// An STMG without any state or switch groups. The texture count is only
+/// written when `textures` is supplied, `trailing` goes after the textures.
+pub fn stmg(textures: Option<&[u32]>, trailing: &[u8]) -> Vec<u8> {
+    let mut body = vec![];
+    body.extend((-80f32).to_le_bytes());
+    body.extend(0x100u16.to_le_bytes());
+    body.extend(0x40u16.to_le_bytes());
+    // State groups, switch groups and ramping params
+    body.extend([0u8; 0xC]);
+
+    if let Some(textures) = textures {
+        body.extend((textures.len() as u32).to_le_bytes());
+        for texture in textures {
+            body.extend(texture.to_le_bytes());
+            body.extend([0u8; 0x18]);
+        }
+    }
+    body.extend(trailing);
+
+    section(b"STMG", &body)
+}
+
+/// The layout shared by the LFO, envelope and time modulators.
+pub fn modulator(props: &[(u8, f32)], ranges: &[(u8, f32, f32)], rtpc_id: u32) -> Vec<u8> {
+    let mut body = prop_bundle(props);
+
+    body.push(ranges.len() as u8);
+    for (prop_id, min, max) in ranges {
+        body.push(*prop_id);
+        body.extend(min.to_le_bytes());
+        body.extend(max.to_le_bytes());
+    }
+
+    body.extend(initial_rtpc(rtpc_id));
+    body
+}
+
+/// An InitialRTPC with a single two point game parameter curve.
+pub fn initial_rtpc(rtpc_id: u32) -> Vec<u8> {
+    let mut body = 1u16.to_le_bytes().to_vec();
+    body.extend(rtpc_id.to_le_bytes());
+    // Game parameter, exclusive, volume
+    body.extend([0x0, 0x1, 0x0]);
+    body.extend(0x1234u32.to_le_bytes());
+    // dB scaling
+    body.push(0x2);
+    body.extend(2u16.to_le_bytes());
+    for (from, to) in [(0f32, -96f32), (100.0, 0.0)] {
+        body.extend(from.to_le_bytes());
+        body.extend(to.to_le_bytes());
+        // Linear
+        body.extend(0x4u32.to_le_bytes());
+    }
+    body
+}
+
+/// A body for every HIRC object type, keyed by body type. Every list and
+/// optional field holds something so that stale counts show up on export.
+pub fn every_object_type() -> Vec<(u8, Vec<u8>)> {
+    vec![
+        (1, state(&[(0x00, -6.0), (0x03, 0.5)])),
+        (2, sound_with_states(&[2, 1])),
+        (3, action_play(2, 0xB4D)),
+        (4, event(&[4, 5])),
+        (5, random_sequence_container(&[2, 3])),
+        (6, switch_container(&[2, 3])),
+        (7, actor_mixer(&[2, 3])),
+        (8, bus(0)),
+        (9, layer_container(&[2, 3])),
+        (10, music_segment(&[11])),
+        (11, music_track(100)),
+        (12, music_switch_container(&[10])),
+        (13, music_random_sequence_container(&[10])),
+        (14, attenuation()),
+        (15, dialogue_event(1000)),
+        (16, fx_base_initial_values(0x00870003)),
+        (17, fx_base_initial_values(0x00690003)),
+        (18, bus(1)),
+        (19, modulator(&[(0x02, 50.0)], &[(0x02, -10.0, 10.0)], 0x10)),
+        (20, modulator(&[(0x09, 10.0)], &[], 0x11)),
+        (21, fx_base_initial_values(0x00B50007)),
+        (22, modulator(&[(0x04, 2.5)], &[(0x04, 0.0, 5.0)], 0x12)),
+    ]
+}
+
+pub fn state(props: &[(u16, f32)]) -> Vec<u8> {
+    let mut body = (props.len() as u16).to_le_bytes().to_vec();
+    body.extend(props.iter().flat_map(|p| p.0.to_le_bytes()));
+    body.extend(props.iter().flat_map(|p| p.1.to_le_bytes()));
+    body
+}
+
+pub fn children(ids: &[u32]) -> Vec<u8> {
+    let mut body = (ids.len() as u32).to_le_bytes().to_vec();
+    body.extend(ids.iter().flat_map(|id| id.to_le_bytes()));
+    body
+}
+
+pub fn random_sequence_container(ids: &[u32]) -> Vec<u8> {
+    let mut body = node_base_params(0, 0, &[]);
+    // Loop count and modifiers
+    body.extend([1u16, 0, 0].iter().flat_map(|v| v.to_le_bytes()));
+    // Transition time and modifiers
+    body.extend([1000f32, 0.0, 0.0].iter().flat_map(|v| v.to_le_bytes()));
+    body.extend(1u16.to_le_bytes());
+    // Transition mode, random mode, mode and flags
+    body.extend([0x0, 0x0, 0x1, 0x0]);
+    body.extend(children(ids));
+    body.extend((ids.len() as u16).to_le_bytes());
+    for id in ids {
+        body.extend(id.to_le_bytes());
+        body.extend(50000i32.to_le_bytes());
+    }
+    body
+}
+
+pub fn switch_container(ids: &[u32]) -> Vec<u8> {
+    let mut body = node_base_params(0, 0, &[]);
+    body.push(0x0);
+    body.extend(0x20u32.to_le_bytes());
+    body.extend(0x21u32.to_le_bytes());
+    body.push(0x1);
+    body.extend(children(ids));
+
+    // One switch per child
+    body.extend((ids.len() as u32).to_le_bytes());
+    for (i, id) in ids.iter().enumerate() {
+        body.extend((0x21 + i as u32).to_le_bytes());
+        body.extend(children(&[*id]));
+    }
+
+    body.extend((ids.len() as u32).to_le_bytes());
+    for id in ids {
+        body.extend(id.to_le_bytes());
+        // Flags, fade out and fade in
+        body.extend([0x0, 0x0]);
+        body.extend(0i32.to_le_bytes());
+        body.extend(0i32.to_le_bytes());
+    }
+    body
+}
+
+pub fn actor_mixer(ids: &[u32]) -> Vec<u8> {
+    let mut body = node_base_params(0, 0, &[]);
+    body.extend(children(ids));
+    body
+}
+
+pub fn layer_container(ids: &[u32]) -> Vec<u8> {
+    let mut body = node_base_params(0, 0, &[]);
+    body.extend(children(ids));
+    body.extend(1u32.to_le_bytes());
+    body.extend(0x30u32.to_le_bytes());
+    body.extend(initial_rtpc(0x31));
+    body.extend(0x31u32.to_le_bytes());
+    body.push(0x0);
+
+    body.extend((ids.len() as u32).to_le_bytes());
+    for id in ids {
+        body.extend(id.to_le_bytes());
+        body.extend(graph_points(&[(0.0, 0.0), (100.0, 1.0)]));
+    }
+
+    body.push(0x1);
+    body
+}
+
+/// Graph points with linear interpolation, prefixed by a u32 count.
+pub fn graph_points(points: &[(f32, f32)]) -> Vec<u8> {
+    let mut body = (points.len() as u32).to_le_bytes().to_vec();
+    for (from, to) in points {
+        body.extend(from.to_le_bytes());
+        body.extend(to.to_le_bytes());
+        body.extend(0x4u32.to_le_bytes());
+    }
+    body
+}
+
+pub fn music_node_params(ids: &[u32]) -> Vec<u8> {
+    let mut body = vec![0x0];
+    body.extend(node_base_params(0, 0, &[]));
+    body.extend(children(ids));
+    // AkMeterInfo, 4/4 at 120 BPM
+    body.extend(0.5f64.to_le_bytes());
+    body.extend(0f64.to_le_bytes());
+    body.extend(120f32.to_le_bytes());
+    body.extend([0x4, 0x4, 0x0]);
+
+    body.extend(1u32.to_le_bytes());
+    body.extend(0x40u32.to_le_bytes());
+    body.extend(ids.first().unwrap_or(&0).to_le_bytes());
+    // Next bar
+    body.extend(0x3u32.to_le_bytes());
+    body.extend(0u32.to_le_bytes());
+    body.extend(0i32.to_le_bytes());
+    body.extend(0u32.to_le_bytes());
+    body
+}
+
+pub fn music_trans_node_params(ids: &[u32]) -> Vec<u8> {
+    let mut body = music_node_params(ids);
+    body.extend(1u32.to_le_bytes());
+
+    // Any source to any destination
+    body.extend(1u32.to_le_bytes());
+    body.extend((-1i32).to_le_bytes());
+    body.extend(1u32.to_le_bytes());
+    body.extend((-1i32).to_le_bytes());
+
+    // AkMusicTransSrcRule
+    body.extend(0i32.to_le_bytes());
+    body.extend(0x4u32.to_le_bytes());
+    body.extend(0i32.to_le_bytes());
+    body.extend(0x0u32.to_le_bytes());
+    body.extend(0u32.to_le_bytes());
+    body.push(0x0);
+
+    // AkMusicTransDstRule
+    body.extend(0i32.to_le_bytes());
+    body.extend(0x4u32.to_le_bytes());
+    body.extend(0i32.to_le_bytes());
+    body.extend(0u32.to_le_bytes());
+    body.extend(0i32.to_le_bytes());
+    body.extend(0u16.to_le_bytes());
+    body.extend(0u16.to_le_bytes());
+    body.extend([0x0, 0x0]);
+
+    // AkMusicTransitionObject
+    body.push(0x1);
+    body.extend(ids.first().unwrap_or(&0).to_le_bytes());
+    for _ in 0..2 {
+        body.extend(500i32.to_le_bytes());
+        body.extend(0x4u32.to_le_bytes());
+        body.extend(0i32.to_le_bytes());
+    }
+    body.extend([0x0, 0x0]);
+    body
+}
+
+pub fn music_segment(ids: &[u32]) -> Vec<u8> {
+    let mut body = music_node_params(ids);
+    body.extend(4000f64.to_le_bytes());
+
+    body.extend(2u32.to_le_bytes());
+    body.extend(0x50u32.to_le_bytes());
+    body.extend(0f64.to_le_bytes());
+    body.extend(0u32.to_le_bytes());
+    body.extend(0x51u32.to_le_bytes());
+    body.extend(4000f64.to_le_bytes());
+    body.extend(4u32.to_le_bytes());
+    body.extend(b"End\0");
+    body
+}
+
+pub fn music_track(source_id: u32) -> Vec<u8> {
+    let mut body = vec![0x0];
+    body.extend(1u32.to_le_bytes());
+    body.extend(bank_source_data(0x00040001, &[], source_id));
+
+    body.extend(1u32.to_le_bytes());
+    body.extend(0u32.to_le_bytes());
+    body.extend(source_id.to_le_bytes());
+    body.extend(0u32.to_le_bytes());
+    body.extend([0f64, 0.0, 0.0, 4000.0].iter().flat_map(|v| v.to_le_bytes()));
+    // Subtrack count
+    body.extend(1u32.to_le_bytes());
+
+    body.extend(1u32.to_le_bytes());
+    body.extend(0u32.to_le_bytes());
+    // Fade in
+    body.extend(0x2u32.to_le_bytes());
+    body.extend(graph_points(&[(0.0, 0.0), (1.0, 1.0)]));
+
+    body.extend(node_base_params(0, 10, &[]));
+    body.push(0x0);
+    body.extend(0i32.to_le_bytes());
+    body
+}
+
+pub fn music_switch_container(ids: &[u32]) -> Vec<u8> {
+    let mut body = music_trans_node_params(ids);
+    body.push(0x1);
+    body.extend(decision_tree(ids));
+    body
+}
+
+pub fn music_random_sequence_container(ids: &[u32]) -> Vec<u8> {
+    let mut body = music_trans_node_params(ids);
+
+    body.extend((ids.len() as u32).to_le_bytes());
+    for (i, id) in ids.iter().enumerate() {
+        body.extend(id.to_le_bytes());
+        body.extend((i as i32).to_le_bytes());
+        body.extend(0u32.to_le_bytes());
+        body.extend(0u32.to_le_bytes());
+        body.extend([1i16, 0, 0].iter().flat_map(|v| v.to_le_bytes()));
+        body.extend(50000u32.to_le_bytes());
+        body.extend(0u16.to_le_bytes());
+        body.extend([0x0, 0x0]);
+    }
+    body
+}
+
+/// A single argument decision tree with a leaf per node and the trailing tree
+/// mode.
+pub fn decision_tree(node_ids: &[u32]) -> Vec<u8> {
+    let mut tree = vec![];
+    tree.extend(0u32.to_le_bytes());
+    tree.extend(1u16.to_le_bytes());
+    tree.extend((node_ids.len() as u16).to_le_bytes());
+    tree.extend([50u16, 100].iter().flat_map(|v| v.to_le_bytes()));
+    for (i, id) in node_ids.iter().enumerate() {
+        tree.extend((0x60 + i as u32).to_le_bytes());
+        tree.extend(id.to_le_bytes());
+        tree.extend([50u16, 100].iter().flat_map(|v| v.to_le_bytes()));
+    }
+
+    let mut body = 1u32.to_le_bytes().to_vec();
+    body.extend(0x20u32.to_le_bytes());
+    body.push(0x0);
+    body.extend((tree.len() as u32).to_le_bytes());
+    body.extend(tree);
+    body.push(0x0);
+    body
+}
+
+pub fn attenuation() -> Vec<u8> {
+    // Cone disabled, only the distance volume curve
+    let mut body = vec![0x0];
+    body.extend([0x0, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF]);
+
+    body.push(1);
+    body.push(0x2);
+    body.extend(2u16.to_le_bytes());
+    for (from, to) in [(0f32, 0f32), (100.0, -96.0)] {
+        body.extend(from.to_le_bytes());
+        body.extend(to.to_le_bytes());
+        body.extend(0x4u32.to_le_bytes());
+    }
+
+    body.extend(initial_rtpc(0x70));
+    body
+}
+
+pub fn dialogue_event(node_id: u32) -> Vec<u8> {
+    let mut body = vec![100];
+    body.extend(decision_tree(&[node_id]));
+    body.extend(prop_bundle(&[(0x00, -3.0)]));
+    body.push(0x1);
+    body.push(0x00);
+    body.extend((-4f32).to_le_bytes());
+    body.extend((-2f32).to_le_bytes());
+    body
+}
+
+pub fn fx_base_initial_values(fx_id: u32) -> Vec<u8> {
+    let mut body = fx_id.to_le_bytes().to_vec();
+    body.extend(4u32.to_le_bytes());
+    body.extend(1f32.to_le_bytes());
+
+    body.push(1);
+    body.push(0x0);
+    body.extend(100u32.to_le_bytes());
+
+    body.extend(initial_rtpc(0x80));
+    // StateChunk
+    body.extend([0x0, 0x0]);
+
+    body.extend(1i16.to_le_bytes());
+    body.push(0x00);
+    body.push(0x0);
+    body.extend(1f32.to_le_bytes());
+    body
+}
+
+/// A Vorbis WEM header with the vorb data appended to the fmt chunk, the way
+/// newer Wwise versions write it. The audio packets are left out.
+pub fn vorbis_wem(channels: u16, sample_rate: u32, sample_count: u32, loop_points: Option<(u32, u32)>) -> Vec<u8> {
+    let mut fmt = vec![];
+    fmt.extend(0xFFFFu16.to_le_bytes());
+    fmt.extend(channels.to_le_bytes());
+    fmt.extend(sample_rate.to_le_bytes());
+    fmt.extend(0x4000u32.to_le_bytes());
+    // Block align and bits per sample
+    fmt.extend([0x0; 4]);
+    fmt.extend(0x30u16.to_le_bytes());
+    // Samples per block and channel mask
+    fmt.extend(0x0u16.to_le_bytes());
+    fmt.extend(0x3u32.to_le_bytes());
+    fmt.extend(sample_count.to_le_bytes());
+    fmt.resize(0x42, 0);
+
+    let mut smpl = vec![0u8; 0x3C];
+    if let Some((start, end)) = loop_points {
+        smpl[0x1C..0x20].copy_from_slice(&1u32.to_le_bytes());
+        smpl[0x2C..0x30].copy_from_slice(&start.to_le_bytes());
+        smpl[0x30..0x34].copy_from_slice(&end.to_le_bytes());
+    }
+
+    let mut body = b"WAVE".to_vec();
+    body.extend(section(b"fmt ", &fmt));
+    body.extend(section(b"smpl", &smpl));
+    body.extend(section(b"data", &[0x0; 0x10]));
+    section(b"RIFF", &body)
+}
+
+pub fn stid(entries: &[(u32, &[u8])]) -> Vec<u8> {
+    // UTF-8
+    let mut body = 1u32.to_le_bytes().to_vec();
+    body.extend((entries.len() as u32).to_le_bytes());
+    for (bnk_id, name) in entries {
+        body.extend(bnk_id.to_le_bytes());
+        body.push(name.len() as u8);
+        body.extend(*name);
+    }
+    section(b"STID", &body)
+}
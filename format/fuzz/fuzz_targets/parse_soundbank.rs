@@ -0,0 +1,9 @@
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+
+// Parsing arbitrary input may fail but must never panic or abort.
+fuzz_target!(|data: &[u8]| {
+    let _ = wwise_format::parse_soundbank(data);
+    let _ = wwise_format::parse_soundbank_lenient(data);
+});
@@ -1,41 +1,144 @@
+use std::error;
 use std::fs;
-use std::env;
-use std::io::Seek;
-use std::io::SeekFrom;
-use std::io::Write;
 use std::path;
-use std::io;
 use std::io::Read;
-use std::collections;
 
-use deku::DekuWrite;
-use deku::bitvec::BitVec;
-use wwise_format::DATASection;
-use wwise_format::DIDXDescriptor;
-use wwise_format::DIDXSection;
+use clap::Parser;
+#[cfg(feature = "parallel")]
+use rayon::prelude::*;
+use wwise_format::FNVDictionary;
 use wwise_format::ObjectId;
-use wwise_format::Section;
-use wwise_format::SectionBody;
 use wwise_format::Soundbank;
 
+#[derive(Parser)]
+#[command(author, version, about, long_about = None)]
+struct Arguments {
+    #[arg(required = true)]
+    /// Soundbanks to unpack, unpacked soundbanks to rebuild or directories to
+    /// search for soundbanks
+    inputs: Vec<path::PathBuf>,
+
+    #[arg(long)]
+    /// Also search subdirectories for soundbanks
+    recursive: bool,
+
+    #[arg(long)]
+    /// Directory to write the output to, defaults to next to the input
+    out: Option<path::PathBuf>,
+
+    #[arg(long)]
+    /// Dictionary to resolve object names with, defaults to the built-in one
+    dictionary: Option<path::PathBuf>,
+
+    #[arg(long)]
+    /// Keep the DIDX and DATA in the soundbank.json instead of writing the
+    /// WEMs to separate files
+    inline_media: bool,
+
+    #[arg(long)]
+    /// Original soundbank to take the media from when rebuilding, WEMs in the
+    /// unpacked soundbank replace the original ones with the same ID
+    media_from: Option<path::PathBuf>,
+
+    #[arg(long)]
+    /// Accept soundbanks with incorrect section and HIRC object sizes, the
+    /// sizes are corrected when rebuilding
+    repair: bool,
+}
+
 fn main() {
-    let args: Vec<String> = env::args().collect();
+    let args = Arguments::parse();
+
+    let dictionary = match &args.dictionary {
+        Some(p) => parse_dictionary(
+            &fs::read_to_string(p).expect("Could not read dictionary file"),
+        ),
+        None => parse_dictionary(include_str!("default_dictionary.txt")),
+    };
+
+    let original_media = args.media_from.as_ref().map(|p| {
+        let file_buffer = fs::read(p)
+            .expect("Could not read original soundbank");
+        wwise_format::parse_soundbank(&file_buffer)
+            .expect("Could not parse original soundbank")
+            .extract_media()
+    });
+
+    if let Some(out) = &args.out {
+        fs::create_dir_all(out)
+            .expect("Could not create output directory");
+    }
 
-    for path in args[1..].iter() {
-        let path = path::PathBuf::from(path);
-        let md = fs::metadata(&path).unwrap();
+    for path in args.inputs.iter() {
+        let md = fs::metadata(path).unwrap();
+        let out = args.out.as_deref();
 
         if md.is_file() {
-            handle_soundbank(path);
+            unpack_soundbank(path.clone(), out, &dictionary, args.inline_media, args.repair);
         } else if md.is_dir() {
-            handle_dir(path);
+            // An unpacked soundbank gets rebuilt, any other directory is
+            // searched for soundbanks to unpack.
+            if path.join("soundbank.json").is_file() {
+                handle_dir(path.clone(), out, original_media.as_deref());
+            } else {
+                handle_soundbank_dir(path.clone(), args.recursive, out, &dictionary, args.inline_media, args.repair);
+            }
         } else {
             panic!("Was unable to handle path {:?}", path);
         }
     }
 }
 
-pub type FNVDictionary = collections::HashMap<u32, String>;
+fn handle_soundbank_dir(
+    path: path::PathBuf,
+    recursive: bool,
+    out: Option<&path::Path>,
+    dictionary: &FNVDictionary,
+    inline_media: bool,
+    repair: bool,
+) {
+    let mut soundbanks = vec![];
+    find_soundbanks(&path, recursive, &mut soundbanks);
+
+    #[cfg(feature = "parallel")]
+    let soundbanks = soundbanks.into_par_iter();
+    #[cfg(not(feature = "parallel"))]
+    let soundbanks = soundbanks.into_iter();
+
+    soundbanks.for_each(|p| unpack_soundbank(p, out, dictionary, inline_media, repair));
+}
+
+/// Unpacks a single soundbank, reporting failures instead of stopping the
+/// rest of the batch.
+fn unpack_soundbank(
+    path: path::PathBuf,
+    out: Option<&path::Path>,
+    dictionary: &FNVDictionary,
+    inline_media: bool,
+    repair: bool,
+) {
+    if let Err(e) = handle_soundbank(&path, out, dictionary, inline_media, repair) {
+        eprintln!("Could not unpack {:?}: {}", path, e);
+    }
+}
+
+fn find_soundbanks(path: &path::Path, recursive: bool, result: &mut Vec<path::PathBuf>) {
+    let entries = fs::read_dir(path)
+        .expect("Could not read soundbank directory");
+
+    for entry in entries {
+        let entry_path = entry.unwrap().path();
+        let file_name = entry_path.to_string_lossy().to_string();
+
+        if entry_path.is_dir() {
+            if recursive {
+                find_soundbanks(&entry_path, recursive, result);
+            }
+        } else if file_name.ends_with(".bnk") && !file_name.ends_with(".created.bnk") {
+            result.push(entry_path);
+        }
+    }
+}
 
 pub fn parse_dictionary(input: &str) -> FNVDictionary {
     input.lines()
@@ -47,18 +150,41 @@ pub fn parse_dictionary(input: &str) -> FNVDictionary {
         .collect()
 }
 
-fn handle_soundbank(path: path::PathBuf) {
+/// Moves `path` into `out` if an output directory was given.
+fn relocate(path: path::PathBuf, out: Option<&path::Path>) -> path::PathBuf {
+    match out {
+        Some(out) => out.join(path.file_name().unwrap()),
+        None => path,
+    }
+}
+
+fn handle_soundbank(
+    path: &path::Path,
+    out: Option<&path::Path>,
+    dictionary: &FNVDictionary,
+    inline_media: bool,
+    repair: bool,
+) -> Result<(), Box<dyn error::Error>> {
     // Parse the soundbank
     let mut soundbank = {
-        let mut handle = fs::File::open(&path)
-            .expect("Could not acquire read file handle");
+        let mut handle = fs::File::open(path)
+            .map_err(|e| format!("Could not acquire read file handle: {}", e))?;
 
         let mut file_buffer = vec![];
         handle.read_to_end(&mut file_buffer)
-            .expect("Could not read input file");
-
-        wwise_format::parse_soundbank(&file_buffer)
-            .expect("Could not parse bnk")
+            .map_err(|e| format!("Could not read input file: {}", e))?;
+
+        let parsed = match repair {
+            true => wwise_format::parse_soundbank_repair(&file_buffer).map(|(s, warnings)| {
+                for warning in warnings {
+                    eprintln!("{:?} at {:#x}: {}", path, warning.offset, warning.message);
+                }
+                s
+            }),
+            false => wwise_format::parse_soundbank(&file_buffer),
+        };
+
+        parsed.map_err(|e| format!("Could not parse soundbank: {}", e))?
     };
 
     // Create output directory
@@ -67,81 +193,53 @@ fn handle_soundbank(path: path::PathBuf) {
             .to_path_buf();
 
         p.push(path.file_stem().unwrap());
+        let p = relocate(p, out);
 
         fs::create_dir_all(&p)
-            .expect("Could not create output directory");
+            .map_err(|e| format!("Could not create output directory: {}", e))?;
 
         p
     };
 
-    {
-        // Acquire DIDX and the DATA
-        let didx = &soundbank.sections.iter()
-            .find_map(|s| match &s.body {
-                wwise_format::SectionBody::DIDX(s) => Some(s),
-                _ => None,
-            });
-        let data = &soundbank.sections.iter()
-            .find_map(|s| match &s.body {
-                wwise_format::SectionBody::DATA(s) => Some(s),
-                _ => None,
-            });
-
-        // If both are available start carving
-        if didx.is_some() && data.is_some() {
-            let didx = didx.unwrap();
-            let data = data.unwrap();
-
-            for descriptor in didx.descriptors.iter() {
-                let mut file_path = output_dir.clone();
-                file_path.push(format!("{}.wem", descriptor.id));
-
-                let start = descriptor.offset as usize;
-                let end = start + descriptor.size as usize;
-
-                let bytes = &data.data[start..end];
-                fs::write(file_path, bytes)
-                    .expect("Could not write WEM to output directory");
-            }
+    // Inlined media stays in the JSON as base64
+    if !inline_media {
+        for (id, bytes) in soundbank.extract_media() {
+            let mut file_path = output_dir.clone();
+            file_path.push(format!("{}.wem", id));
+
+            fs::write(file_path, bytes)
+                .map_err(|e| format!("Could not write WEM to output directory: {}", e))?;
         }
-    }
 
-    // Remove DIDX and DATA from JSON output
-    soundbank.sections
-        .retain(|s| match &s.body {
-            wwise_format::SectionBody::DIDX(_) => false,
-            wwise_format::SectionBody::DATA(_) => false,
-            _ => true,
-        });
+        // Remove DIDX and DATA from JSON output
+        soundbank.sections
+            .retain(|s| !matches!(
+                &s.body,
+                wwise_format::SectionBody::DIDX(_) | wwise_format::SectionBody::DATA(_)
+            ));
+    }
 
     // Make object IDs easier to read by mapping them against a dictionary
 
-    let dictionary = parse_dictionary(include_str!("default_dictionary.txt"));
-    soundbank.sections.iter_mut()
-        .find_map(|s| match &mut s.body {
-            SectionBody::HIRC(h) => Some(h),
-            _ => None,
-        })
-        .map(|h| {
-            for object in h.objects.iter_mut() {
-                object.id = match dictionary.get(&object.id.as_hash()) {
-                    Some(s) => ObjectId::String(s.to_string()),
-                    None => object.id.clone(),
-                };
-            }
-        });
+    soundbank.resolve_names(dictionary);
 
     // Create the soundbank.json
     let mut json_path = output_dir.clone();
     json_path.push("soundbank.json");
     let handle = fs::File::create(&json_path)
-        .expect("could not acquire write file handle");
+        .map_err(|e| format!("Could not acquire write file handle: {}", e))?;
 
     serde_json::to_writer_pretty(handle, &soundbank)
-        .expect("could not write json to output file");
+        .map_err(|e| format!("Could not write json to output file: {}", e))?;
+
+    Ok(())
 }
 
-fn handle_dir(path: path::PathBuf) {
+fn handle_dir(
+    path: path::PathBuf,
+    out: Option<&path::Path>,
+    original_media: Option<&[(u32, Vec<u8>)]>,
+) {
     // Parse soundbank JSON
     let mut soundbank = {
         let mut json_path = path.clone();
@@ -154,8 +252,45 @@ fn handle_dir(path: path::PathBuf) {
             .expect("Could not deserialize input into a soundbank")
     };
 
+    // Soundbanks unpacked with --inline-media still carry their DATA
+    if soundbank.data().is_none() {
+        let wem_alignment = soundbank.bkhd()
+            .expect("Soundbank needs a BKDH section")
+            .wem_alignment;
+
+        let mut media = original_media.unwrap_or_default().to_vec();
+        for (id, bytes) in read_wems(&path) {
+            match media.iter_mut().find(|(i, _)| *i == id) {
+                Some(existing) => existing.1 = bytes,
+                None => media.push((id, bytes)),
+            }
+        }
+
+        soundbank.replace_media(&media, wem_alignment);
+    }
+
+    // Prepare soundbank JSON repr for its bin equivalent
+    if let Err(e) = wwise_format::prepare_soundbank(&mut soundbank) {
+        eprintln!("Could not rebuild {:?}: {}", path, e);
+        return;
+    }
+
+    // Make output bnk file
+    let mut bnk_path = path.clone();
+    bnk_path.set_extension("created.bnk");
+    let bnk_path = relocate(bnk_path, out);
+
+    let mut handle = fs::File::create(&bnk_path)
+        .expect("Could not acquire write file handle");
+
+    soundbank.to_writer(&mut handle)
+        .expect("Could not write soundbank to result file");
+}
+
+/// Reads the loose WEMs of an unpacked soundbank, sorted by ID.
+fn read_wems(path: &path::Path) -> Vec<(u32, Vec<u8>)> {
     // Get a directory listing
-    let files = fs::read_dir(&path)
+    let files = fs::read_dir(path)
         .expect("Could not read unpacked soundbank director")
         .map(|f| f.unwrap().file_name().to_string_lossy().to_string())
         .collect::<Vec<String>>();
@@ -173,103 +308,13 @@ fn handle_dir(path: path::PathBuf) {
         }
     );
 
-    // Rebuild the DIDX and the DATA
-    let mut descriptors = Vec::new();
-    let mut data = Vec::new();
-    let mut cursor = io::Cursor::new(&mut data);
-
-    // Obtain the WEM alignment
-    let wem_alignment = soundbank.sections.iter()
-        .find_map(|f| match &f.body {
-            SectionBody::BKHD(b) => Some(b),
-            _ => None,
+    wems.iter()
+        .map(|wem| {
+            let id = wem.replace(".wem", "").parse::<u32>()
+                .expect("Could not parse WEM name to WEM ID");
+            let bytes = fs::read(path.join(wem))
+                .expect("Could not read WEM file");
+            (id, bytes)
         })
-        .expect("Soundbank needs a BKDH section")
-        .wem_alignment;
-
-    for (i, wem) in wems.iter().enumerate() {
-        let id = wem.replace(".wem", "").parse::<u32>()
-            .expect("Could not parse WEM name to WEM ID");
-        let offset = cursor.seek(SeekFrom::Current(0)).unwrap() as u32;
-        let wem_path = format!("{}/{}", path.to_string_lossy(), wem);
-
-        // Write WEM bytes to DATA section buffer
-        let file_bytes = fs::read(wem_path)
-            .expect("Could not read WEM file");
-
-        cursor.write_all(&file_bytes)
-            .expect("Could not write WEM to DATA buffer");
-
-        let current_pos = cursor.seek(SeekFrom::Current(0))
-            .expect("Could not seek") as u32;
-        let padded_position = (current_pos + wem_alignment - 1) & !(wem_alignment - 1); 
-        let bytes_to_pad = padded_position - current_pos;
-
-        // Last WEM entry has no padding
-        if i != wems.len() - 1 {
-            for _ in 0..bytes_to_pad {
-                cursor.write(&[0]).expect("Could not write padding byte");
-            }
-        }
-
-        let size = file_bytes.len() as u32;
-        descriptors.push(DIDXDescriptor {
-            id,
-            offset,
-            size,
-        });
-    }
-
-    if descriptors.len() > 0 {
-        let didx = DIDXSection { descriptors };
-        let data = DATASection { data };
-
-        // Put the DIDX and the DATA after the BKHD but before anythign elsee
-        // TODO: could use a deque instead of a vec?
-        let mut sections = soundbank.sections;
-
-        // Grab the BKHD
-        sections.rotate_left(1);
-        let bkhd = sections.pop().unwrap();
-
-        // Append and rotate the DATA
-        sections.push(Section {
-            magic: [0x0; 4],
-            size: 0,
-            body: SectionBody::DATA(data),
-        });
-        sections.rotate_right(1);
-
-        // Append and rotate the DIDX
-        sections.push(Section {
-            magic: [0x0; 4],
-            size: 0,
-            body: SectionBody::DIDX(didx),
-        });
-        sections.rotate_right(1);
-
-        // Readd the NKHD
-        sections.push(bkhd);
-        sections.rotate_right(1);
-
-        soundbank.sections = sections;
-    }
-
-    // Prepare soundbank JSON repr for its bin equivalent
-    wwise_format::prepare_soundbank(&mut soundbank);
-
-    // Write the soundbank to the bin buffer
-    let mut soundbank_bytes = BitVec::default();
-    soundbank.write(&mut soundbank_bytes, ())
-        .expect("Could not encode soundbank to bytes");
-
-    // Make output bnk file
-    let mut bnk_path = path.clone();
-    bnk_path.set_extension("created.bnk");
-
-    let mut handle = fs::File::create(&bnk_path)
-        .expect("Could not acquire write file handle");
-
-    handle.write_all(soundbank_bytes.as_raw_slice())
-        .expect("Could not write to result file");
+        .collect()
 }
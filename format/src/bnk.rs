@@ -1,7 +1,8 @@
 use std::ffi;
 use std::num::Wrapping;
 
-use deku::bitvec::{BitSlice, BitVec, Msb0};
+use deku::bitvec::{BitSlice, BitVec, BitView, Msb0};
+use deku::error::NeedSize;
 use deku::prelude::*;
 use serde::{Deserialize, Serialize};
 
@@ -9,33 +10,89 @@ use serde::{Deserialize, Serialize};
 pub enum ObjectId {
     String(String),
     Hash(u32),
+    /// A hash from `create_hash64`, as used by the string tables of newer
+    /// Wwise versions. HIRC objects are addressed by 32-bit hashes so these
+    /// can't be encoded as an object's ID.
+    Hash64(u64),
 }
 
 impl ObjectId {
+    /// The 32-bit hash of the ID. A `Hash64` has no 32-bit counterpart and
+    /// is cut down to its lower half.
     pub fn as_hash(&self) -> u32 {
         match self {
             ObjectId::String(s) => create_hash(s),
-            ObjectId::Hash(h) => h.clone(),
+            ObjectId::Hash(h) => *h,
+            ObjectId::Hash64(h) => *h as u32,
+        }
+    }
+
+    /// The 64-bit hash of the ID, `None` for a 32-bit hash as the name it
+    /// came from is unknown.
+    pub fn as_hash64(&self) -> Option<u64> {
+        match self {
+            ObjectId::String(s) => Some(create_hash64(s)),
+            ObjectId::Hash(_) => None,
+            ObjectId::Hash64(h) => Some(*h),
         }
     }
 
     fn write(
         output: &mut BitVec<u8, Msb0>,
-        value: &Self
+        value: &Self,
+        endian: deku::ctx::Endian,
     ) -> Result<(), DekuError> {
+        if let ObjectId::Hash64(h) = value {
+            return Err(DekuError::InvalidParam(format!("64-bit hash {} can not be used as an object ID", h)));
+        }
+
         let hash = value.as_hash();
-        u32::write(&hash, output, ())?;
+        u32::write(&hash, output, endian)?;
         Ok(())
     }
 
     fn read(
         rest: &BitSlice<u8, Msb0>,
+        endian: deku::ctx::Endian,
     ) -> Result<(&BitSlice<u8, Msb0>, Self), DekuError> {
-        let (r, v) = u32::read(rest, ())?;
+        let (r, v) = u32::read(rest, endian)?;
         Ok((r, Self::Hash(v)))
     }
 }
 
+/// Prints the name if the ID carries one, the hash in decimal otherwise.
+impl std::fmt::Display for ObjectId {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ObjectId::String(s) => write!(f, "{}", s),
+            ObjectId::Hash(h) => write!(f, "{}", h),
+            ObjectId::Hash64(h) => write!(f, "{}", h),
+        }
+    }
+}
+
+/// Reads decimal and `0x` prefixed hexadecimal numbers as a hash, anything
+/// else is taken to be a name. Numbers that don't fit in 32 bits are read as
+/// a `Hash64`.
+impl std::str::FromStr for ObjectId {
+    type Err = std::convert::Infallible;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let hash = match s.strip_prefix("0x").or_else(|| s.strip_prefix("0X")) {
+            Some(hex) => u64::from_str_radix(hex, 16).ok(),
+            None => s.parse().ok(),
+        };
+
+        Ok(match hash {
+            Some(h) => match u32::try_from(h) {
+                Ok(h) => ObjectId::Hash(h),
+                Err(_) => ObjectId::Hash64(h),
+            },
+            None => ObjectId::String(s.to_string()),
+        })
+    }
+}
+
 const FNV_BASE: Wrapping<u32> = Wrapping(2166136261);
 const FNV_PRIME: Wrapping<u32> = Wrapping(16777619);
 
@@ -52,38 +109,892 @@ pub fn create_hash(input: &str) -> u32 {
     result.0
 }
 
+const FNV_BASE_64: Wrapping<u64> = Wrapping(14695981039346656037);
+const FNV_PRIME_64: Wrapping<u64> = Wrapping(1099511628211);
+
+/// The 64-bit variant of `create_hash`, used by newer string tables.
+pub fn create_hash64(input: &str) -> u64 {
+    let input_lower = input.to_ascii_lowercase();
+    let input_buffer = input_lower.as_bytes();
+
+    let mut result = FNV_BASE_64;
+    for byte in input_buffer {
+        result *= FNV_PRIME_64;
+        result ^= *byte as u64;
+    }
+
+    result.0
+}
+
 #[cfg(test)]
 mod test {
-    use crate::ObjectId;
+    use deku::bitvec::{BitVec, BitView};
+
+    use crate::*;
+    use wwise_fixtures as fixtures;
 
     #[test]
     fn hashes_properly() {
         assert!(ObjectId::String("Play_c407001000".to_string()).as_hash() == 1834890111);
     }
+
+    #[test]
+    fn hashes_64_bit() {
+        assert_eq!(create_hash("Play_c407001000"), 1834890111);
+        assert_eq!(create_hash64("Play_c407001000"), 14184971900114103007);
+        assert_eq!(create_hash64("PLAY_C407001000"), create_hash64("play_c407001000"));
+    }
+
+    fn tree_node(key: u32, value: u32, weight: u16) -> Vec<u8> {
+        let mut result = key.to_le_bytes().to_vec();
+        result.extend(value.to_le_bytes());
+        result.extend(weight.to_le_bytes());
+        result.extend(100u16.to_le_bytes());
+        result
+    }
+
+    fn branch(key: u32, index: u16, count: u16) -> Vec<u8> {
+        tree_node(key, index as u32 | (count as u32) << 16, 50)
+    }
+
+    // Two arguments: a switch (10 or 11) and a state (20, or any other).
+    fn dialogue_event(mode: AkDecisionTreeMode) -> CAkDialogueEvent {
+        let tree_data = [
+            branch(0, 1, 3),
+            branch(10, 4, 2),
+            branch(11, 6, 1),
+            branch(0, 7, 1),
+            tree_node(20, 1000, 50),
+            tree_node(0, 1001, 60),
+            tree_node(20, 1002, 50),
+            tree_node(0, 1003, 50),
+        ].concat();
+
+        CAkDialogueEvent {
+            probability: 100,
+            tree_depth: 2,
+            arguments: vec![AkGameSync { group_id: 1 }, AkGameSync { group_id: 2 }],
+            group_types: vec![AkGroupType::Switch, AkGroupType::State],
+            tree_size: tree_data.len() as u32,
+            tree_data,
+            tree_mode: mode,
+            prop_bundle: vec![],
+            ranged_modifiers: PropRangedModifiers { count: 0, entries: vec![] },
+        }
+    }
+
+    fn stmg_bank(version: u32, textures: Option<&[u32]>) -> Vec<u8> {
+        let mut bytes = fixtures::bkhd(version, 0xB4D, 0x10, 0);
+        bytes.extend(fixtures::stmg(textures, &[]));
+        bytes.extend(fixtures::hirc(&[]));
+        bytes
+    }
+
+    #[test]
+    fn object_id_parses_and_prints() {
+        assert_eq!("0x1A2B".parse(), Ok(ObjectId::Hash(0x1A2B)));
+        assert_eq!("6699".parse(), Ok(ObjectId::Hash(6699)));
+        assert_eq!("Play_Footstep".parse(), Ok(ObjectId::String("Play_Footstep".to_string())));
+        // Not a valid number either way
+        assert_eq!("0xZZ".parse(), Ok(ObjectId::String("0xZZ".to_string())));
+
+        assert_eq!(ObjectId::Hash(0x1A2B).to_string(), "6699");
+        assert_eq!(ObjectId::String("Play_Footstep".to_string()).to_string(), "Play_Footstep");
+
+        let hash64 = create_hash64("Play_c407001000");
+        assert_eq!(hash64.to_string().parse(), Ok(ObjectId::Hash64(hash64)));
+        assert_eq!(format!("{:#x}", hash64).parse(), Ok(ObjectId::Hash64(hash64)));
+        assert_eq!(ObjectId::Hash64(hash64).to_string(), hash64.to_string());
+        assert_eq!(ObjectId::String("Play_c407001000".to_string()).as_hash64(), Some(hash64));
+        assert_eq!(ObjectId::Hash(1).as_hash64(), None);
+
+        let json = serde_json::to_string(&ObjectId::Hash64(hash64)).unwrap();
+        assert_eq!(serde_json::from_str::<ObjectId>(&json).unwrap(), ObjectId::Hash64(hash64));
+
+        // Can't be written into a HIRC object
+        let mut soundbank = parse_soundbank(&fixtures::soundbank(0x10)).unwrap();
+        soundbank.hirc_mut().unwrap().objects[0].id = ObjectId::Hash64(hash64);
+        assert!(soundbank.to_prepared_bytes().is_err());
+    }
+
+    #[test]
+    fn positioning_paths_slice_vertices() {
+        let vertex = |x| AkPathVertex { x, y: 0.0, z: 0.0, duration: 1000 };
+        let offsets = |vertices_offset, vertices_count| AkPathListItemOffset { vertices_offset, vertices_count };
+        let params = PositioningParams {
+            unk1: false,
+            three_dimensional_position_type: Ak3DPositionType::EmitterWithAutomation,
+            speaker_panning_type: AkSpeakerPanningType::DirectSpeakerAssignment,
+            listener_relative_routing: true,
+            override_parent: true,
+            unk2: false,
+            enable_diffraction: false,
+            hold_listener_orientation: false,
+            hold_emitter_position_and_orientation: false,
+            enable_attenuation: true,
+            three_dimensional_spatialization_mode: Ak3DSpatializationMode::PositionOnly,
+            path_mode: AkPathMode::StepSequence,
+            transition_time: 0,
+            vertex_count: 3,
+            vertices: vec![vertex(1.0), vertex(2.0), vertex(3.0)],
+            path_list_item_count: 3,
+            // The first two paths share the middle vertex
+            path_list_item_offsets: vec![offsets(0, 2), offsets(1, 2), offsets(2, 5)],
+            three_dimensional_automation_params: vec![],
+        };
+
+        let paths = params.paths().iter()
+            .map(|p| p.iter().map(|v| v.x).collect::<Vec<_>>())
+            .collect::<Vec<_>>();
+        assert_eq!(paths, vec![vec![1.0, 2.0], vec![2.0, 3.0], vec![]]);
+    }
+
+    #[test]
+    fn stmg_without_textures_roundtrips() {
+        let bytes = stmg_bank(0x71, None);
+        let soundbank = parse_soundbank(&bytes).unwrap();
+
+        // The HIRC header must not have been consumed as a texture count
+        assert_eq!(soundbank.sections.len(), 3);
+        assert!(matches!(soundbank.sections[2].body, SectionBody::HIRC(_)));
+        assert_eq!(soundbank.to_bytes().unwrap(), bytes);
+    }
+
+    #[test]
+    fn stmg_with_textures_roundtrips() {
+        let bytes = stmg_bank(0x8C, Some(&[0x1234]));
+        let soundbank = parse_soundbank(&bytes).unwrap();
+
+        match &soundbank.sections[1].body {
+            SectionBody::STMG(s) => {
+                assert!(s.state_groups.is_empty() && s.switch_groups.is_empty());
+                assert_eq!(s.textures.len(), 1);
+                assert_eq!(s.textures[0].id, 0x1234);
+            },
+            _ => unreachable!(),
+        }
+        assert_eq!(soundbank.to_bytes().unwrap(), bytes);
+    }
+
+    #[test]
+    fn stmg_with_bytes_left_is_an_error() {
+        let mut bytes = fixtures::bkhd(0x91, 0xB4D, 0x10, 0);
+        bytes.extend(fixtures::stmg(Some(&[0x1234]), &[]));
+        bytes.extend(fixtures::hirc(&[]));
+        let soundbank = parse_soundbank(&bytes).unwrap();
+
+        match &soundbank.sections[1].body {
+            SectionBody::STMG(s) => {
+                assert_eq!(s.max_voice_instances, 0x100);
+                assert_eq!(s.max_num_dangerous_virt_voices_limit_internal, 0x40);
+            },
+            _ => unreachable!(),
+        }
+
+        // Newer fields such as the HDR settings aren't modeled yet, they
+        // shouldn't end up read as the next section.
+        let mut bytes = fixtures::bkhd(0x91, 0xB4D, 0x10, 0);
+        bytes.extend(fixtures::stmg(Some(&[0x1234]), &[0x01, 0x02, 0x03, 0x04, 0x05]));
+        assert!(matches!(parse_soundbank(&bytes), Err(DekuError::Parse(_))));
+    }
+
+    #[test]
+    fn container_traits_roundtrip() {
+        let bytes = fixtures::soundbank(0x10);
+        let ((rest, pad), soundbank) = Soundbank::from_bytes((&bytes, 0)).unwrap();
+
+        assert!(rest.is_empty());
+        assert_eq!(pad, 0);
+        assert_eq!(soundbank.to_bytes().unwrap(), bytes);
+    }
+
+    #[test]
+    fn malformed_input_is_an_error() {
+        // The texture count is missing from the section
+        assert!(parse_soundbank(&stmg_bank(0x8C, None)).is_err());
+
+        let bytes = fixtures::soundbank(0x10);
+        for end in 0..bytes.len() {
+            let _ = parse_soundbank(&bytes[..end]);
+            let _ = parse_soundbank_lenient(&bytes[..end]);
+        }
+    }
+
+    fn roundtrip_sound(body: &[u8]) -> CAkSound {
+        let bytes = fixtures::minimal_bank(&[fixtures::hirc_object(0x02, 1, body)]);
+
+        let soundbank = parse_soundbank(&bytes).unwrap();
+        assert_eq!(soundbank.to_bytes().unwrap(), bytes);
+
+        let mut sections = soundbank.sections;
+        match sections.remove(1).body {
+            SectionBody::HIRC(mut h) => match h.objects.remove(0).body {
+                HIRCObjectBody::Sound(s) => s,
+                _ => unreachable!(),
+            },
+            _ => unreachable!(),
+        }
+    }
+
+    #[test]
+    fn external_source_has_no_params() {
+        let sound = roundtrip_sound(&fixtures::sound_with_plugin(0x00080001, &[], 100, 1, 0));
+
+        assert!(sound.bank_source_data.is_external_source());
+        assert!(sound.bank_source_data.params.is_empty());
+        assert_eq!(sound.bank_source_data.media_information.source_id, 100);
+        assert_eq!(sound.node_base_params.override_bus_id, ObjectId::Hash(1));
+    }
+
+    #[test]
+    fn source_plugin_has_params() {
+        let params = [0x1, 0x2, 0x3, 0x4, 0x5];
+        let sound = roundtrip_sound(&fixtures::sound_with_plugin(0x00660002, &params, 0, 1, 0));
+
+        assert!(!sound.bank_source_data.is_external_source());
+        assert_eq!(sound.bank_source_data.params, params);
+        assert_eq!(sound.node_base_params.override_bus_id, ObjectId::Hash(1));
+    }
+
+    #[test]
+    fn state_groups_roundtrip() {
+        for state_count in [0, 254, 255] {
+            let sound = roundtrip_sound(&fixtures::sound_with_states(&[state_count, 1]));
+
+            let groups = &sound.node_base_params.state_chunk.state_group_chunks;
+            assert_eq!(groups.len(), 2);
+            assert_eq!(groups[0].states.len(), state_count);
+            assert_eq!(groups[1].states.len(), 1);
+        }
+    }
+
+    #[test]
+    fn state_group_overflow_is_an_error() {
+        use crate::export::PrepareExport;
+
+        let mut sound = roundtrip_sound(&fixtures::sound_with_states(&[255]));
+        sound.node_base_params.state_chunk.state_group_chunks[0].states
+            .push(AkState { state_id: 1, state_instance_id: 2 });
+
+        assert!(sound.prepare_export().is_err());
+    }
+
+    #[test]
+    fn data_section_size_is_checked() {
+        let mut bytes = fixtures::bkhd(0x8C, 0xB4D, 0x10, 0);
+        bytes.extend(fixtures::section(b"DATA", &[]));
+        let soundbank = parse_soundbank(&bytes).unwrap();
+        assert_eq!(soundbank.to_bytes().unwrap(), bytes);
+
+        // Claim a DATA section far larger than the input
+        let offset = bytes.len() - 4;
+        bytes[offset..].copy_from_slice(&0xFFFFFFF0u32.to_le_bytes());
+        bytes.extend([0xAA; 0x10]);
+        assert!(parse_soundbank(&bytes).is_err());
+    }
+
+    #[test]
+    fn meter_info_flag_keeps_unknown_bits() {
+        let mut meter = AkMeterInfo {
+            grid_period: 1000.0,
+            grid_offset: 0.0,
+            tempo: 120.0,
+            time_signature_beat_count: 4,
+            time_signature_beat_value: 4,
+            meter_info_flag: 0x80,
+        };
+        assert!(!meter.overrides_parent());
+
+        meter.set_overrides_parent(true);
+        assert!(meter.overrides_parent());
+        assert_eq!(meter.meter_info_flag, 0x81);
+
+        meter.set_overrides_parent(false);
+        assert_eq!(meter.meter_info_flag, 0x80);
+    }
+
+    #[test]
+    fn meter_info_converts_positions() {
+        let meter = AkMeterInfo {
+            grid_period: 2000.0,
+            grid_offset: 0.0,
+            tempo: 120.0,
+            time_signature_beat_count: 4,
+            time_signature_beat_value: 4,
+            meter_info_flag: 0x1,
+        };
+
+        assert_eq!(meter.ms_per_beat(), 500.0);
+        assert_eq!(meter.ms_per_bar(), 2000.0);
+        assert_eq!(meter.position_to_bars_beats(0.0), (0, 0.0));
+        assert_eq!(meter.position_to_bars_beats(5250.0), (2, 2.5));
+        assert_eq!(meter.position_to_bars_beats(2000.0), (1, 0.0));
+    }
+
+    #[test]
+    fn graph_point_mutators_keep_count() {
+        let point = |from: f32| AkRTPCGraphPoint {
+            from,
+            to: from * 2.0,
+            interpolation: AkCurveInterpolation::Linear,
+        };
+
+        let mut rtpc = RTPC {
+            id: 0x1234,
+            rtpc_type: AkRtpcType::GameParameter,
+            rtpc_accum: AkRtpcAccum::Exclusive,
+            param_id: 0x0,
+            curve_id: 0x5678,
+            curve_scaling: AkCurveScaling::None,
+            graph_point_count: 0,
+            graph_points: vec![],
+        };
+
+        assert_eq!(rtpc.add_graph_point(point(100.0)).unwrap(), 0);
+        assert_eq!(rtpc.add_graph_point(point(0.0)).unwrap(), 0);
+        assert_eq!(rtpc.add_graph_point(point(50.0)).unwrap(), 1);
+        assert_eq!(rtpc.remove_graph_point(2).unwrap().from, 100.0);
+        assert!(rtpc.remove_graph_point(2).is_none());
+
+        // Written without a prepare_export
+        let mut output = BitVec::default();
+        rtpc.write(&mut output, Endian::Little).unwrap();
+        let (_, parsed) = RTPC::read(output.as_bitslice(), Endian::Little).unwrap();
+        let from = parsed.graph_points.iter().map(|p| p.from).collect::<Vec<_>>();
+        assert_eq!(from, vec![0.0, 50.0]);
+
+        let mut table = CAkConversionTable {
+            curve_scaling: AkCurveScaling::DB,
+            point_count: u16::MAX - 1,
+            points: vec![point(0.0); (u16::MAX - 1) as usize],
+        };
+        assert!(table.add_point(point(1.0)).is_ok());
+        assert!(table.add_point(point(2.0)).is_err());
+        assert_eq!(table.points.len(), u16::MAX as usize);
+        assert_eq!(table.point_count, u16::MAX);
+    }
+
+    #[test]
+    fn unknown_prop_ids_roundtrip() {
+        let mut input = vec![0x2];
+        for (prop_id, min, max) in [(0x00u8, -3f32, 3f32), (0xF0, 0.0, 1.0)] {
+            input.push(prop_id);
+            input.extend(min.to_le_bytes());
+            input.extend(max.to_le_bytes());
+        }
+
+        let (_, parsed) = PropRangedModifiers::read(input.view_bits(), Endian::Little).unwrap();
+        assert!(matches!(parsed.entries[0].prop_type, AkPropID::Volume));
+        assert!(matches!(parsed.entries[1].prop_type, AkPropID::Unknown(0xF0)));
+        assert_eq!(parsed.entries[1].prop_type.id(), 0xF0);
+
+        let mut output = BitVec::default();
+        parsed.write(&mut output, Endian::Little).unwrap();
+        assert_eq!(output.into_vec(), input);
+    }
+
+    #[test]
+    fn unknown_prop_bundle_id_is_an_error() {
+        let input = fixtures::prop_bundle(&[(0x00, -3.0), (0xF0, 1.0)]);
+
+        match PropBundle::read_list(input.view_bits(), Endian::Little) {
+            Err(DekuError::Parse(e)) => assert!(e.contains("0xf0")),
+            _ => panic!("Expected a parse error"),
+        }
+    }
+
+    #[test]
+    fn big_endian_roundtrips() {
+        let little = fixtures::soundbank(0x10);
+        let mut soundbank = parse_soundbank(&little).unwrap();
+        assert_eq!(soundbank.endian, Endian::Little);
+
+        soundbank.endian = Endian::Big;
+        let big = soundbank.to_bytes().unwrap();
+        assert_eq!(big.len(), little.len());
+        assert_eq!(&big[8..12], &0x8Cu32.to_be_bytes());
+        assert_eq!(detect_endian(&big), Some(Endian::Big));
+
+        assert!(parse_soundbank(&big).is_err());
+        let reparsed = parse_soundbank_detect(&big).unwrap();
+        assert_eq!(reparsed.endian, Endian::Big);
+        assert_eq!(reparsed.hirc().unwrap().objects.len(), 5);
+        assert_eq!(reparsed.to_bytes().unwrap(), big);
+
+        let json = serde_json::to_value(&reparsed).unwrap();
+        assert_eq!(json["endian"], "big");
+        assert!(serde_json::to_value(parse_soundbank(&little).unwrap()).unwrap().get("endian").is_none());
+    }
+
+    fn roundtrip_object(body_type: u8, body: &[u8]) -> HIRCObjectBody {
+        let bytes = fixtures::minimal_bank(&[fixtures::hirc_object(body_type, 1, body)]);
+
+        let mut soundbank = parse_soundbank(&bytes).unwrap();
+        assert_eq!(soundbank.to_bytes().unwrap(), bytes);
+
+        // Sizes get recomputed from the encoded object
+        soundbank.visit_mut(&mut |o| o.size = 0);
+        crate::prepare_soundbank(&mut soundbank).unwrap();
+        assert_eq!(soundbank.to_bytes().unwrap(), bytes);
+
+        match soundbank.sections.remove(1).body {
+            SectionBody::HIRC(mut h) => h.objects.remove(0).body,
+            _ => unreachable!(),
+        }
+    }
+
+    #[test]
+    fn find_object_matches_by_hash() {
+        let mut soundbank = parse_soundbank(&fixtures::soundbank(0x10)).unwrap();
+        let hirc = soundbank.hirc_mut().unwrap();
+        hirc.objects[1].id = ObjectId::String("Play_Footstep".to_string());
+
+        let named = ObjectId::Hash(create_hash("Play_Footstep"));
+        assert!(matches!(hirc.find_object(&named).unwrap().body, HIRCObjectBody::Sound(_)));
+
+        let sound = hirc.find_object_mut(&ObjectId::Hash(3)).unwrap();
+        sound.body.node_base_params_mut().unwrap().direct_parent_id = ObjectId::Hash(1);
+
+        let reparsed = parse_soundbank(&soundbank.to_bytes().unwrap()).unwrap();
+        let sound = reparsed.hirc().unwrap().find_object(&ObjectId::Hash(3)).unwrap();
+        assert_eq!(sound.body.node_base_params().unwrap().direct_parent_id, ObjectId::Hash(1));
+        assert!(reparsed.hirc().unwrap().find_object(&ObjectId::Hash(6)).is_none());
+    }
+
+    #[test]
+    fn stid_looks_up_both_ways() {
+        let mut bytes = fixtures::bkhd(0x8C, 0xB4D, 0x10, 0);
+        bytes.extend(fixtures::stid(&[(0xB4D, b"cs_c4070"), (0x1234, b"vo_\xff")]));
+
+        let stid = match parse_soundbank(&bytes).unwrap().sections.remove(1).body {
+            SectionBody::STID(s) => s,
+            _ => unreachable!(),
+        };
+
+        assert_eq!(stid.name_of(0xB4D), Some("cs_c4070"));
+        assert_eq!(stid.name_of(0x1234), None);
+        assert_eq!(stid.name_of(0x5678), None);
+        assert_eq!(stid.id_of("CS_c4070"), Some(0xB4D));
+        assert_eq!(stid.id_of("vo"), None);
+    }
+
+    fn roundtrip_action(action_type: u16, params: &[u8]) -> CAkAction {
+        match roundtrip_object(0x03, &fixtures::action(action_type, 0x1234, params)) {
+            HIRCObjectBody::Action(a) => {
+                assert_eq!(a.action_type, action_type);
+                a
+            },
+            _ => unreachable!(),
+        }
+    }
+
+    #[test]
+    fn seek_actions_roundtrip() {
+        let mut params = vec![0x1];
+        params.extend(fixtures::randomizer_modifier(0.5));
+        params.push(0x1);
+        params.extend(fixtures::except_params(&[0x5678]));
+
+        for action_type in [0x1E02, 0x1E03, 0x1E04, 0x1E05, 0x1E08, 0x1E09] {
+            let seek = match roundtrip_action(action_type, &params).params {
+                CAkActionParams::SeekE(p) | CAkActionParams::SeekEO(p)
+                | CAkActionParams::SeekALL(p) | CAkActionParams::SeekALLO(p)
+                | CAkActionParams::SeekAE(p) | CAkActionParams::SeekAEO(p) => p,
+                _ => unreachable!(),
+            };
+
+            assert_eq!(seek.seek_value.base, 0.5);
+            assert_eq!(seek.snap_to_nearest_marker, 0x1);
+            assert_eq!(seek.except.exceptions[0].object_id, 0x5678);
+        }
+    }
+
+    #[test]
+    fn set_state_action_roundtrips() {
+        let mut params = 0x1111u32.to_le_bytes().to_vec();
+        params.extend(0x2222u32.to_le_bytes());
+
+        let set = match roundtrip_action(0x1204, &params).params {
+            CAkActionParams::SetState(p) => p,
+            _ => unreachable!(),
+        };
+        assert_eq!(set.state_group_id, 0x1111);
+        assert_eq!(set.target_state_id, 0x2222);
+
+        // JSON from before SetState had its own struct still loads
+        let json = r#"{"SetState": {"switch_group_id": 1, "switch_state_id": 2}}"#;
+        match serde_json::from_str::<CAkActionParams>(json).unwrap() {
+            CAkActionParams::SetState(p) => assert_eq!((p.state_group_id, p.target_state_id), (1, 2)),
+            _ => unreachable!(),
+        }
+    }
+
+    #[test]
+    fn bypass_fx_actions_roundtrip() {
+        let mut params = vec![0x1, 0x3];
+        params.extend(fixtures::except_params(&[0x5678]));
+
+        for action_type in [0x1A02, 0x1A03, 0x1B02, 0x1B03, 0x1B04, 0x1B05, 0x1B08, 0x1B09] {
+            let bypass = match roundtrip_action(action_type, &params).params {
+                CAkActionParams::BypassFXM(p) | CAkActionParams::BypassFXO(p)
+                | CAkActionParams::ResetBypassFXM(p) | CAkActionParams::ResetBypassFXO(p)
+                | CAkActionParams::ResetBypassFXALL(p) | CAkActionParams::ResetBypassFXALLO(p)
+                | CAkActionParams::ResetBypassFXAE(p) | CAkActionParams::ResetBypassFXAEO(p) => p,
+                _ => unreachable!(),
+            };
+
+            assert_eq!(bypass.is_bypass, 0x1);
+            assert_eq!(bypass.target_mask, 0x3);
+            assert_eq!(bypass.except.exceptions[0].object_id, 0x5678);
+        }
+    }
+
+    #[test]
+    fn duck_action_keeps_params_opaque() {
+        let params = [0x4, 0xBC, 0x9A, 0x0, 0x0, 0x1, 0x2, 0x3];
+
+        let duck = match roundtrip_action(0x1820, &params).params {
+            CAkActionParams::Duck(p) => p,
+            _ => unreachable!(),
+        };
+
+        assert_eq!(duck, params);
+    }
+
+    #[test]
+    fn game_parameter_actions_roundtrip() {
+        let mut params = vec![0x4, 0x0, 0x1];
+        params.extend(fixtures::randomizer_modifier(25.0));
+        params.extend(fixtures::except_params(&[]));
+
+        for action_type in [0x1302, 0x1303, 0x1402, 0x1403] {
+            let set = match roundtrip_action(action_type, &params).params {
+                CAkActionParams::SetGameParameter(p) | CAkActionParams::SetGameParameterO(p)
+                | CAkActionParams::ResetGameParameter(p) | CAkActionParams::ResetGameParameterO(p) => p,
+                _ => unreachable!(),
+            };
+
+            assert_eq!(set.fade_curve, 0x4);
+            assert_eq!(set.set_game_parameter.value_meaning, 0x1);
+            assert_eq!(set.set_game_parameter.randomizer_modifier.max, 26.0);
+        }
+    }
+
+    #[test]
+    fn trigger_actions_roundtrip() {
+        assert!(matches!(roundtrip_action(0x1D00, &[]).params, CAkActionParams::Trigger));
+        assert!(matches!(roundtrip_action(0x1D01, &[]).params, CAkActionParams::TriggerO));
+    }
+
+    #[test]
+    fn lfo_modulator_roundtrips() {
+        let body = fixtures::modulator(
+            &[(0x02, 50.0), (0x04, 2.5), (0x05, 0.0)],
+            &[(0x02, -10.0, 10.0)],
+            0xABCD,
+        );
+
+        match roundtrip_object(0x13, &body) {
+            HIRCObjectBody::LFOModulator(m) => {
+                assert_eq!(m.prop_bundle.len(), 3);
+                assert_eq!(m.ranged_modifiers.entries.len(), 1);
+                assert_eq!(m.initial_rtpc.rtpcs[0].id, 0xABCD);
+                assert_eq!(m.initial_rtpc.rtpcs[0].graph_points.len(), 2);
+            },
+            _ => unreachable!(),
+        }
+    }
+
+    #[test]
+    fn envelope_modulator_roundtrips() {
+        let body = fixtures::modulator(
+            &[(0x09, 10.0), (0x0B, 200.0), (0x0C, -6.0), (0x0E, 500.0)],
+            &[],
+            0x5678,
+        );
+
+        match roundtrip_object(0x14, &body) {
+            HIRCObjectBody::EnvelopeModulator(m) => {
+                assert_eq!(m.prop_bundle.len(), 4);
+                assert!(m.ranged_modifiers.entries.is_empty());
+                assert_eq!(m.initial_rtpc.rtpcs[0].id, 0x5678);
+            },
+            _ => unreachable!(),
+        }
+    }
+
+    #[test]
+    fn fxpr_section_roundtrips() {
+        let mut body = 2u32.to_le_bytes().to_vec();
+        for (fx_id, params) in [(0x10u32, vec![0x1, 0x2, 0x3]), (0x20, vec![])] {
+            body.extend(fx_id.to_le_bytes());
+            body.extend((params.len() as u32).to_le_bytes());
+            body.extend(params);
+        }
+
+        let mut bytes = fixtures::bkhd(0x30, 0xB4D, 0x10, 0);
+        bytes.extend(fixtures::section(b"FXPR", &body));
+
+        let mut soundbank = parse_soundbank(&bytes).unwrap();
+        soundbank.sections[1].size = 0;
+        crate::prepare_soundbank(&mut soundbank).unwrap();
+        assert_eq!(soundbank.to_bytes().unwrap(), bytes);
+
+        match &soundbank.sections[1].body {
+            SectionBody::FXPR(FXPRSection::Effects(f)) => {
+                assert_eq!(f.effects.len(), 2);
+                assert_eq!(f.effects[0].fx_id, 0x10);
+                assert_eq!(f.effects[0].params, vec![0x1, 0x2, 0x3]);
+            },
+            _ => unreachable!(),
+        }
+    }
+
+    #[test]
+    fn unexpected_fxpr_section_stays_raw() {
+        // One effect whose params stop short of the section's end
+        let mut body = 1u32.to_le_bytes().to_vec();
+        body.extend(0x10u32.to_le_bytes());
+        body.extend(1u32.to_le_bytes());
+        body.extend([0x1, 0x2, 0x3]);
+
+        let mut bytes = fixtures::bkhd(0x30, 0xB4D, 0x10, 0);
+        bytes.extend(fixtures::section(b"FXPR", &body));
+        bytes.extend(fixtures::hirc(&[]));
+
+        let mut soundbank = parse_soundbank(&bytes).unwrap();
+        match &soundbank.sections[1].body {
+            SectionBody::FXPR(FXPRSection::Raw(data)) => assert_eq!(*data, body),
+            _ => unreachable!(),
+        }
+
+        crate::prepare_soundbank(&mut soundbank).unwrap();
+        assert_eq!(soundbank.to_bytes().unwrap(), bytes);
+    }
+
+    #[test]
+    fn dialogue_event_resolves_best_match() {
+        let event = dialogue_event(AkDecisionTreeMode::BestMatch);
+
+        assert_eq!(event.resolve(&[10, 20], Endian::Little), Some(1000));
+        assert_eq!(event.resolve(&[10, 21], Endian::Little), Some(1001));
+        assert_eq!(event.resolve(&[11, 20], Endian::Little), Some(1002));
+        // Exact switch match has no path for state 21, fall back to the wildcard
+        assert_eq!(event.resolve(&[11, 21], Endian::Little), Some(1003));
+        assert_eq!(event.resolve(&[12, 0], Endian::Little), Some(1003));
+    }
+
+    #[test]
+    fn decision_tree_iterates_depth_first() {
+        let tree = dialogue_event(AkDecisionTreeMode::BestMatch).decision_tree(Endian::Little).unwrap();
+
+        let leaves = tree.iter()
+            .filter(|(_, n)| n.children.is_empty())
+            .map(|(path, n)| (path, n.node_id))
+            .collect::<Vec<_>>();
+        assert_eq!(leaves, vec![
+            (vec![10, 20], 1000),
+            (vec![10, 0], 1001),
+            (vec![11, 20], 1002),
+            (vec![0, 0], 1003),
+        ]);
+        assert_eq!(tree.iter().count(), 8);
+        assert!(tree.iter().next().unwrap().0.is_empty());
+
+        let leaf = tree.resolve(&[11, 20], &AkDecisionTreeMode::BestMatch).unwrap();
+        assert_eq!(leaf.node_id, 1002);
+        assert!(tree.resolve(&[10], &AkDecisionTreeMode::BestMatch).is_none());
+    }
+
+    #[test]
+    fn malformed_decision_tree_is_an_error() {
+        let mut event = dialogue_event(AkDecisionTreeMode::BestMatch);
+
+        // The second branch points past the end of the cut off data
+        event.tree_data.truncate(5 * 0xC + 6);
+        assert!(matches!(event.decision_tree(Endian::Little), Err(DekuError::Incomplete(_))));
+        assert_eq!(event.resolve(&[10, 20], Endian::Little), None);
+
+        // A root claiming itself as its child is read as a leaf
+        event.tree_data = branch(0, 0, 1);
+        event.tree_depth = u32::MAX;
+        assert!(event.decision_tree(Endian::Little).unwrap().children.is_empty());
+
+        // Every branch claims the rest of the nodes as its children, which
+        // would expand exponentially
+        event.tree_data = (0..40u16).flat_map(|i| branch(0, i + 1, 39 - i)).collect();
+        event.tree_depth = 40;
+        assert!(matches!(event.decision_tree(Endian::Little), Err(DekuError::Parse(_))));
+        assert_eq!(event.resolve(&[10, 20], Endian::Little), None);
+    }
+
+    #[test]
+    fn dialogue_event_resolves_weighted() {
+        let event = dialogue_event(AkDecisionTreeMode::Weighted);
+
+        // Both 1000 and 1001 match but the wildcard leaf is heavier
+        assert_eq!(event.resolve(&[10, 20], Endian::Little), Some(1001));
+        assert_eq!(event.resolve(&[11, 21], Endian::Little), Some(1003));
+    }
+
+    #[test]
+    fn decision_tree_reads_and_writes_big_endian() {
+        let mut event = dialogue_event(AkDecisionTreeMode::BestMatch);
+
+        // The first four nodes are branches with two u16s where the leaves
+        // have their u32 node ID
+        for (i, node) in event.tree_data.chunks_mut(0xC).enumerate() {
+            node[0..4].reverse();
+            match i < 4 {
+                true => {
+                    node[4..6].reverse();
+                    node[6..8].reverse();
+                },
+                false => node[4..8].reverse(),
+            }
+            node[8..10].reverse();
+            node[10..12].reverse();
+        }
+
+        assert_eq!(event.resolve(&[10, 20], Endian::Big), Some(1000));
+        assert_eq!(event.resolve(&[11, 21], Endian::Big), Some(1003));
+        assert_eq!(event.resolve(&[10, 20], Endian::Little), None);
+
+        let tree = event.decision_tree(Endian::Big).unwrap();
+        let mut output = BitVec::new();
+        AkDecisionTreeNode::write(&mut output, &[&tree], Endian::Big).unwrap();
+        assert_eq!(output.into_vec(), event.tree_data);
+    }
 }
 
 #[deku_derive(DekuRead, DekuWrite)]
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[deku(ctx = "endian: deku::ctx::Endian")]
 pub struct Soundbank {
-    #[deku(bits_read = "deku::rest.len()")]
+    /// Byte order the soundbank was read in and will be written back with.
+    #[serde(
+        default = "crate::serialization::endian::little",
+        skip_serializing_if = "crate::serialization::endian::is_little",
+        with = "crate::serialization::endian",
+    )]
+    #[deku(skip, default = "endian")]
+    pub endian: deku::ctx::Endian,
+    #[deku(
+        reader = "Soundbank::read_sections(deku::rest, *endian)",
+        writer = "Soundbank::write_sections(deku::output, &self.sections, self.endian)",
+    )]
     pub sections: Vec<Section>,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+impl Soundbank {
+    /// The bank version as declared by the BKHD section.
+    pub fn version(&self) -> u32 {
+        bank_version(&self.sections)
+    }
+
+    // Some sections change layout between Wwise versions so the version from
+    // the BKHD, which always comes first, is handed to every section after it.
+    fn read_sections(
+        rest: &BitSlice<u8, Msb0>,
+        endian: deku::ctx::Endian,
+    ) -> Result<(&BitSlice<u8, Msb0>, Vec<Section>), DekuError> {
+        let mut rest = rest;
+        let mut version = 0;
+        let mut sections = vec![];
+
+        while !rest.is_empty() {
+            let (r, section) = Section::read(rest, (endian, version))?;
+            if let SectionBody::BKHD(b) = &section.body {
+                version = b.version;
+            }
+
+            sections.push(section);
+            rest = r;
+        }
+
+        Ok((rest, sections))
+    }
+
+    fn write_sections(
+        output: &mut BitVec<u8, Msb0>,
+        sections: &[Section],
+        endian: deku::ctx::Endian,
+    ) -> Result<(), DekuError> {
+        let version = bank_version(sections);
+        for section in sections {
+            section.write(output, (endian, version))?;
+        }
+        Ok(())
+    }
+}
+
+// The container traits are written out by hand, deku's ctx_default derive for
+// them trips clippy's manual_div_ceil. Reading without a context assumes a
+// little endian bank, writing uses the byte order the bank was read in.
+impl<'a> DekuRead<'a, ()> for Soundbank {
+    fn read(
+        input: &'a BitSlice<u8, Msb0>,
+        _: (),
+    ) -> Result<(&'a BitSlice<u8, Msb0>, Self), DekuError> {
+        Self::read(input, deku::ctx::Endian::Little)
+    }
+}
+
+impl<'a> DekuContainerRead<'a> for Soundbank {
+    fn from_bytes(input: (&'a [u8], usize)) -> Result<((&'a [u8], usize), Self), DekuError> {
+        let (bytes, bit_offset) = input;
+        let bits = bytes.view_bits::<Msb0>();
+        let (rest, value) = <Self as DekuRead<()>>::read(&bits[bit_offset..], ())?;
+
+        let pad = 8 * rest.len().div_ceil(8) - rest.len();
+        let read_bytes = (bits.len() - rest.len() - pad) / 8;
+        Ok(((&bytes[read_bytes..], pad), value))
+    }
+}
+
+impl DekuWrite<()> for Soundbank {
+    fn write(&self, output: &mut BitVec<u8, Msb0>, _: ()) -> Result<(), DekuError> {
+        self.write(output, self.endian)
+    }
+}
+
+impl DekuContainerWrite for Soundbank {
+    fn to_bytes(&self) -> Result<Vec<u8>, DekuError> {
+        Ok(self.to_bits()?.into_vec())
+    }
+
+    fn to_bits(&self) -> Result<BitVec<u8, Msb0>, DekuError> {
+        let mut output = BitVec::new();
+        self.write(&mut output, ())?;
+        Ok(output)
+    }
+}
+
+pub(crate) fn bank_version(sections: &[Section]) -> u32 {
+    sections.iter()
+        .find_map(|s| match &s.body {
+            SectionBody::BKHD(b) => Some(b.version),
+            _ => None,
+        })
+        .unwrap_or(0)
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
 #[deku_derive(DekuRead, DekuWrite)]
+#[deku(ctx = "endian: deku::ctx::Endian, version: u32", endian = "endian")]
 pub struct Section {
     #[serde(skip)]
     #[deku(update = "self.body.deku_id().unwrap()")]
     pub magic: [u8; 4],
     #[serde(skip)]
     pub size: u32,
-    #[deku(ctx = "*magic, *size")]
+    #[deku(ctx = "*magic, *size, version")]
     pub body: SectionBody,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Clone, Debug, Serialize, Deserialize)]
 #[deku_derive(DekuRead, DekuWrite)]
-#[deku(ctx = "magic: [u8; 4], size: u32", id = "magic")]
+#[deku(ctx = "endian: deku::ctx::Endian, magic: [u8; 4], size: u32, version: u32", id = "magic", endian = "endian")]
 pub enum SectionBody {
     #[deku(id = b"BKHD")]
     BKHD(#[deku(ctx = "size")] BKHDSection),
@@ -94,13 +1005,19 @@ pub enum SectionBody {
     #[deku(id = b"ENVS")]
     ENVS(ENVSSection),
     #[deku(id = b"FXPR")]
-    FXPR(#[deku(ctx = "size")] TodoSection),
+    FXPR(#[deku(ctx = "size")] FXPRSection),
     #[deku(id = b"HIRC")]
     HIRC(HIRCSection),
     #[deku(id = b"STID")]
     STID(STIDSection),
     #[deku(id = b"STMG")]
-    STMG(STMGSection),
+    STMG(
+        #[deku(
+            ctx = "version",
+            reader = "read_bounded(deku::rest, size, |body| STMGSection::read(body, (endian, version)))",
+        )]
+        STMGSection
+    ),
     #[deku(id = b"INIT")]
     INIT(INITSection),
     #[deku(id = b"PLAT")]
@@ -108,13 +1025,15 @@ pub enum SectionBody {
 }
 
 #[deku_derive(DekuRead, DekuWrite)]
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[deku(ctx = "endian: deku::ctx::Endian", endian = "endian")]
 pub struct ENVSSection {
     pub conversion_table: ConversionTable,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Clone, Debug, Serialize, Deserialize)]
 #[deku_derive(DekuRead, DekuWrite)]
+#[deku(ctx = "endian: deku::ctx::Endian", endian = "endian")]
 pub struct ConversionTable {
     pub curve_obs_vol: ObsOccCurve,
     pub curve_obs_lpf: ObsOccCurve,
@@ -124,29 +1043,59 @@ pub struct ConversionTable {
     pub curve_occ_hpf: ObsOccCurve,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Clone, Debug, Serialize, Deserialize)]
 #[deku_derive(DekuRead, DekuWrite)]
+#[deku(ctx = "endian: deku::ctx::Endian", endian = "endian")]
 pub struct ObsOccCurve {
     pub curve_enabled: u8,
     pub curve_scaling: u8,
     #[serde(skip)]
     #[deku(update = "self.points.len()")]
     point_count: u16,
-    #[deku(count = "point_count")]
+    #[deku(count = "checked_count(deku::rest, *point_count)?")]
     pub points: Vec<AkRTPCGraphPoint>,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Clone, Debug, Serialize, Deserialize)]
 #[deku_derive(DekuRead, DekuWrite)]
+#[deku(ctx = "endian: deku::ctx::Endian", endian = "endian")]
 pub struct AkRTPCGraphPoint {
     pub from: f32,
     pub to: f32,
     pub interpolation: AkCurveInterpolation,
 }
 
-#[derive(Debug, Default, Serialize, Deserialize)]
+/// Inserts `point` after the points that don't start past it, so the curve
+/// stays ordered, and updates `count` along with it.
+fn add_graph_point<C: TryFrom<usize>>(
+    points: &mut Vec<AkRTPCGraphPoint>,
+    count: &mut C,
+    point: AkRTPCGraphPoint,
+) -> Result<usize, DekuError> {
+    *count = C::try_from(points.len() + 1)
+        .map_err(|_| DekuError::InvalidParam("Curve can not hold any more points".into()))?;
+
+    let index = points.iter().position(|p| p.from > point.from).unwrap_or(points.len());
+    points.insert(index, point);
+    Ok(index)
+}
+
+fn remove_graph_point<C: TryFrom<usize>>(
+    points: &mut Vec<AkRTPCGraphPoint>,
+    count: &mut C,
+    index: usize,
+) -> Option<AkRTPCGraphPoint> {
+    if index >= points.len() {
+        return None;
+    }
+
+    *count = C::try_from(points.len() - 1).ok()?;
+    Some(points.remove(index))
+}
+
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
 #[deku_derive(DekuRead, DekuWrite)]
-#[deku(type = "u32")]
+#[deku(type = "u32", ctx = "endian: deku::ctx::Endian", endian = "endian")]
 pub enum AkCurveInterpolation {
     #[default]
     #[deku(id = "0x0")]
@@ -172,8 +1121,8 @@ pub enum AkCurveInterpolation {
 }
 
 #[deku_derive(DekuRead, DekuWrite)]
-#[derive(Debug, Serialize, Deserialize)]
-#[deku(ctx = "size: u32")]
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[deku(ctx = "endian: deku::ctx::Endian, size: u32", endian = "endian")]
 pub struct BKHDSection {
     pub version: u32,
     pub bank_id: u32,
@@ -182,22 +1131,38 @@ pub struct BKHDSection {
     pub project_id: u32,
 
     // This padding is here to align the DATA sections's
-    // first WEM to a multiple of wem_alignment.
-    #[deku(count = "size - (4 * 5)")]
+    // first WEM to a multiple of wem_alignment. Newer versions put fields of
+    // their own in front of the zeros, those are kept as they are.
+    #[deku(count = "checked_count(deku::rest, remaining_size(size, 4 * 5)?)?")]
     pub padding: Vec<u8>,
 }
 
+impl BKHDSection {
+    /// Drops the zeros aligning the first WEM from the padding, keeping the
+    /// fields in front of them. Returns the size of the fields.
+    pub fn trim_padding(&mut self) -> usize {
+        let fields_size = self.padding.iter()
+            .rposition(|b| *b != 0)
+            .map_or(0, |p| p + 1);
+
+        self.padding.truncate(fields_size);
+        fields_size
+    }
+}
+
 #[deku_derive(DekuRead, DekuWrite)]
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[deku(ctx = "endian: deku::ctx::Endian", endian = "endian")]
 pub struct INITSection {
     #[deku(update = "self.plugins.len()")]
     plugin_count: u32,
-    #[deku(count = "plugin_count")]
+    #[deku(count = "checked_count(deku::rest, *plugin_count)?")]
     pub plugins: Vec<IAkPlugin>,
 }
 
 #[deku_derive(DekuRead, DekuWrite)]
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[deku(ctx = "endian: deku::ctx::Endian", endian = "endian")]
 pub struct IAkPlugin {
     pub plugin_id: PluginId,
     #[deku(update = "self.dll_name.as_bytes_with_nul().len()")]
@@ -207,7 +1172,8 @@ pub struct IAkPlugin {
 }
 
 #[deku_derive(DekuRead, DekuWrite)]
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[deku(ctx = "endian: deku::ctx::Endian", endian = "endian")]
 pub struct DIDXDescriptor {
     pub id: u32,
     pub offset: u32,
@@ -215,24 +1181,95 @@ pub struct DIDXDescriptor {
 }
 
 #[deku_derive(DekuRead, DekuWrite)]
-#[derive(Debug, Serialize, Deserialize)]
-#[deku(ctx = "size: u32")]
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[deku(ctx = "endian: deku::ctx::Endian, size: u32", endian = "endian")]
 pub struct DIDXSection {
     #[deku(bytes_read = "size")]
     pub descriptors: Vec<DIDXDescriptor>,
 }
 
 #[deku_derive(DekuRead, DekuWrite)]
-#[derive(Debug, Serialize, Deserialize)]
-#[deku(ctx = "size: u32")]
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[deku(ctx = "_endian: deku::ctx::Endian, size: u32")]
 pub struct DATASection {
     #[serde(with = "crate::serialization::base64")]
-    #[deku(bytes_read = "size")]
+    #[deku(reader = "read_sized_bytes(deku::rest, size)")]
     pub data: Vec<u8>,
 }
 
+// Every element takes up at least a byte, so a count exceeding the remaining
+// input can't be right. Checking it up front stops deku from preallocating for
+// whatever garbage count a malformed file claims.
+fn checked_count(
+    rest: &BitSlice<u8, Msb0>,
+    count: impl TryInto<usize>,
+) -> Result<usize, DekuError> {
+    let available = rest.len() / 8;
+    match count.try_into() {
+        Ok(count) if count <= available => Ok(count),
+        _ => Err(DekuError::Parse(format!(
+            "Count exceeds the {:#x} remaining bytes",
+            available,
+        ))),
+    }
+}
+
+// The fixed fields in front of a sized blob have to fit in the size.
+fn remaining_size(size: u32, fields_size: u32) -> Result<u32, DekuError> {
+    size.checked_sub(fields_size).ok_or_else(|| DekuError::Parse(format!(
+        "Size {:#x} doesn't cover the {:#x} bytes of fields",
+        size,
+        fields_size,
+    )))
+}
+
+// Reads a section body from exactly `size` bytes, anything the body leaves
+// unread means the layout doesn't match what's in the file.
+fn read_bounded<T>(
+    rest: &BitSlice<u8, Msb0>,
+    size: u32,
+    read: impl FnOnce(&BitSlice<u8, Msb0>) -> Result<(&BitSlice<u8, Msb0>, T), DekuError>,
+) -> Result<(&BitSlice<u8, Msb0>, T), DekuError> {
+    let available = rest.len() / 8;
+    if size as usize > available {
+        return Err(DekuError::Parse(format!(
+            "Section claims {:#x} bytes but only {:#x} remain",
+            size,
+            available,
+        )));
+    }
+
+    let (body, rest) = rest.split_at(size as usize * 8);
+    let (left, value) = read(body)?;
+    if !left.is_empty() {
+        return Err(DekuError::Parse(format!("Section has {:#x} bytes left", left.len() / 8)));
+    }
+
+    Ok((rest, value))
+}
+
+// Reads the entire blob in one go. The size comes straight from the file so
+// it's checked against the remaining input before anything gets allocated.
+fn read_sized_bytes(
+    rest: &BitSlice<u8, Msb0>,
+    size: u32,
+) -> Result<(&BitSlice<u8, Msb0>, Vec<u8>), DekuError> {
+    let available = rest.len() / 8;
+    if size as usize > available {
+        return Err(DekuError::Parse(format!(
+            "Section claims {:#x} bytes but only {:#x} remain",
+            size,
+            available,
+        )));
+    }
+
+    let (data, rest) = rest.split_at(size as usize * 8);
+    Ok((rest, data.to_bitvec().into_vec()))
+}
+
 #[deku_derive(DekuRead, DekuWrite)]
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[deku(ctx = "endian: deku::ctx::Endian", endian = "endian")]
 pub struct AkStateTransition {
     from_state: u32,
     to_state: u32,
@@ -240,45 +1277,132 @@ pub struct AkStateTransition {
 }
 
 #[deku_derive(DekuRead, DekuWrite)]
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[deku(ctx = "endian: deku::ctx::Endian", endian = "endian")]
 pub struct STMGSectionStateGroup {
     id: u32,
     default_transition_time: u32,
     #[deku(update = "self.state_transitions.len()")]
     state_transition_count: u32,
-    #[deku(count = "state_transition_count")]
+    #[deku(count = "checked_count(deku::rest, *state_transition_count)?")]
     state_transitions: Vec<AkStateTransition>,
 }
 
 #[deku_derive(DekuRead, DekuWrite)]
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[deku(ctx = "endian: deku::ctx::Endian", endian = "endian")]
 pub struct PLATSection {
     #[deku(update = "self.string.as_bytes_with_nul().len()")]
     string_length: u32,
     #[serde(with = "crate::serialization::cstring")]
-    string: ffi::CString,
+    pub string: ffi::CString,
 }
 
 #[deku_derive(DekuRead, DekuWrite)]
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[deku(ctx = "endian: deku::ctx::Endian", endian = "endian")]
 pub struct HIRCSection {
     #[deku(update = "self.objects.len()")]
     object_count: u32,
-    #[deku(count = "object_count")]
+    #[deku(count = "checked_count(deku::rest, *object_count)?")]
     pub objects: Vec<HIRCObject>,
 }
 
+impl HIRCSection {
+    pub fn new(objects: Vec<HIRCObject>) -> Self {
+        Self {
+            object_count: objects.len() as u32,
+            objects,
+        }
+    }
+
+    /// Looks up an object by its hash, so named and hashed IDs referring to
+    /// the same object both match.
+    pub fn find_object(&self, id: &ObjectId) -> Option<&HIRCObject> {
+        let hash = id.as_hash();
+        self.objects.iter().find(|o| o.id.as_hash() == hash)
+    }
+
+    pub fn find_object_mut(&mut self, id: &ObjectId) -> Option<&mut HIRCObject> {
+        let hash = id.as_hash();
+        self.objects.iter_mut().find(|o| o.id.as_hash() == hash)
+    }
+}
+
+/// Effect parameter presets, only found in banks from older Wwise versions.
+/// The layout hasn't been checked against a real bank, so a body that doesn't
+/// read as exactly a list of effects is kept as raw bytes instead.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub enum FXPRSection {
+    Effects(FXPREffects),
+    Raw(#[serde(with = "crate::serialization::base64")] Vec<u8>),
+}
+
+impl<'a> DekuRead<'a, (deku::ctx::Endian, u32)> for FXPRSection {
+    fn read(
+        input: &'a BitSlice<u8, Msb0>,
+        (endian, size): (deku::ctx::Endian, u32),
+    ) -> Result<(&'a BitSlice<u8, Msb0>, Self), DekuError> {
+        match read_bounded(input, size, |body| FXPREffects::read(body, endian)) {
+            Ok((rest, effects)) => Ok((rest, FXPRSection::Effects(effects))),
+            Err(_) => read_sized_bytes(input, size)
+                .map(|(rest, data)| (rest, FXPRSection::Raw(data))),
+        }
+    }
+}
+
+impl DekuWrite<(deku::ctx::Endian, u32)> for FXPRSection {
+    fn write(
+        &self,
+        output: &mut BitVec<u8, Msb0>,
+        (endian, _): (deku::ctx::Endian, u32),
+    ) -> Result<(), DekuError> {
+        match self {
+            FXPRSection::Effects(effects) => effects.write(output, endian),
+            FXPRSection::Raw(data) => data.write(output, ()),
+        }
+    }
+}
+
+#[deku_derive(DekuRead, DekuWrite)]
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[deku(ctx = "endian: deku::ctx::Endian", endian = "endian")]
+pub struct FXPREffects {
+    #[serde(skip)]
+    #[deku(update = "self.effects.len()")]
+    effect_count: u32,
+    #[deku(count = "checked_count(deku::rest, *effect_count)?")]
+    pub effects: Vec<FXPREffect>,
+}
+
+#[deku_derive(DekuRead, DekuWrite)]
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[deku(ctx = "endian: deku::ctx::Endian", endian = "endian")]
+pub struct FXPREffect {
+    pub fx_id: u32,
+    #[serde(skip)]
+    #[deku(update = "self.params.len()")]
+    params_size: u32,
+    #[serde(with = "crate::serialization::base64")]
+    #[deku(count = "checked_count(deku::rest, *params_size)?")]
+    pub params: Vec<u8>,
+}
+
 #[deku_derive(DekuRead, DekuWrite)]
-#[derive(Debug, Serialize, Deserialize)]
-#[deku(ctx = "size: u32")]
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[deku(ctx = "_endian: deku::ctx::Endian, size: u32")]
 pub struct TodoSection {
     #[serde(with = "crate::serialization::base64")]
-    #[deku(bytes_read = "size")]
+    #[deku(reader = "read_sized_bytes(deku::rest, size)")]
     data: Vec<u8>,
 }
 
+/// The first bank version to carry acoustic textures in the STMG.
+pub const STMG_TEXTURES_VERSION: u32 = 0x77;
+
 #[deku_derive(DekuRead, DekuWrite)]
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[deku(ctx = "endian: deku::ctx::Endian, version: u32", endian = "endian")]
 pub struct STMGSection {
     pub volume_threshold: f32,
     pub max_voice_instances: u16,
@@ -286,39 +1410,47 @@ pub struct STMGSection {
     #[serde(skip)]
     #[deku(update = "self.state_groups.len()")]
     state_group_count: u32,
-    #[deku(count = "state_group_count")]
+    #[deku(count = "checked_count(deku::rest, *state_group_count)?")]
     pub state_groups: Vec<StateGroup>,
     #[serde(skip)]
     #[deku(update = "self.switch_groups.len()")]
     switch_group_count: u32,
-    #[deku(count = "switch_group_count")]
+    #[deku(count = "checked_count(deku::rest, *switch_group_count)?")]
     pub switch_groups: Vec<SwitchGroup>,
     #[serde(skip)]
     #[deku(update = "self.ramping_params.len()")]
     ramping_param_count: u32,
-    #[deku(count = "ramping_param_count")]
+    #[deku(count = "checked_count(deku::rest, *ramping_param_count)?")]
     pub ramping_params: Vec<RTPCRamping>,
     #[serde(skip)]
-    #[deku(update = "self.textures.len()")]
+    #[deku(
+        update = "self.textures.len()",
+        skip,
+        cond = "version < STMG_TEXTURES_VERSION",
+        default = "0",
+    )]
     texture_count: u32,
-    #[deku(count = "texture_count")]
+    #[serde(default)]
+    #[deku(count = "checked_count(deku::rest, *texture_count)?")]
     pub textures: Vec<AkAcousticTexture>,
 }
 
 #[deku_derive(DekuRead, DekuWrite)]
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[deku(ctx = "endian: deku::ctx::Endian", endian = "endian")]
 pub struct StateGroup {
     pub id: u32,
     pub default_transition_time: u32,
     #[serde(skip)]
     #[deku(update = "self.transitions.len()")]
     transition_count: u32,
-    #[deku(count = "transition_count")]
+    #[deku(count = "checked_count(deku::rest, *transition_count)?")]
     pub transitions: Vec<AkStateTransition>,
 }
 
 #[deku_derive(DekuRead, DekuWrite)]
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[deku(ctx = "endian: deku::ctx::Endian", endian = "endian")]
 pub struct SwitchGroup {
     pub id: u32,
     pub rtpc_id: u32,
@@ -326,12 +1458,13 @@ pub struct SwitchGroup {
     #[serde(skip)]
     #[deku(update = "self.graph_points.len()")]
     graph_point_count: u32,
-    #[deku(count = "graph_point_count")]
+    #[deku(count = "checked_count(deku::rest, *graph_point_count)?")]
     pub graph_points: Vec<AkSwitchGraphPoint>,
 }
 
 #[deku_derive(DekuRead, DekuWrite)]
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[deku(ctx = "endian: deku::ctx::Endian", endian = "endian")]
 pub struct AkSwitchGraphPoint {
     pub rtpc_value: f32,
     pub switch: u32,
@@ -339,7 +1472,8 @@ pub struct AkSwitchGraphPoint {
 }
 
 #[deku_derive(DekuRead, DekuWrite)]
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[deku(ctx = "endian: deku::ctx::Endian", endian = "endian")]
 pub struct RTPCRamping {
     pub rtpc_id: u32,
     pub value: u32,
@@ -350,7 +1484,8 @@ pub struct RTPCRamping {
 }
 
 #[deku_derive(DekuRead, DekuWrite)]
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[deku(ctx = "endian: deku::ctx::Endian", endian = "endian")]
 pub struct AkAcousticTexture {
     pub id: u32,
     pub absorption_offset: f32,
@@ -362,30 +1497,59 @@ pub struct AkAcousticTexture {
 }
 
 #[deku_derive(DekuRead, DekuWrite)]
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[deku(ctx = "endian: deku::ctx::Endian", endian = "endian")]
 pub struct STIDSectionEntry {
     pub bnk_id: u32,
     #[serde(skip)]
     #[deku(update = "self.name.len()")]
     name_length: u8,
     #[serde(with = "crate::serialization::bytestring")]
-    #[deku(count = "name_length")]
+    #[deku(count = "checked_count(deku::rest, *name_length)?")]
     pub name: Vec<u8>,
 }
 
 #[deku_derive(DekuRead, DekuWrite)]
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[deku(ctx = "endian: deku::ctx::Endian", endian = "endian")]
 pub struct STIDSection {
     pub string_encoding: u32,
     #[serde(skip)]
     #[deku(update = "self.entries.len()")]
     entry_count: u32,
-    #[deku(count = "entry_count")]
+    #[deku(count = "checked_count(deku::rest, *entry_count)?")]
     pub entries: Vec<STIDSectionEntry>,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+impl STIDSection {
+    /// The name of a referenced soundbank, `None` if the bank isn't listed or
+    /// its name isn't valid UTF-8.
+    pub fn name_of(&self, bnk_id: u32) -> Option<&str> {
+        self.entries.iter()
+            .find(|e| e.bnk_id == bnk_id)
+            .and_then(|e| std::str::from_utf8(&e.name).ok())
+    }
+
+    /// The ID of a referenced soundbank by name. Like the hashes the names are
+    /// matched case-insensitively.
+    pub fn id_of(&self, name: &str) -> Option<u32> {
+        self.entries.iter()
+            .find(|e| e.name.eq_ignore_ascii_case(name.as_bytes()))
+            .map(|e| e.bnk_id)
+    }
+}
+
+/// Bytes taken up by the type and size fields in front of every HIRC object.
+/// These are not counted by the object's `size`.
+pub const HIRC_OBJECT_HEADER_SIZE: u32 = 5;
+
+/// Bytes taken up by the ID of a HIRC object. Unlike the header these are
+/// counted by the object's `size`, which covers both the ID and the body.
+pub const HIRC_OBJECT_ID_SIZE: u32 = 4;
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
 #[deku_derive(DekuRead, DekuWrite)]
+#[deku(ctx = "endian: deku::ctx::Endian", endian = "endian")]
 pub struct HIRCObject {
     #[serde(skip)]
     #[deku(update = "self.body.deku_id().unwrap()")]
@@ -394,8 +1558,8 @@ pub struct HIRCObject {
     pub size: u32,
 
     #[deku(
-        reader = "ObjectId::read(deku::rest)",
-        writer = "ObjectId::write(deku::output, &self.id)",
+        reader = "ObjectId::read(deku::rest, endian)",
+        writer = "ObjectId::write(deku::output, &self.id, endian)",
     )]
     pub id: ObjectId,
 
@@ -403,16 +1567,25 @@ pub struct HIRCObject {
     pub body: HIRCObjectBody,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Clone, Debug, Serialize, Deserialize)]
 #[deku_derive(DekuRead, DekuWrite)]
-#[deku(ctx = "body_type: u8, size: u32", id = "body_type")]
+// The size bounds actions so parameters that aren't modeled yet can be kept
+// as-is, it's also what TodoObject needs for when new object types come up.
+#[deku(ctx = "endian: deku::ctx::Endian, body_type: u8, size: u32", id = "body_type", endian = "endian")]
 pub enum HIRCObjectBody {
     #[deku(id = "01")]
     State(CAkState),
     #[deku(id = "02")]
     Sound(CAkSound),
     #[deku(id = "03")]
-    Action(CAkAction),
+    Action(
+        #[deku(reader = "read_bounded(
+            deku::rest,
+            remaining_size(size, HIRC_OBJECT_ID_SIZE)?,
+            |rest| CAkAction::read(rest, endian),
+        )")]
+        CAkAction,
+    ),
     #[deku(id = "04")]
     Event(CAkEvent),
     #[deku(id = "05")]
@@ -444,18 +1617,18 @@ pub enum HIRCObjectBody {
     #[deku(id = "18")]
     AuxiliaryBus(CAkAuxBus),
     #[deku(id = "19")]
-    LFOModulator(#[deku(ctx = "size")] TodoObject),
+    LFOModulator(CAkLFOModulator),
     #[deku(id = "20")]
-    EnvelopeModulator(#[deku(ctx = "size")] TodoObject),
+    EnvelopeModulator(CAkEnvelopeModulator),
     #[deku(id = "21")]
     AudioDevice(CAkAudioDevice),
     #[deku(id = "22")]
     TimeModulator(CAkTimeModulator),
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Clone, Debug, Serialize, Deserialize)]
 #[deku_derive(DekuRead, DekuWrite)]
-#[deku(type = "u8")]
+#[deku(type = "u8", ctx = "endian: deku::ctx::Endian", endian = "endian")]
 pub enum AkPropID {
     #[deku(id = "0x00")]
     Volume,
@@ -599,24 +1772,45 @@ pub enum AkPropID {
     PositioningTypeBlend,
     #[deku(id = "0x48")]
     ReflectionBusVolume,
+    #[deku(id_pat = "_")]
+    Unknown(u8),
+}
+
+impl AkPropID {
+    /// The raw prop ID, also for props that aren't known to this crate.
+    pub fn id(&self) -> u8 {
+        match self {
+            AkPropID::Unknown(id) => *id,
+            // Every other variant has an ID assigned
+            known => known.deku_id().unwrap(),
+        }
+    }
 }
 
 // Incomplete but I best enable them when I have examples to work off of
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Clone, Debug, Serialize, Deserialize)]
 #[deku_derive(DekuRead, DekuWrite)]
-#[deku(ctx = "action_type: u16", id = "action_type")]
+#[deku(ctx = "endian: deku::ctx::Endian, action_type: u16", id = "action_type", endian = "endian")]
 pub enum CAkActionParams {
     // #[deku(id="0x0000")] None,
     #[deku(id = "0x1204")]
-    SetState(CAkActionSetSwitch),
-    // #[deku(id="0x1A02")] BypassFXM,
-    // #[deku(id="0x1A03")] BypassFXO,
-    // #[deku(id="0x1B02")] ResetBypassFXM,
-    // #[deku(id="0x1B03")] ResetBypassFXO,
-    // #[deku(id="0x1B04")] ResetBypassFXALL,
-    // #[deku(id="0x1B05")] ResetBypassFXALLO,
-    // #[deku(id="0x1B08")] ResetBypassFXAE,
-    // #[deku(id="0x1B09")] ResetBypassFXAEO,
+    SetState(CAkActionSetState),
+    #[deku(id = "0x1A02")]
+    BypassFXM(CAkActionBypassFX),
+    #[deku(id = "0x1A03")]
+    BypassFXO(CAkActionBypassFX),
+    #[deku(id = "0x1B02")]
+    ResetBypassFXM(CAkActionBypassFX),
+    #[deku(id = "0x1B03")]
+    ResetBypassFXO(CAkActionBypassFX),
+    #[deku(id = "0x1B04")]
+    ResetBypassFXALL(CAkActionBypassFX),
+    #[deku(id = "0x1B05")]
+    ResetBypassFXALLO(CAkActionBypassFX),
+    #[deku(id = "0x1B08")]
+    ResetBypassFXAE(CAkActionBypassFX),
+    #[deku(id = "0x1B09")]
+    ResetBypassFXAEO(CAkActionBypassFX),
     #[deku(id = "0x1901")]
     SetSwitch(CAkActionSetSwitch),
     // #[deku(id="0x1002")] UseStateE,
@@ -714,28 +1908,47 @@ pub enum CAkActionParams {
     // #[deku(id="0x1511")] StopEvent,
     // #[deku(id="0x1611")] PauseEvent,
     // #[deku(id="0x1711")] ResumeEvent,
-    // #[deku(id="0x1820")] Duck,
-    // #[deku(id="0x1D00")] Trigger,
-    // #[deku(id="0x1D01")] TriggerO,
-    // #[deku(id="0x1E02")] SeekE,
-    // #[deku(id="0x1E03")] SeekEO,
-    // #[deku(id="0x1E04")] SeekALL,
-    // #[deku(id="0x1E05")] SeekALLO,
-    // #[deku(id="0x1E08")] SeekAE,
-    // #[deku(id="0x1E09")] SeekAEO,
+    // The layout of the duck parameters hasn't been checked against a bank yet
+    // so they're kept as-is, the action body is bounded by the object size.
+    #[deku(id = "0x1820")]
+    Duck(
+        #[serde(with = "crate::serialization::base64")]
+        #[deku(reader = "read_sized_bytes(deku::rest, (deku::rest.len() / 8) as u32)")]
+        Vec<u8>,
+    ),
+    #[deku(id = "0x1D00")]
+    Trigger,
+    #[deku(id = "0x1D01")]
+    TriggerO,
+    #[deku(id = "0x1E02")]
+    SeekE(CAkActionSeek),
+    #[deku(id = "0x1E03")]
+    SeekEO(CAkActionSeek),
+    #[deku(id = "0x1E04")]
+    SeekALL(CAkActionSeek),
+    #[deku(id = "0x1E05")]
+    SeekALLO(CAkActionSeek),
+    #[deku(id = "0x1E08")]
+    SeekAE(CAkActionSeek),
+    #[deku(id = "0x1E09")]
+    SeekAEO(CAkActionSeek),
     // #[deku(id="0x2202")] ResetPlaylistE,
     // #[deku(id="0x2203")] ResetPlaylistEO,
-    // #[deku(id="0x1302")] SetGameParameter,
-    // #[deku(id="0x1303")] SetGameParameterO,
-    // #[deku(id="0x1402")] ResetGameParameter,
-    // #[deku(id="0x1403")] ResetGameParameterO,
+    #[deku(id = "0x1302")]
+    SetGameParameter(CAkActionSetGameParameter),
+    #[deku(id = "0x1303")]
+    SetGameParameterO(CAkActionSetGameParameter),
+    #[deku(id = "0x1402")]
+    ResetGameParameter(CAkActionSetGameParameter),
+    #[deku(id = "0x1403")]
+    ResetGameParameterO(CAkActionSetGameParameter),
     // #[deku(id="0x1F02")] Release,
     // #[deku(id="0x1F03")] ReleaseO,
 }
 
-#[derive(Debug, Default, Serialize, Deserialize)]
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
 #[deku_derive(DekuRead, DekuWrite)]
-#[deku(type = "u8")]
+#[deku(type = "u8", ctx = "endian: deku::ctx::Endian", endian = "endian")]
 pub enum AkPathMode {
     #[default]
     #[deku(id = "0x0")]
@@ -752,9 +1965,9 @@ pub enum AkPathMode {
     StepRandomPickNewPath,
 }
 
-#[derive(Debug, Default, Serialize, Deserialize)]
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
 #[deku_derive(DekuRead, DekuWrite)]
-#[deku(type = "u8", bits = "3")]
+#[deku(type = "u8", bits = "3", ctx = "endian: deku::ctx::Endian", endian = "endian")]
 pub enum Ak3DSpatializationMode {
     #[default]
     #[deku(id = "0x0")]
@@ -765,9 +1978,9 @@ pub enum Ak3DSpatializationMode {
     PositionAndOrientation,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Clone, Debug, Serialize, Deserialize)]
 #[deku_derive(DekuRead, DekuWrite)]
-#[deku(type = "u8", bits = "3")]
+#[deku(type = "u8", bits = "3", ctx = "endian: deku::ctx::Endian", endian = "endian")]
 pub enum AkSpeakerPanningType {
     #[deku(id = "0x0")]
     DirectSpeakerAssignment,
@@ -777,9 +1990,9 @@ pub enum AkSpeakerPanningType {
     SteeringPanner,
 }
 
-#[derive(Debug, PartialEq, Serialize, Deserialize)]
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
 #[deku_derive(DekuRead, DekuWrite)]
-#[deku(type = "u8", bits = "2")]
+#[deku(type = "u8", bits = "2", ctx = "endian: deku::ctx::Endian", endian = "endian")]
 pub enum Ak3DPositionType {
     #[deku(id = "0x0")]
     Emitter,
@@ -789,9 +2002,9 @@ pub enum Ak3DPositionType {
     ListenerWithAutomation,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Clone, Debug, Serialize, Deserialize)]
 #[deku_derive(DekuRead, DekuWrite)]
-#[deku(type = "u8")]
+#[deku(type = "u8", ctx = "endian: deku::ctx::Endian", endian = "endian")]
 pub enum AkVirtualQueueBehavior {
     #[deku(id = "0x0")]
     PlayFromBeginning,
@@ -801,9 +2014,9 @@ pub enum AkVirtualQueueBehavior {
     Resume,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Clone, Debug, Serialize, Deserialize)]
 #[deku_derive(DekuRead, DekuWrite)]
-#[deku(type = "u8")]
+#[deku(type = "u8", ctx = "endian: deku::ctx::Endian", endian = "endian")]
 pub enum AkBelowThresholdBehavior {
     #[deku(id = "0x0")]
     ContinueToPlay,
@@ -815,9 +2028,9 @@ pub enum AkBelowThresholdBehavior {
     KillIfOneShotElseVirtual,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Clone, Debug, Serialize, Deserialize)]
 #[deku_derive(DekuRead, DekuWrite)]
-#[deku(type = "u32")]
+#[deku(type = "u32", ctx = "endian: deku::ctx::Endian", endian = "endian")]
 pub enum AkSyncType {
     #[deku(id = "0x0")]
     Immediate,
@@ -841,9 +2054,9 @@ pub enum AkSyncType {
     LastExitPosition,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Clone, Debug, Serialize, Deserialize)]
 #[deku_derive(DekuRead, DekuWrite)]
-#[deku(type = "u8")]
+#[deku(type = "u8", ctx = "endian: deku::ctx::Endian", endian = "endian")]
 pub enum AkSyncTypeU8 {
     #[deku(id = "0x0")]
     Immediate,
@@ -867,9 +2080,9 @@ pub enum AkSyncTypeU8 {
     LastExitPosition,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Clone, Debug, Serialize, Deserialize)]
 #[deku_derive(DekuRead, DekuWrite)]
-#[deku(type = "u8")]
+#[deku(type = "u8", ctx = "endian: deku::ctx::Endian", endian = "endian")]
 pub enum AkRtpcAccum {
     #[deku(id = "0x0")]
     None,
@@ -887,9 +2100,9 @@ pub enum AkRtpcAccum {
     Filter,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Clone, Debug, Serialize, Deserialize)]
 #[deku_derive(DekuRead, DekuWrite)]
-#[deku(type = "u8")]
+#[deku(type = "u8", ctx = "endian: deku::ctx::Endian", endian = "endian")]
 pub enum AkRtpcType {
     #[deku(id = "0x0")]
     GameParameter,
@@ -899,9 +2112,9 @@ pub enum AkRtpcType {
     Modulator,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Clone, Debug, Serialize, Deserialize)]
 #[deku_derive(DekuRead, DekuWrite)]
-#[deku(type = "u8")]
+#[deku(type = "u8", ctx = "endian: deku::ctx::Endian", endian = "endian")]
 pub enum AkCurveScaling {
     #[deku(id = "0x0")]
     None,
@@ -913,9 +2126,9 @@ pub enum AkCurveScaling {
     DBToLin,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Clone, Debug, Serialize, Deserialize)]
 #[deku_derive(DekuRead, DekuWrite)]
-#[deku(type = "u8")]
+#[deku(type = "u8", ctx = "endian: deku::ctx::Endian", endian = "endian")]
 pub enum AkCurveInterpolationU8 {
     #[deku(id = "0x0")]
     Log3,
@@ -939,9 +2152,9 @@ pub enum AkCurveInterpolationU8 {
     Constant,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Clone, Debug, Serialize, Deserialize)]
 #[deku_derive(DekuRead, DekuWrite)]
-#[deku(type = "u8")]
+#[deku(type = "u8", ctx = "endian: deku::ctx::Endian", endian = "endian")]
 pub enum AkGroupType {
     #[deku(id = "0x0")]
     Switch,
@@ -949,9 +2162,9 @@ pub enum AkGroupType {
     State,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Clone, Debug, Serialize, Deserialize)]
 #[deku_derive(DekuRead, DekuWrite)]
-#[deku(type = "u8")]
+#[deku(type = "u8", ctx = "endian: deku::ctx::Endian", endian = "endian")]
 pub enum AkDecisionTreeMode {
     #[deku(id = "0x0")]
     BestMatch,
@@ -959,31 +2172,32 @@ pub enum AkDecisionTreeMode {
     Weighted,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Clone, Debug, Serialize, Deserialize)]
 #[deku_derive(DekuRead, DekuWrite)]
-#[deku(ctx = "size: u32")]
+#[deku(ctx = "endian: deku::ctx::Endian, size: u32", endian = "endian")]
 pub struct TodoObject {
-    #[deku(count = "size - 4")]
+    #[deku(count = "checked_count(deku::rest, remaining_size(size, HIRC_OBJECT_ID_SIZE)?)?")]
     data: Vec<u8>,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Clone, Debug, Serialize, Deserialize)]
 #[deku_derive(DekuRead, DekuWrite)]
+#[deku(ctx = "endian: deku::ctx::Endian", endian = "endian")]
 pub struct CAkMusicSwitchCntr {
     pub music_trans_node_params: MusicTransNodeParams,
     pub continue_playback: u8,
     #[serde(skip)]
     #[deku(update = "self.arguments.len()")]
     pub tree_depth: u32,
-    #[deku(count = "tree_depth")]
+    #[deku(count = "checked_count(deku::rest, *tree_depth)?")]
     pub arguments: Vec<AkGameSync>,
-    #[deku(count = "tree_depth")]
+    #[deku(count = "checked_count(deku::rest, *tree_depth)?")]
     pub group_types: Vec<AkGroupType>,
 
     #[serde(skip)]
     #[deku(update = "self.tree_data.len()")]
     pub tree_size: u32,
-    #[deku(count = "tree_size")]
+    #[deku(count = "checked_count(deku::rest, *tree_size)?")]
     pub tree_data: Vec<u8>,
     pub tree_mode: AkDecisionTreeMode,
     // #[deku(
@@ -1001,22 +2215,23 @@ pub struct CAkMusicSwitchCntr {
     // pub tree: Vec<AkDecisionTreeNode>,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Clone, Debug, Serialize, Deserialize)]
 #[deku_derive(DekuRead, DekuWrite)]
+#[deku(ctx = "endian: deku::ctx::Endian", endian = "endian")]
 pub struct CAkDialogueEvent {
     pub probability: u8,
     #[serde(skip)]
     #[deku(update = "self.arguments.len()")]
     pub tree_depth: u32,
-    #[deku(count = "tree_depth")]
+    #[deku(count = "checked_count(deku::rest, *tree_depth)?")]
     pub arguments: Vec<AkGameSync>,
-    #[deku(count = "tree_depth")]
+    #[deku(count = "checked_count(deku::rest, *tree_depth)?")]
     pub group_types: Vec<AkGroupType>,
 
     #[serde(skip)]
     #[deku(update = "self.tree_data.len()")]
     pub tree_size: u32,
-    #[deku(count = "tree_size")]
+    #[deku(count = "checked_count(deku::rest, *tree_size)?")]
     pub tree_data: Vec<u8>,
     pub tree_mode: AkDecisionTreeMode,
 
@@ -1037,16 +2252,53 @@ pub struct CAkDialogueEvent {
     #[deku(
         reader = "PropBundle::read_list(
             deku::rest,
+            endian,
         )",
         writer = "PropBundle::write_list(
             deku::output,
             &self.prop_bundle.iter().collect::<Vec<_>>(),
+            endian,
         )"
     )]
     pub prop_bundle: Vec<PropBundle>,
     pub ranged_modifiers: PropRangedModifiers,
 }
 
+impl CAkMusicSwitchCntr {
+    /// Decodes the decision tree, `endian` being the byte order of the
+    /// soundbank the container was read from.
+    pub fn decision_tree(&self, endian: deku::ctx::Endian) -> Result<AkDecisionTreeNode, DekuError> {
+        AkDecisionTreeNode::read(&self.tree_data, self.tree_depth, endian)
+    }
+
+    /// Walks the decision tree with the supplied switch or state IDs, one per
+    /// argument. Returns the ID of the node the container would switch to.
+    pub fn resolve(&self, args: &[u32], endian: deku::ctx::Endian) -> Option<u32> {
+        let tree = self.decision_tree(endian).ok()?;
+        tree.resolve(args, &self.tree_mode)
+            .map(|l| l.node_id)
+            .filter(|id| *id != 0)
+    }
+}
+
+impl CAkDialogueEvent {
+    /// Decodes the decision tree, `endian` being the byte order of the
+    /// soundbank the event was read from.
+    pub fn decision_tree(&self, endian: deku::ctx::Endian) -> Result<AkDecisionTreeNode, DekuError> {
+        AkDecisionTreeNode::read(&self.tree_data, self.tree_depth, endian)
+    }
+
+    /// Simulates the dialogue event by walking its decision tree with the
+    /// supplied switch or state IDs, one per argument. Returns the ID of the
+    /// audio node that would be played.
+    pub fn resolve(&self, args: &[u32], endian: deku::ctx::Endian) -> Option<u32> {
+        let tree = self.decision_tree(endian).ok()?;
+        tree.resolve(args, &self.tree_mode)
+            .map(|l| l.node_id)
+            .filter(|id| *id != 0)
+    }
+}
+
 #[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct AkDecisionTreeNode {
     pub key: u32,
@@ -1059,88 +2311,115 @@ pub struct AkDecisionTreeNode {
 }
 
 impl AkDecisionTreeNode {
-    fn read(
-        rest: &BitSlice<u8, Msb0>,
-        node_count: u16,
-        tree_size: u16,
-        tree_depth: u32,
-        current_depth: u32,
-    ) -> Result<(&BitSlice<u8, Msb0>, Vec<AkDecisionTreeNode>), DekuError> {
-        let mut nodes = vec![];
-        let mut result_rest = rest;
-
-        for _ in 0..node_count {
-            let (rest, key) = u32::read(result_rest, ())?;
-            let (rest, node_id, index, child_count) = {
-                let (rest, node_id) = u32::read(rest, ())?;
-
-                let index = (node_id & 0xFFFF) as u16;
-                let child_count = (node_id >> 16 & 0xFFFF) as u16;
-
-                // If it's reliable enough for the wwiser people...
-                if index > tree_size || child_count > tree_size || current_depth == tree_depth {
-                    (rest, node_id, 0, 0)
-                } else {
-                    (rest, 0, index, child_count)
-                }
-            };
+    /// Size of a single encoded node in the tree data.
+    const NODE_SIZE: usize = 0xC;
+
+    /// Decodes the flattened decision tree as it is stored in `tree_data`.
+    /// Branches refer to their children by index, the root is the first node.
+    /// The tree data is kept as raw bytes in the byte order of the soundbank,
+    /// which has to be passed in as `endian`. Trees whose branches share
+    /// children are an error, as are trees nested deeper than they have nodes.
+    pub fn read(tree_data: &[u8], tree_depth: u32, endian: deku::ctx::Endian) -> Result<AkDecisionTreeNode, DekuError> {
+        let node_count = tree_data.len() / Self::NODE_SIZE;
+        let mut reader = DecisionTreeReader {
+            tree_data,
+            node_count,
+            tree_depth,
+            max_depth: tree_depth.min(node_count.try_into().unwrap_or(u32::MAX)),
+            endian,
+            visited: 0,
+        };
+
+        reader.parse_node(0, 0)
+    }
+    /// Walks the tree matching `args` against the node keys, in the same way
+    /// the engine picks an audio node for a set of switch and state values.
+    /// A key of 0 acts as the wildcard. In best match mode exact matches are
+    /// explored before wildcards. The weighted mode picks randomly at runtime,
+    /// so here the heaviest of all the matching leaves is chosen instead.
+    pub fn resolve(&self, args: &[u32], mode: &AkDecisionTreeMode) -> Option<&AkDecisionTreeNode> {
+        match mode {
+            AkDecisionTreeMode::BestMatch => self.best_match(args),
+            AkDecisionTreeMode::Weighted => {
+                let mut leaves = vec![];
+                self.matching_leaves(args, &mut leaves);
+                leaves.into_iter()
+                    .rev()
+                    .max_by_key(|l| l.weight)
+            },
+        }
+    }
 
-            let (rest, weight) = u16::read(rest, ())?;
-            let (rest, probability) = u16::read(rest, ())?;
+    /// Visits every node depth-first, starting at the root, along with the
+    /// keys leading up to it. The root's own key isn't part of any path.
+    pub fn iter(&self) -> impl Iterator<Item = (Vec<u32>, &AkDecisionTreeNode)> {
+        let mut pending = vec![(vec![], self)];
+
+        std::iter::from_fn(move || {
+            let (path, node) = pending.pop()?;
+            for child in node.children.iter().rev() {
+                let mut child_path = path.clone();
+                child_path.push(child.key);
+                pending.push((child_path, child));
+            }
 
-            nodes.push(AkDecisionTreeNode {
-                key,
-                node_id,
-                index,
-                child_count,
-                weight,
-                probability,
-                children: vec![],
-            });
+            Some((path, node))
+        })
+    }
 
-            result_rest = rest;
-        }
+    fn best_match(&self, args: &[u32]) -> Option<&AkDecisionTreeNode> {
+        let (arg, rest) = match args.split_first() {
+            Some(a) => a,
+            None => return self.children.is_empty().then_some(self),
+        };
 
-        for node in nodes.iter_mut() {
-            let (rest, children) = AkDecisionTreeNode::read(
-                result_rest,
-                node.child_count,
-                tree_size,
-                tree_depth,
-                current_depth + 1,
-            )?;
-            node.children = children;
-            result_rest = rest;
-        }
+        let exact = self.children.iter().filter(|c| c.key == *arg);
+        let wildcard = self.children.iter().filter(|c| c.key == 0 && *arg != 0);
+        exact.chain(wildcard).find_map(|c| c.best_match(rest))
+    }
+
+    fn matching_leaves<'a>(&'a self, args: &[u32], leaves: &mut Vec<&'a AkDecisionTreeNode>) {
+        let (arg, rest) = match args.split_first() {
+            Some(a) => a,
+            None => {
+                if self.children.is_empty() {
+                    leaves.push(self);
+                }
+                return;
+            },
+        };
 
-        Ok((result_rest, nodes))
+        for child in self.children.iter().filter(|c| c.key == *arg || c.key == 0) {
+            child.matching_leaves(rest, leaves);
+        }
     }
 
+    /// Flattens the tree back into tree data, in the byte order `endian`.
     pub fn write(
         output: &mut BitVec<u8, Msb0>,
         nodes: &[&AkDecisionTreeNode],
+        endian: deku::ctx::Endian,
     ) -> Result<(), DekuError> {
         let mut current_layer = nodes
-            .to_vec()
-            .into_iter()
-            .map(|i| i.clone())
+            .iter()
+            .map(|i| (*i).clone())
             .collect::<Vec<AkDecisionTreeNode>>();
 
         while !current_layer.is_empty() {
             let mut next_layer = vec![];
             for node in current_layer.iter() {
-                node.key.write(output, ())?;
+                node.key.write(output, endian)?;
 
                 // Check if we're dealing with a leaf or a branch
                 if node.child_count != 0x0 {
-                    node.index.write(output, ())?;
-                    node.child_count.write(output, ())?;
+                    node.index.write(output, endian)?;
+                    node.child_count.write(output, endian)?;
                 } else {
-                    node.node_id.write(output, ())?;
+                    node.node_id.write(output, endian)?;
                 }
 
-                node.weight.write(output, ())?;
-                node.probability.write(output, ())?;
+                node.weight.write(output, endian)?;
+                node.probability.write(output, endian)?;
 
                 for child in node.children.iter() {
                     next_layer.push(child.clone());
@@ -1154,28 +2433,156 @@ impl AkDecisionTreeNode {
     }
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+/// Decodes the nodes of a decision tree while keeping count of them, a
+/// malformed tree could otherwise have overlapping branches expand into far
+/// more nodes than the data holds.
+struct DecisionTreeReader<'a> {
+    tree_data: &'a [u8],
+    node_count: usize,
+    tree_depth: u32,
+    max_depth: u32,
+    endian: deku::ctx::Endian,
+    visited: usize,
+}
+
+impl DecisionTreeReader<'_> {
+    fn parse_node(&mut self, offset: usize, current_depth: u32) -> Result<AkDecisionTreeNode, DekuError> {
+        self.visited += 1;
+        if self.visited > self.node_count {
+            return Err(DekuError::Parse(format!(
+                "Decision tree visits more than its {} nodes",
+                self.node_count,
+            )));
+        }
+
+        if current_depth > self.max_depth {
+            return Err(DekuError::Parse(format!(
+                "Decision tree is nested deeper than {} levels",
+                self.max_depth,
+            )));
+        }
+
+        let size = AkDecisionTreeNode::NODE_SIZE;
+        let start = offset * size;
+        let bytes = self.tree_data.get(start..start + size)
+            .ok_or(DekuError::Incomplete(NeedSize::new(size * 8)))?;
+
+        let endian = self.endian;
+        let field = |i: usize| match endian {
+            deku::ctx::Endian::Little => u16::from_le_bytes([bytes[i], bytes[i + 1]]),
+            deku::ctx::Endian::Big => u16::from_be_bytes([bytes[i], bytes[i + 1]]),
+        };
+        let key = crate::read_u32(bytes[0..4].try_into().unwrap(), endian);
+        let node_id = crate::read_u32(bytes[4..8].try_into().unwrap(), endian);
+        let index = field(4);
+        let child_count = field(6);
+        let weight = field(8);
+        let probability = field(10);
+
+        // There's no flag telling leaves and branches apart so we take a guess.
+        // If it's reliable enough for the wwiser people... Children are always
+        // stored after their parent, which also keeps a malformed tree from
+        // looping back onto itself.
+        let is_leaf = current_depth == self.tree_depth
+            || index as usize >= self.node_count
+            || index as usize <= offset
+            || child_count as usize > self.node_count;
+
+        if is_leaf {
+            return Ok(AkDecisionTreeNode {
+                key,
+                node_id,
+                index: 0,
+                child_count: 0,
+                weight,
+                probability,
+                children: vec![],
+            });
+        }
+
+        let children = (index as usize..index as usize + child_count as usize)
+            .map(|i| self.parse_node(i, current_depth + 1))
+            .collect::<Result<_, _>>()?;
+
+        Ok(AkDecisionTreeNode {
+            key,
+            node_id: 0,
+            index,
+            child_count,
+            weight,
+            probability,
+            children,
+        })
+    }
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
 #[deku_derive(DekuRead, DekuWrite)]
+#[deku(ctx = "endian: deku::ctx::Endian", endian = "endian")]
 pub struct AkGameSync {
     pub group_id: u32,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Clone, Debug, Serialize, Deserialize)]
 #[deku_derive(DekuRead, DekuWrite)]
+#[deku(ctx = "endian: deku::ctx::Endian", endian = "endian")]
 pub struct CAkFxShareSet {
     pub fx_base_initial_values: FxBaseInitialValues,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[deku_derive(DekuRead, DekuWrite)]
+#[deku(ctx = "endian: deku::ctx::Endian", endian = "endian")]
+pub struct CAkLFOModulator {
+    #[deku(
+        reader = "PropBundle::read_list(
+            deku::rest,
+            endian,
+        )",
+        writer = "PropBundle::write_list(
+            deku::output,
+            &self.prop_bundle.iter().collect::<Vec<_>>(),
+            endian,
+        )"
+    )]
+    pub prop_bundle: Vec<PropBundle>,
+    pub ranged_modifiers: PropRangedModifiers,
+    pub initial_rtpc: InitialRTPC,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[deku_derive(DekuRead, DekuWrite)]
+#[deku(ctx = "endian: deku::ctx::Endian", endian = "endian")]
+pub struct CAkEnvelopeModulator {
+    #[deku(
+        reader = "PropBundle::read_list(
+            deku::rest,
+            endian,
+        )",
+        writer = "PropBundle::write_list(
+            deku::output,
+            &self.prop_bundle.iter().collect::<Vec<_>>(),
+            endian,
+        )"
+    )]
+    pub prop_bundle: Vec<PropBundle>,
+    pub ranged_modifiers: PropRangedModifiers,
+    pub initial_rtpc: InitialRTPC,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
 #[deku_derive(DekuRead, DekuWrite)]
+#[deku(ctx = "endian: deku::ctx::Endian", endian = "endian")]
 pub struct CAkTimeModulator {
     #[deku(
         reader = "PropBundle::read_list(
             deku::rest,
+            endian,
         )",
         writer = "PropBundle::write_list(
             deku::output,
             &self.prop_bundle.iter().collect::<Vec<_>>(),
+            endian,
         )"
     )]
     pub prop_bundle: Vec<PropBundle>,
@@ -1183,84 +2590,91 @@ pub struct CAkTimeModulator {
     pub initial_rtpc: InitialRTPC,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Clone, Debug, Serialize, Deserialize)]
 #[deku_derive(DekuRead, DekuWrite)]
+#[deku(ctx = "endian: deku::ctx::Endian", endian = "endian")]
 pub struct CAkMusicRanSeqCntr {
     pub music_trans_node_params: MusicTransNodeParams,
     #[serde(skip)]
     #[deku(update = "self.playlist_items.len()")]
     playlist_item_count: u32,
-    #[deku(count = "playlist_item_count")]
+    #[deku(count = "checked_count(deku::rest, *playlist_item_count)?")]
     pub playlist_items: Vec<AkMusicRanSeqPlaylistItem>,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Clone, Debug, Serialize, Deserialize)]
 #[deku_derive(DekuRead, DekuWrite)]
+#[deku(ctx = "endian: deku::ctx::Endian", endian = "endian")]
 pub struct AkMusicRanSeqPlaylistItem {
-    segment_id: u32,
-    playlist_item_id: i32,
-    child_count: u32,
-    ers_type: u32,
-    loop_base: i16,
-    loop_min: i16,
-    loop_max: i16,
-    weight: u32,
-    avoid_repeat_count: u16,
-    use_weight: u8,
-    shuffle: u8,
+    pub segment_id: u32,
+    pub playlist_item_id: i32,
+    pub child_count: u32,
+    pub ers_type: u32,
+    pub loop_base: i16,
+    pub loop_min: i16,
+    pub loop_max: i16,
+    pub weight: u32,
+    pub avoid_repeat_count: u16,
+    pub use_weight: u8,
+    pub shuffle: u8,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Clone, Debug, Serialize, Deserialize)]
 #[deku_derive(DekuRead, DekuWrite)]
+#[deku(ctx = "endian: deku::ctx::Endian", endian = "endian")]
 pub struct MusicTransNodeParams {
     pub music_node_params: MusicNodeParams,
     #[serde(skip)]
     #[deku(update = "self.transition_rules.len()")]
     transition_rule_count: u32,
-    #[deku(count = "transition_rule_count")]
+    #[deku(count = "checked_count(deku::rest, *transition_rule_count)?")]
     pub transition_rules: Vec<AkMusicTransitionRule>,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Clone, Debug, Serialize, Deserialize)]
 #[deku_derive(DekuRead, DekuWrite)]
+#[deku(ctx = "endian: deku::ctx::Endian", endian = "endian")]
 pub struct AkMusicTransitionRule {
     #[serde(skip)]
     #[deku(update = "self.source_ids.len()")]
     source_transition_rule_count: u32,
-    #[deku(count = "source_transition_rule_count")]
-    source_ids: Vec<i32>,
+    #[deku(count = "checked_count(deku::rest, *source_transition_rule_count)?")]
+    pub source_ids: Vec<i32>,
     #[serde(skip)]
     #[deku(update = "self.destination_ids.len()")]
     destination_transition_rule_count: u32,
-    #[deku(count = "destination_transition_rule_count")]
-    destination_ids: Vec<i32>,
-    source_transition_rule: AkMusicTransSrcRule,
-    destination_transition_rule: AkMusicTransDstRule,
-    alloc_trans_object_flag: u8,
+    #[deku(count = "checked_count(deku::rest, *destination_transition_rule_count)?")]
+    pub destination_ids: Vec<i32>,
+    pub source_transition_rule: AkMusicTransSrcRule,
+    pub destination_transition_rule: AkMusicTransDstRule,
+    pub alloc_trans_object_flag: u8,
     #[deku(skip, cond = "*alloc_trans_object_flag == 0")]
-    transition_object: AkMusicTransitionObject,
+    pub transition_object: AkMusicTransitionObject,
 }
 
-#[derive(Debug, Default, Serialize, Deserialize)]
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
 #[deku_derive(DekuRead, DekuWrite)]
+#[deku(ctx = "endian: deku::ctx::Endian", endian = "endian")]
 pub struct AkMusicTransitionObject {
-    segment_id: u32,
-    fade_out: AkMusicFade,
-    fade_in: AkMusicFade,
-    play_pre_entry: u8,
-    play_post_exit: u8,
+    pub segment_id: u32,
+    pub fade_out: AkMusicFade,
+    pub fade_in: AkMusicFade,
+    pub play_pre_entry: u8,
+    pub play_post_exit: u8,
 }
 
-#[derive(Debug, Default, Serialize, Deserialize)]
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
 #[deku_derive(DekuRead, DekuWrite)]
+#[deku(ctx = "endian: deku::ctx::Endian", endian = "endian")]
 pub struct AkMusicFade {
     transition_time: i32,
     curve: AkCurveInterpolation,
     offset: i32,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Clone, Debug, Serialize, Deserialize)]
 #[deku_derive(DekuRead, DekuWrite)]
+#[deku(ctx = "endian: deku::ctx::Endian", endian = "endian")]
 pub struct AkMusicTransSrcRule {
     transition_time: i32,
     fade_curve: AkCurveInterpolation,
@@ -1270,8 +2684,9 @@ pub struct AkMusicTransSrcRule {
     play_post_exit: u8,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Clone, Debug, Serialize, Deserialize)]
 #[deku_derive(DekuRead, DekuWrite)]
+#[deku(ctx = "endian: deku::ctx::Endian", endian = "endian")]
 pub struct AkMusicTransDstRule {
     transition_time: i32,
     fade_curve: AkCurveInterpolation,
@@ -1284,20 +2699,22 @@ pub struct AkMusicTransDstRule {
     destination_match_source_cue_name: u8,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Clone, Debug, Serialize, Deserialize)]
 #[deku_derive(DekuRead, DekuWrite)]
+#[deku(ctx = "endian: deku::ctx::Endian", endian = "endian")]
 pub struct CAkMusicSegment {
     pub music_node_params: MusicNodeParams,
     pub duration: f64,
     #[serde(skip)]
     #[deku(update = "self.markers.len()")]
     marker_count: u32,
-    #[deku(count = "marker_count")]
+    #[deku(count = "checked_count(deku::rest, *marker_count)?")]
     pub markers: Vec<AkMusicMarkerWwise>,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Clone, Debug, Serialize, Deserialize)]
 #[deku_derive(DekuRead, DekuWrite)]
+#[deku(ctx = "endian: deku::ctx::Endian", endian = "endian")]
 pub struct MusicNodeParams {
     pub flags: u8,
     pub node_base_params: NodeBaseParams,
@@ -1306,30 +2723,27 @@ pub struct MusicNodeParams {
     #[serde(skip)]
     #[deku(update = "self.stingers.len()")]
     stinger_count: u32,
-    #[deku(count = "stinger_count")]
+    #[deku(count = "checked_count(deku::rest, *stinger_count)?")]
     pub stingers: Vec<CAkStinger>,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Clone, Debug, Serialize, Deserialize)]
 #[deku_derive(DekuRead, DekuWrite)]
+#[deku(ctx = "endian: deku::ctx::Endian", endian = "endian")]
 pub struct AkMusicMarkerWwise {
-    id: u32,
-    position: f64,
-    #[deku(update = "
-            if self.string.is_empty() {
-                0
-            } else {
-                self.string.as_bytes_with_nul().len()
-            }
-        ")]
+    pub id: u32,
+    /// Position within the segment in milliseconds.
+    pub position: f64,
+    #[deku(update = "if self.string.is_empty() { 0 } else { self.string.as_bytes_with_nul().len() }")]
     string_length: u32,
     #[serde(with = "crate::serialization::cstring")]
     #[deku(skip, cond = "*string_length == 0")]
-    string: ffi::CString,
+    pub string: ffi::CString,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Clone, Debug, Serialize, Deserialize)]
 #[deku_derive(DekuRead, DekuWrite)]
+#[deku(ctx = "endian: deku::ctx::Endian", endian = "endian")]
 pub struct AkMeterInfo {
     pub grid_period: f64,
     pub grid_offset: f64,
@@ -1339,46 +2753,86 @@ pub struct AkMeterInfo {
     pub meter_info_flag: u8,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+impl AkMeterInfo {
+    const OVERRIDE_PARENT: u8 = 0x1;
+
+    /// Whether the tempo, time signature and grid are this node's own rather
+    /// than inherited from its parent. Only the lowest bit of the flag has a
+    /// known meaning, the other bits are left alone when setting it.
+    pub fn overrides_parent(&self) -> bool {
+        self.meter_info_flag & Self::OVERRIDE_PARENT != 0
+    }
+
+    pub fn set_overrides_parent(&mut self, value: bool) {
+        if value {
+            self.meter_info_flag |= Self::OVERRIDE_PARENT;
+        } else {
+            self.meter_info_flag &= !Self::OVERRIDE_PARENT;
+        }
+    }
+
+    /// The length of a beat, the note value of the time signature, in
+    /// milliseconds.
+    pub fn ms_per_beat(&self) -> f64 {
+        60_000.0 / self.tempo as f64
+    }
+
+    pub fn ms_per_bar(&self) -> f64 {
+        self.ms_per_beat() * self.time_signature_beat_count as f64
+    }
+
+    /// Converts a position in milliseconds, like that of a marker, into the
+    /// number of whole bars before it and the beat within its bar. Both count
+    /// from 0 so the start of the segment is `(0, 0.0)`.
+    pub fn position_to_bars_beats(&self, ms: f64) -> (u32, f64) {
+        let bars = (ms / self.ms_per_bar()).floor();
+        let beats = (ms - bars * self.ms_per_bar()) / self.ms_per_beat();
+        (bars as u32, beats)
+    }
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
 #[deku_derive(DekuRead, DekuWrite)]
+#[deku(ctx = "endian: deku::ctx::Endian", endian = "endian")]
 pub struct CAkStinger {
-    trigger_id: u32,
-    segment_id: u32,
-    sync_play_at: AkSyncType,
-    cue_filter_hash: u32,
-    dont_repeat_time: i32,
-    segment_look_head_count: u32,
+    pub trigger_id: u32,
+    pub segment_id: u32,
+    pub sync_play_at: AkSyncType,
+    pub cue_filter_hash: u32,
+    pub dont_repeat_time: i32,
+    pub segment_look_head_count: u32,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Clone, Debug, Serialize, Deserialize)]
 #[deku_derive(DekuRead, DekuWrite)]
+#[deku(ctx = "endian: deku::ctx::Endian", endian = "endian")]
 pub struct CAkMusicTrack {
     pub flags: u8,
     #[serde(skip)]
     #[deku(update = "self.sources.len()")]
     source_count: u32,
-    #[deku(count = "source_count")]
+    #[deku(count = "checked_count(deku::rest, *source_count)?")]
     pub sources: Vec<AkBankSourceData>,
     #[serde(skip)]
     #[deku(update = "self.playlist.len()")]
     playlist_item_count: u32,
-    #[deku(count = "playlist_item_count")]
+    #[deku(count = "checked_count(deku::rest, *playlist_item_count)?")]
     pub playlist: Vec<AkTrackSrcInfo>,
     #[deku(skip, cond = "*playlist_item_count == 0")]
     pub subtrack_count: u32,
     #[serde(skip)]
     #[deku(update = "self.clip_items.len()")]
     clip_item_count: u32,
-    #[deku(count = "clip_item_count")]
+    #[deku(count = "checked_count(deku::rest, *clip_item_count)?")]
     pub clip_items: Vec<AkClipAutomation>,
     pub node_base_params: NodeBaseParams,
     pub track_type: u8,
     pub look_ahead_time: i32,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Clone, Debug, Serialize, Deserialize)]
 #[deku_derive(DekuRead, DekuWrite)]
-#[deku(type = "u32")]
+#[deku(type = "u32", ctx = "endian: deku::ctx::Endian", endian = "endian")]
 pub enum AkClipAutomationType {
     #[deku(id = "0x00")]
     Volume,
@@ -1392,20 +2846,35 @@ pub enum AkClipAutomationType {
     FadeOut,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Clone, Debug, Serialize, Deserialize)]
 #[deku_derive(DekuRead, DekuWrite)]
+#[deku(ctx = "endian: deku::ctx::Endian", endian = "endian")]
 pub struct AkClipAutomation {
     pub clip_index: u32,
     pub auto_type: AkClipAutomationType,
     #[serde(skip)]
     #[deku(update = "self.graph_points.len()")]
     graph_point_count: u32,
-    #[deku(count = "graph_point_count")]
+    #[deku(count = "checked_count(deku::rest, *graph_point_count)?")]
     pub graph_points: Vec<AkRTPCGraphPoint>,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+impl AkClipAutomation {
+    /// Adds a point to the automation curve at the position its `from`
+    /// dictates and returns its index. The point count is kept in sync so the
+    /// clip doesn't need a `prepare_export` to encode correctly.
+    pub fn add_graph_point(&mut self, point: AkRTPCGraphPoint) -> Result<usize, DekuError> {
+        add_graph_point(&mut self.graph_points, &mut self.graph_point_count, point)
+    }
+
+    pub fn remove_graph_point(&mut self, index: usize) -> Option<AkRTPCGraphPoint> {
+        remove_graph_point(&mut self.graph_points, &mut self.graph_point_count, index)
+    }
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
 #[deku_derive(DekuRead, DekuWrite)]
+#[deku(ctx = "endian: deku::ctx::Endian", endian = "endian")]
 pub struct AkTrackSrcInfo {
     pub track_id: u32,
     pub source_id: u32,
@@ -1416,73 +2885,99 @@ pub struct AkTrackSrcInfo {
     pub source_duration: f64,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Clone, Debug, Serialize, Deserialize)]
 #[deku_derive(DekuRead, DekuWrite)]
+#[deku(ctx = "endian: deku::ctx::Endian", endian = "endian")]
 pub struct CAkFxCustom {
     pub fx_base_initial_values: FxBaseInitialValues,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Clone, Debug, Serialize, Deserialize)]
 #[deku_derive(DekuRead, DekuWrite)]
+#[deku(ctx = "endian: deku::ctx::Endian", endian = "endian")]
 pub struct CAkAuxBus {
     pub initial_values: BusInitialValues,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+impl CAkAuxBus {
+    /// The buses ducked while this bus is playing audio.
+    pub fn ducking_rules(&self) -> &[AkDuckInfo] {
+        &self.initial_values.ducks
+    }
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
 #[deku_derive(DekuRead, DekuWrite)]
+#[deku(ctx = "endian: deku::ctx::Endian", endian = "endian")]
 pub struct CAkAudioDevice {
     pub fx_base_initial_values: FxBaseInitialValues,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Clone, Debug, Serialize, Deserialize)]
 #[deku_derive(DekuRead, DekuWrite)]
+#[deku(ctx = "endian: deku::ctx::Endian", endian = "endian")]
 pub struct FxBaseInitialValues {
     pub fx_id: u32,
     #[serde(skip)]
     #[deku(update = "self.params.len()")]
     params_size: u32,
-    #[deku(count = "params_size")]
+    #[deku(count = "checked_count(deku::rest, *params_size)?")]
     pub params: Vec<u8>,
     #[serde(skip)]
     #[deku(update = "self.media.len()")]
     media_count: u8,
-    #[deku(count = "media_count")]
+    #[deku(count = "checked_count(deku::rest, *media_count)?")]
     pub media: Vec<AkMediaMap>,
     pub initial_rtpc: InitialRTPC,
     pub state_chunk: StateChunk,
     #[serde(skip)]
     #[deku(update = "self.property_values.len()")]
     property_value_count: i16,
-    #[deku(count = "property_value_count")]
+    #[deku(count = "checked_count(deku::rest, *property_value_count)?")]
     pub property_values: Vec<PluginPropertyValue>,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Clone, Debug, Serialize, Deserialize)]
 #[deku_derive(DekuRead, DekuWrite)]
+#[deku(ctx = "endian: deku::ctx::Endian", endian = "endian")]
 pub struct PluginPropertyValue {
     pub property: AkPropID,
     pub rtpc_accum: AkRtpcAccum,
     pub value: f32,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Clone, Debug, Serialize, Deserialize)]
 #[deku_derive(DekuRead, DekuWrite)]
+#[deku(ctx = "endian: deku::ctx::Endian", endian = "endian")]
 pub struct AkMediaMap {
     pub index: u8,
     pub source_id: u32,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Clone, Debug, Serialize, Deserialize)]
 #[deku_derive(DekuRead, DekuWrite)]
+#[deku(ctx = "endian: deku::ctx::Endian", endian = "endian")]
 pub struct CAkBus {
     pub initial_values: BusInitialValues,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+impl CAkBus {
+    /// The buses ducked while this bus is playing audio.
+    pub fn ducking_rules(&self) -> &[AkDuckInfo] {
+        &self.initial_values.ducks
+    }
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
 #[deku_derive(DekuRead, DekuWrite)]
+#[deku(ctx = "endian: deku::ctx::Endian", endian = "endian")]
 pub struct BusInitialValues {
-    pub override_bus_id: u32,
-    #[deku(skip, cond = "*override_bus_id != 0")]
+    #[deku(
+        reader = "ObjectId::read(deku::rest, endian)",
+        writer = "ObjectId::write(deku::output, &self.override_bus_id, endian)",
+    )]
+    pub override_bus_id: ObjectId,
+    #[deku(skip, cond = "override_bus_id.as_hash() != 0")]
     pub device_share_set_id: u32,
     pub bus_initial_params: BusInitialParams,
     pub recovery_time: i32,
@@ -1490,7 +2985,7 @@ pub struct BusInitialValues {
     #[serde(skip)]
     #[deku(update = "self.ducks.len()")]
     duck_count: u32,
-    #[deku(count = "duck_count")]
+    #[deku(count = "checked_count(deku::rest, *duck_count)?")]
     pub ducks: Vec<AkDuckInfo>,
     pub bus_initial_fx_params: BusInitialFxParams,
     pub override_attachment_params: u8,
@@ -1498,8 +2993,9 @@ pub struct BusInitialValues {
     pub state_chunk: StateChunk,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Clone, Debug, Serialize, Deserialize)]
 #[deku_derive(DekuRead, DekuWrite)]
+#[deku(ctx = "endian: deku::ctx::Endian", endian = "endian")]
 pub struct AkDuckInfo {
     pub bus_id: u32,
     pub duck_volume: f32,
@@ -1509,16 +3005,19 @@ pub struct AkDuckInfo {
     pub target_prop: AkPropID,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Clone, Debug, Serialize, Deserialize)]
 #[deku_derive(DekuRead, DekuWrite)]
+#[deku(ctx = "endian: deku::ctx::Endian", endian = "endian")]
 pub struct BusInitialParams {
     #[deku(
         reader = "PropBundle::read_list(
             deku::rest,
+            endian,
         )",
         writer = "PropBundle::write_list(
             deku::output,
             &self.prop_bundle.iter().collect::<Vec<_>>(),
+            endian,
         )"
     )]
     pub prop_bundle: Vec<PropBundle>,
@@ -1530,22 +3029,24 @@ pub struct BusInitialParams {
     pub hdr_flags: u8,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Clone, Debug, Serialize, Deserialize)]
 #[deku_derive(DekuRead, DekuWrite)]
+#[deku(ctx = "endian: deku::ctx::Endian", endian = "endian")]
 pub struct BusInitialFxParams {
     #[serde(skip)]
     #[deku(update = "self.fx.len()")]
     fx_count: u8,
     #[deku(skip, cond = "*fx_count == 0")]
     pub fx_bypass: u8,
-    #[deku(count = "fx_count")]
+    #[deku(count = "checked_count(deku::rest, *fx_count)?")]
     pub fx: Vec<FXChunk>,
     pub fx_id_0: u32,
     pub is_share_set_0: u8,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Clone, Debug, Serialize, Deserialize)]
 #[deku_derive(DekuRead, DekuWrite)]
+#[deku(ctx = "endian: deku::ctx::Endian", endian = "endian")]
 pub struct FXChunk {
     pub fx_index: u8,
     pub fx_id: u32,
@@ -1553,19 +3054,26 @@ pub struct FXChunk {
     pub is_rendered: u8,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Clone, Debug, Serialize, Deserialize)]
 #[deku_derive(DekuRead, DekuWrite)]
+#[deku(ctx = "endian: deku::ctx::Endian", endian = "endian")]
 pub struct CAkAction {
     pub action_type: u16,
-    pub external_id: u32,
+    #[deku(
+        reader = "ObjectId::read(deku::rest, endian)",
+        writer = "ObjectId::write(deku::output, &self.external_id, endian)",
+    )]
+    pub external_id: ObjectId,
     pub is_bus: u8,
     #[deku(
         reader = "PropBundle::read_list(
             deku::rest,
+            endian,
         )",
         writer = "PropBundle::write_list(
             deku::output,
             &self.prop_bundle.iter().collect::<Vec<_>>(),
+            endian,
         )"
     )]
     pub prop_bundle: Vec<PropBundle>,
@@ -1574,116 +3082,173 @@ pub struct CAkAction {
     pub params: CAkActionParams,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Clone, Debug, Serialize, Deserialize)]
 #[deku_derive(DekuRead, DekuWrite)]
+#[deku(ctx = "endian: deku::ctx::Endian", endian = "endian")]
 pub struct CAkActionSetState {
+    // SetState actions used to be read as CAkActionSetSwitch, which has the
+    // same layout, so older JSON names the fields after the switch.
+    #[serde(alias = "switch_group_id")]
     pub state_group_id: u32,
+    #[serde(alias = "switch_state_id")]
     pub target_state_id: u32,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Clone, Debug, Serialize, Deserialize)]
 #[deku_derive(DekuRead, DekuWrite)]
+#[deku(ctx = "endian: deku::ctx::Endian", endian = "endian")]
 pub struct CAkActionSetSwitch {
     pub switch_group_id: u32,
     pub switch_state_id: u32,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Clone, Debug, Serialize, Deserialize)]
 #[deku_derive(DekuRead, DekuWrite)]
+#[deku(ctx = "endian: deku::ctx::Endian", endian = "endian")]
 pub struct CAkActionMute {
     pub fade_curve: u8,
     pub except: CAkActionParamsExcept,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[deku_derive(DekuRead, DekuWrite)]
+#[deku(ctx = "endian: deku::ctx::Endian", endian = "endian")]
+pub struct CAkActionBypassFX {
+    pub is_bypass: u8,
+    /// Bit per effect slot, with the top bit covering all of them.
+    pub target_mask: u8,
+    pub except: CAkActionParamsExcept,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
 #[deku_derive(DekuRead, DekuWrite)]
+#[deku(ctx = "endian: deku::ctx::Endian", endian = "endian")]
 pub struct CAkActionSetAkProp {
     pub fade_curve: u8,
     pub set_ak_prop: CAkActionParamsSetAkProp,
     pub except: CAkActionParamsExcept,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Clone, Debug, Serialize, Deserialize)]
 #[deku_derive(DekuRead, DekuWrite)]
+#[deku(ctx = "endian: deku::ctx::Endian", endian = "endian")]
 pub struct CAkActionParamsSetAkProp {
     pub value_meaning: u8,
     pub randomizer_modifier: RandomizerModifier,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Clone, Debug, Serialize, Deserialize)]
 #[deku_derive(DekuRead, DekuWrite)]
+#[deku(ctx = "endian: deku::ctx::Endian", endian = "endian")]
 pub struct RandomizerModifier {
     pub base: f32,
     pub min: f32,
     pub max: f32,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Clone, Debug, Serialize, Deserialize)]
 #[deku_derive(DekuRead, DekuWrite)]
+#[deku(ctx = "endian: deku::ctx::Endian", endian = "endian")]
 pub struct CAkActionPlay {
     pub fade_curve: u8,
     pub bank_id: u32,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Clone, Debug, Serialize, Deserialize)]
 #[deku_derive(DekuRead, DekuWrite)]
+#[deku(ctx = "endian: deku::ctx::Endian", endian = "endian")]
 pub struct CAkActionPause {
     pub fade_curve: u8,
     pub pause: CAkActionParamsPause,
     pub except: CAkActionParamsExcept,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Clone, Debug, Serialize, Deserialize)]
 #[deku_derive(DekuRead, DekuWrite)]
+#[deku(ctx = "endian: deku::ctx::Endian", endian = "endian")]
 pub struct CAkActionParamsPause {
     flags: u8,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Clone, Debug, Serialize, Deserialize)]
 #[deku_derive(DekuRead, DekuWrite)]
+#[deku(ctx = "endian: deku::ctx::Endian", endian = "endian")]
 pub struct CAkActionStop {
     pub stop: CAkActionParamsStop,
     pub except: CAkActionParamsExcept,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Clone, Debug, Serialize, Deserialize)]
 #[deku_derive(DekuRead, DekuWrite)]
+#[deku(ctx = "endian: deku::ctx::Endian", endian = "endian")]
 pub struct CAkActionParamsStop {
     flags1: u8,
     flags2: u8,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[deku_derive(DekuRead, DekuWrite)]
+#[deku(ctx = "endian: deku::ctx::Endian", endian = "endian")]
+pub struct CAkActionSeek {
+    pub is_seek_relative_to_duration: u8,
+    pub seek_value: RandomizerModifier,
+    pub snap_to_nearest_marker: u8,
+    pub except: CAkActionParamsExcept,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[deku_derive(DekuRead, DekuWrite)]
+#[deku(ctx = "endian: deku::ctx::Endian", endian = "endian")]
+pub struct CAkActionSetGameParameter {
+    pub fade_curve: u8,
+    pub set_game_parameter: CAkActionParamsSetGameParameter,
+    pub except: CAkActionParamsExcept,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[deku_derive(DekuRead, DekuWrite)]
+#[deku(ctx = "endian: deku::ctx::Endian", endian = "endian")]
+pub struct CAkActionParamsSetGameParameter {
+    pub bypass_transition: u8,
+    pub value_meaning: u8,
+    pub randomizer_modifier: RandomizerModifier,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
 #[deku_derive(DekuRead, DekuWrite)]
+#[deku(ctx = "endian: deku::ctx::Endian", endian = "endian")]
 pub struct CAkActionParamsExcept {
     #[serde(skip)]
     #[deku(update = "self.exceptions.len()")]
     count: u8,
-    #[deku(count = "count")]
+    #[deku(count = "checked_count(deku::rest, *count)?")]
     pub exceptions: Vec<CAkActionParamsExceptEntry>,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Clone, Debug, Serialize, Deserialize)]
 #[deku_derive(DekuRead, DekuWrite)]
+#[deku(ctx = "endian: deku::ctx::Endian", endian = "endian")]
 pub struct CAkActionParamsExceptEntry {
     pub object_id: u32,
     pub is_bus: u8,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Clone, Debug, Serialize, Deserialize)]
 #[deku_derive(DekuRead, DekuWrite)]
+#[deku(ctx = "endian: deku::ctx::Endian", endian = "endian")]
 pub struct AkPropBundleByte {
     #[serde(skip)]
     #[deku(update = "self.types.len()")]
     count: u8,
-    #[deku(count = "count")]
+    #[deku(count = "checked_count(deku::rest, *count)?")]
     pub types: Vec<AkPropID>,
-    #[deku(count = "count")]
+    #[deku(count = "checked_count(deku::rest, *count)?")]
     pub values: Vec<f32>,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Clone, Debug, Serialize, Deserialize)]
 #[deku_derive(DekuRead, DekuWrite)]
+#[deku(ctx = "endian: deku::ctx::Endian", endian = "endian")]
 pub struct CAkSwitchCntr {
     pub node_base_params: NodeBaseParams,
     pub group_type: u8,
@@ -1694,28 +3259,30 @@ pub struct CAkSwitchCntr {
     #[serde(skip)]
     #[deku(update = "self.switch_groups.len()")]
     switch_group_count: u32,
-    #[deku(count = "switch_group_count")]
+    #[deku(count = "checked_count(deku::rest, *switch_group_count)?")]
     pub switch_groups: Vec<CAkSwitchPackage>,
     #[serde(skip)]
     #[deku(update = "self.switch_params.len()")]
     switch_param_count: u32,
-    #[deku(count = "switch_param_count")]
+    #[deku(count = "checked_count(deku::rest, *switch_param_count)?")]
     pub switch_params: Vec<AkSwitchNodeParams>,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Clone, Debug, Serialize, Deserialize)]
 #[deku_derive(DekuRead, DekuWrite)]
+#[deku(ctx = "endian: deku::ctx::Endian", endian = "endian")]
 pub struct CAkSwitchPackage {
     pub switch_id: u32,
     #[serde(skip)]
     #[deku(update = "self.nodes.len()")]
     node_count: u32,
-    #[deku(count = "node_count")]
+    #[deku(count = "checked_count(deku::rest, *node_count)?")]
     pub nodes: Vec<u32>,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Clone, Debug, Serialize, Deserialize)]
 #[deku_derive(DekuRead, DekuWrite)]
+#[deku(ctx = "endian: deku::ctx::Endian", endian = "endian")]
 pub struct AkSwitchNodeParams {
     pub node_id: u32,
     #[deku(bits = "1")]
@@ -1754,28 +3321,31 @@ pub struct AkSwitchNodeParams {
     pub fade_in_time: i32,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Clone, Debug, Serialize, Deserialize)]
 #[deku_derive(DekuRead, DekuWrite)]
+#[deku(ctx = "endian: deku::ctx::Endian", endian = "endian")]
 pub struct CAkActorMixer {
     pub node_base_params: NodeBaseParams,
     pub children: Children,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Clone, Debug, Serialize, Deserialize)]
 #[deku_derive(DekuRead, DekuWrite)]
+#[deku(ctx = "endian: deku::ctx::Endian", endian = "endian")]
 pub struct CAkLayerCntr {
     pub node_base_params: NodeBaseParams,
     pub children: Children,
     #[serde(skip)]
     #[deku(update = "self.layers.len()")]
     layer_count: u32,
-    #[deku(count = "layer_count")]
+    #[deku(count = "checked_count(deku::rest, *layer_count)?")]
     pub layers: Vec<CAkLayer>,
     pub is_continuous_validation: u8,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Clone, Debug, Serialize, Deserialize)]
 #[deku_derive(DekuRead, DekuWrite)]
+#[deku(ctx = "endian: deku::ctx::Endian", endian = "endian")]
 pub struct CAkLayer {
     pub layer_id: u32,
     pub initial_rtpc: InitialRTPC,
@@ -1784,23 +3354,25 @@ pub struct CAkLayer {
     #[serde(skip)]
     #[deku(update = "self.associated_children.len()")]
     associated_childen_count: u32,
-    #[deku(count = "associated_childen_count")]
+    #[deku(count = "checked_count(deku::rest, *associated_childen_count)?")]
     pub associated_children: Vec<CAssociatedChildData>,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Clone, Debug, Serialize, Deserialize)]
 #[deku_derive(DekuRead, DekuWrite)]
+#[deku(ctx = "endian: deku::ctx::Endian", endian = "endian")]
 pub struct CAssociatedChildData {
     pub associated_child_id: u32,
     #[serde(skip)]
     #[deku(update = "self.graph_points.len()")]
     graph_point_count: u32,
-    #[deku(count = "graph_point_count")]
+    #[deku(count = "checked_count(deku::rest, *graph_point_count)?")]
     pub graph_points: Vec<AkRTPCGraphPoint>,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Clone, Debug, Serialize, Deserialize)]
 #[deku_derive(DekuRead, DekuWrite)]
+#[deku(ctx = "endian: deku::ctx::Endian", endian = "endian")]
 pub struct CAkRanSeqCntr {
     pub node_base_params: NodeBaseParams,
     loop_count: u16,
@@ -1818,102 +3390,139 @@ pub struct CAkRanSeqCntr {
     pub playlist: CAkPlaylist,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Clone, Debug, Serialize, Deserialize)]
 #[deku_derive(DekuRead, DekuWrite)]
+#[deku(ctx = "endian: deku::ctx::Endian", endian = "endian")]
 pub struct Children {
     #[serde(skip)]
     #[deku(update = "self.items.len()")]
     count: u32,
-    #[deku(count = "count")]
+    #[deku(count = "checked_count(deku::rest, *count)?")]
     pub items: Vec<u32>,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Clone, Debug, Serialize, Deserialize)]
 #[deku_derive(DekuRead, DekuWrite)]
+#[deku(ctx = "endian: deku::ctx::Endian", endian = "endian")]
 pub struct CAkPlaylist {
     #[serde(skip)]
     #[deku(update = "self.items.len()")]
     count: u16,
-    #[deku(count = "count")]
-    items: Vec<CAkPlaylistItem>,
+    #[deku(count = "checked_count(deku::rest, *count)?")]
+    pub items: Vec<CAkPlaylistItem>,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Clone, Debug, Serialize, Deserialize)]
 #[deku_derive(DekuRead, DekuWrite)]
+#[deku(ctx = "endian: deku::ctx::Endian", endian = "endian")]
 pub struct CAkPlaylistItem {
-    play_id: u32,
-    weight: i32,
+    pub play_id: u32,
+    pub weight: i32,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Clone, Debug, Serialize, Deserialize)]
 #[deku_derive(DekuRead, DekuWrite)]
+#[deku(ctx = "endian: deku::ctx::Endian", endian = "endian")]
 pub struct CAkState {
     #[serde(skip)]
     #[deku(update = "self.parameters.len()")]
     entry_count: u16,
-    #[deku(count = "entry_count")]
+    #[deku(count = "checked_count(deku::rest, *entry_count)?")]
     parameters: Vec<u16>,
-    #[deku(count = "entry_count")]
+    #[deku(count = "checked_count(deku::rest, *entry_count)?")]
     values: Vec<f32>,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Clone, Debug, Serialize, Deserialize)]
 #[deku_derive(DekuRead, DekuWrite)]
+#[deku(ctx = "endian: deku::ctx::Endian", endian = "endian")]
 pub struct CAkAttentuation {
     pub is_cone_enabled: u8,
     pub curves_to_use: [u8; 7],
     #[serde(skip)]
     #[deku(update = "self.curves.len()")]
     curve_count: u8,
-    #[deku(count = "curve_count")]
+    #[deku(count = "checked_count(deku::rest, *curve_count)?")]
     pub curves: Vec<CAkConversionTable>,
     pub initial_rtpc: InitialRTPC,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Clone, Debug, Serialize, Deserialize)]
 #[deku_derive(DekuRead, DekuWrite)]
+#[deku(ctx = "endian: deku::ctx::Endian", endian = "endian")]
 pub struct CAkConversionTable {
     pub curve_scaling: AkCurveScaling,
     #[serde(skip)]
     #[deku(update = "self.points.len()")]
     point_count: u16,
-    #[deku(count = "point_count")]
+    #[deku(count = "checked_count(deku::rest, *point_count)?")]
     pub points: Vec<AkRTPCGraphPoint>,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+impl CAkConversionTable {
+    /// Adds a point to the attenuation curve at the position its `from`
+    /// dictates and returns its index. The point count is kept in sync so the
+    /// curve doesn't need a `prepare_export` to encode correctly.
+    pub fn add_point(&mut self, point: AkRTPCGraphPoint) -> Result<usize, DekuError> {
+        add_graph_point(&mut self.points, &mut self.point_count, point)
+    }
+
+    pub fn remove_point(&mut self, index: usize) -> Option<AkRTPCGraphPoint> {
+        remove_graph_point(&mut self.points, &mut self.point_count, index)
+    }
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
 #[deku_derive(DekuRead, DekuWrite)]
+#[deku(ctx = "endian: deku::ctx::Endian", endian = "endian")]
 pub struct CAkEvent {
     #[serde(skip)]
     #[deku(update = "self.actions.len()")]
     action_count: u8,
-    #[deku(count = "action_count")]
+    #[deku(count = "checked_count(deku::rest, *action_count)?")]
     pub actions: Vec<u32>,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Clone, Debug, Serialize, Deserialize)]
 #[deku_derive(DekuRead, DekuWrite)]
+#[deku(ctx = "endian: deku::ctx::Endian", endian = "endian")]
 pub struct CAkSound {
     pub bank_source_data: AkBankSourceData,
     pub node_base_params: NodeBaseParams,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Clone, Debug, Serialize, Deserialize)]
 #[deku_derive(DekuRead, DekuWrite)]
+#[deku(ctx = "endian: deku::ctx::Endian", endian = "endian")]
 pub struct AkBankSourceData {
     pub plugin: PluginId,
     pub source_type: SourceType,
     pub media_information: AkMediaInformation,
     #[serde(skip)]
-    #[deku(update = "self.params.len()", skip, cond = "plugin.has_params()?")]
+    #[deku(update = "self.params.len()", skip, cond = "!plugin.has_params()?")]
     params_size: u32,
-    #[deku(count = "params_size")]
+    #[deku(count = "checked_count(deku::rest, *params_size)?")]
     pub params: Vec<u8>,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+impl AkBankSourceData {
+    /// External sources have their audio supplied by the game at runtime. The
+    /// media information's source ID is the cookie the game uses to look it up
+    /// and the bank contains no media for it.
+    pub fn is_external_source(&self) -> bool {
+        matches!(self.plugin, PluginId::EXTERNALSOURCE)
+    }
+
+    /// Whether the source plays a WEM, as opposed to generating its audio
+    /// through a source plugin or having it supplied externally.
+    pub fn has_media(&self) -> bool {
+        !self.is_external_source() && self.plugin.deku_id().is_ok_and(|id| id & 0x0F == 0x1)
+    }
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
 #[deku_derive(DekuRead, DekuWrite)]
-#[deku(type = "u8")]
+#[deku(type = "u8", ctx = "endian: deku::ctx::Endian", endian = "endian")]
 pub enum SourceType {
     #[deku(id = "0x0")]
     Embedded,
@@ -1923,9 +3532,9 @@ pub enum SourceType {
     Streaming,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Clone, Debug, Serialize, Deserialize)]
 #[deku_derive(DekuRead, DekuWrite)]
-#[deku(type = "u32")]
+#[deku(type = "u32", ctx = "endian: deku::ctx::Endian", endian = "endian")]
 pub enum PluginId {
     #[deku(id = "0x00000000")]
     None,
@@ -2128,26 +3737,38 @@ pub enum PluginId {
 }
 
 impl PluginId {
+    /// Only source plugins (type 2) carry a parameter blob. Codecs, including
+    /// external sources, are followed by the media information alone.
     fn has_params(&self) -> Result<bool, DekuError> {
-        Ok(self.deku_id()? & 0x0F != 0x2)
+        Ok(self.deku_id()? & 0x0F == 0x2)
     }
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Clone, Debug, Serialize, Deserialize)]
 #[deku_derive(DekuRead, DekuWrite)]
+#[deku(ctx = "endian: deku::ctx::Endian", endian = "endian")]
 pub struct AkMediaInformation {
     pub source_id: u32,
     pub in_memory_media_size: u32,
     pub source_flags: u8,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Clone, Debug, Serialize, Deserialize)]
 #[deku_derive(DekuRead, DekuWrite)]
+#[deku(ctx = "endian: deku::ctx::Endian", endian = "endian")]
 pub struct NodeBaseParams {
     pub node_initial_fx_parameters: NodeInitialFxParams,
     pub override_attachment_params: u8,
-    pub override_bus_id: u32,
-    pub direct_parent_id: u32,
+    #[deku(
+        reader = "ObjectId::read(deku::rest, endian)",
+        writer = "ObjectId::write(deku::output, &self.override_bus_id, endian)",
+    )]
+    pub override_bus_id: ObjectId,
+    #[deku(
+        reader = "ObjectId::read(deku::rest, endian)",
+        writer = "ObjectId::write(deku::output, &self.direct_parent_id, endian)",
+    )]
+    pub direct_parent_id: ObjectId,
     pub unknown_flags: u8,
     pub node_initial_params: NodeInitialParams,
     pub positioning_params: PositioningParams,
@@ -2157,8 +3778,9 @@ pub struct NodeBaseParams {
     pub initial_rtpc: InitialRTPC,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Clone, Debug, Serialize, Deserialize)]
 #[deku_derive(DekuRead, DekuWrite)]
+#[deku(ctx = "endian: deku::ctx::Endian", endian = "endian")]
 pub struct NodeInitialFxParams {
     pub is_override_parent_fx: u8,
     #[serde(skip)]
@@ -2166,29 +3788,32 @@ pub struct NodeInitialFxParams {
     fx_chunk_count: u8,
     #[deku(skip, cond = "*fx_chunk_count == 0")]
     pub fx_bypass_bits: u8,
-    #[deku(count = "fx_chunk_count")]
+    #[deku(count = "checked_count(deku::rest, *fx_chunk_count)?")]
     pub fx_chunks: Vec<FXChunk>,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Clone, Debug, Serialize, Deserialize)]
 #[deku_derive(DekuRead, DekuWrite)]
+#[deku(ctx = "endian: deku::ctx::Endian", endian = "endian")]
 pub struct NodeInitialParams {
     #[deku(
         reader = "PropBundle::read_list(
             deku::rest,
+            endian,
         )",
         writer = "PropBundle::write_list(
             deku::output,
             &self.prop_initial_values.iter().collect::<Vec<_>>(),
+            endian,
         )"
     )]
     pub prop_initial_values: Vec<PropBundle>,
     pub prop_ranged_modifiers: PropRangedModifiers,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Clone, Debug, Serialize, Deserialize)]
 #[deku_derive(DekuRead, DekuWrite)]
-#[deku(ctx = "prop_id: u8", id = "prop_id")]
+#[deku(ctx = "endian: deku::ctx::Endian, prop_id: u8", id = "prop_id", endian = "endian")]
 pub enum PropBundle {
     #[deku(id = "0x00")]
     Volume(f32),
@@ -2339,35 +3964,42 @@ pub enum PropBundle {
 }
 
 impl PropBundle {
-    fn read_list(rest: &BitSlice<u8, Msb0>) -> Result<(&BitSlice<u8, Msb0>, Vec<Self>), DekuError> {
-        let (mut rest, count) = u8::read(rest, ())?;
+    fn read_list(
+        rest: &BitSlice<u8, Msb0>,
+        endian: deku::ctx::Endian,
+    ) -> Result<(&BitSlice<u8, Msb0>, Vec<Self>), DekuError> {
+        let (mut rest, count) = u8::read(rest, endian)?;
 
         let mut prop_ids = vec![];
         for _ in 0..count {
             let current_type: u8;
-            (rest, current_type) = u8::read(rest, ())?;
+            (rest, current_type) = u8::read(rest, endian)?;
             prop_ids.push(current_type);
         }
 
         let mut results = vec![];
         for prop_id in prop_ids.iter() {
             let current_value: Self;
-            (rest, current_value) = Self::read_by_id(*prop_id, rest)?;
+            (rest, current_value) = Self::read_by_id(*prop_id, rest, endian)?;
             results.push(current_value);
         }
 
         Ok((rest, results))
     }
 
-    fn write_list(output: &mut BitVec<u8, Msb0>, values: &[&Self]) -> Result<(), DekuError> {
-        u8::write(&(values.len() as u8), output, ())?;
+    fn write_list(
+        output: &mut BitVec<u8, Msb0>,
+        values: &[&Self],
+        endian: deku::ctx::Endian,
+    ) -> Result<(), DekuError> {
+        u8::write(&(values.len() as u8), output, endian)?;
 
         for value in values {
-            u8::write(&(value.deku_id()?), output, ())?;
+            u8::write(&(value.deku_id()?), output, endian)?;
         }
 
         for value in values {
-            value.write_internal(output)?;
+            value.write_internal(output, endian)?;
         }
 
         Ok(())
@@ -2376,405 +4008,413 @@ impl PropBundle {
     fn read_by_id(
         prop_id: u8,
         rest: &BitSlice<u8, Msb0>,
+        endian: deku::ctx::Endian,
     ) -> Result<(&BitSlice<u8, Msb0>, Self), DekuError> {
         match prop_id {
             0x00 => {
-                let (r, v) = f32::read(rest, ())?;
+                let (r, v) = f32::read(rest, endian)?;
                 Ok((r, Self::Volume(v)))
             }
             0x01 => {
-                let (r, v) = f32::read(rest, ())?;
+                let (r, v) = f32::read(rest, endian)?;
                 Ok((r, Self::LFE(v)))
             }
             0x02 => {
-                let (r, v) = f32::read(rest, ())?;
+                let (r, v) = f32::read(rest, endian)?;
                 Ok((r, Self::Pitch(v)))
             }
             0x03 => {
-                let (r, v) = f32::read(rest, ())?;
+                let (r, v) = f32::read(rest, endian)?;
                 Ok((r, Self::LPF(v)))
             }
             0x04 => {
-                let (r, v) = f32::read(rest, ())?;
+                let (r, v) = f32::read(rest, endian)?;
                 Ok((r, Self::HPF(v)))
             }
             0x05 => {
-                let (r, v) = f32::read(rest, ())?;
+                let (r, v) = f32::read(rest, endian)?;
                 Ok((r, Self::BusVolume(v)))
             }
             0x06 => {
-                let (r, v) = f32::read(rest, ())?;
+                let (r, v) = f32::read(rest, endian)?;
                 Ok((r, Self::MakeUpGain(v)))
             }
             0x07 => {
-                let (r, v) = f32::read(rest, ())?;
+                let (r, v) = f32::read(rest, endian)?;
                 Ok((r, Self::Priority(v)))
             }
             0x08 => {
-                let (r, v) = f32::read(rest, ())?;
+                let (r, v) = f32::read(rest, endian)?;
                 Ok((r, Self::PriorityDistanceOffset(v)))
             }
             0x09 => {
-                let (r, v) = f32::read(rest, ())?;
+                let (r, v) = f32::read(rest, endian)?;
                 Ok((r, Self::FeedbackVolume(v)))
             }
             0x0A => {
-                let (r, v) = f32::read(rest, ())?;
+                let (r, v) = f32::read(rest, endian)?;
                 Ok((r, Self::FeedbackLPF(v)))
             }
             0x0B => {
-                let (r, v) = f32::read(rest, ())?;
+                let (r, v) = f32::read(rest, endian)?;
                 Ok((r, Self::MuteRatio(v)))
             }
             0x0C => {
-                let (r, v) = f32::read(rest, ())?;
+                let (r, v) = f32::read(rest, endian)?;
                 Ok((r, Self::PanLR(v)))
             }
             0x0D => {
-                let (r, v) = f32::read(rest, ())?;
+                let (r, v) = f32::read(rest, endian)?;
                 Ok((r, Self::PanFR(v)))
             }
             0x0E => {
-                let (r, v) = f32::read(rest, ())?;
+                let (r, v) = f32::read(rest, endian)?;
                 Ok((r, Self::CenterPCT(v)))
             }
             0x0F => {
-                let (r, v) = i32::read(rest, ())?;
+                let (r, v) = i32::read(rest, endian)?;
                 Ok((r, Self::DelayTime(v)))
             }
             0x10 => {
-                let (r, v) = u32::read(rest, ())?;
+                let (r, v) = u32::read(rest, endian)?;
                 Ok((r, Self::TransitionTime(v)))
             }
             0x11 => {
-                let (r, v) = f32::read(rest, ())?;
+                let (r, v) = f32::read(rest, endian)?;
                 Ok((r, Self::Probability(v)))
             }
             0x12 => {
-                let (r, v) = f32::read(rest, ())?;
+                let (r, v) = f32::read(rest, endian)?;
                 Ok((r, Self::DialogueMode(v)))
             }
             0x13 => {
-                let (r, v) = f32::read(rest, ())?;
+                let (r, v) = f32::read(rest, endian)?;
                 Ok((r, Self::UserAuxSendVolume0(v)))
             }
             0x14 => {
-                let (r, v) = f32::read(rest, ())?;
+                let (r, v) = f32::read(rest, endian)?;
                 Ok((r, Self::UserAuxSendVolume1(v)))
             }
             0x15 => {
-                let (r, v) = f32::read(rest, ())?;
+                let (r, v) = f32::read(rest, endian)?;
                 Ok((r, Self::UserAuxSendVolume2(v)))
             }
             0x16 => {
-                let (r, v) = f32::read(rest, ())?;
+                let (r, v) = f32::read(rest, endian)?;
                 Ok((r, Self::UserAuxSendVolume3(v)))
             }
             0x17 => {
-                let (r, v) = f32::read(rest, ())?;
+                let (r, v) = f32::read(rest, endian)?;
                 Ok((r, Self::GameAuxSendVolume(v)))
             }
             0x18 => {
-                let (r, v) = f32::read(rest, ())?;
+                let (r, v) = f32::read(rest, endian)?;
                 Ok((r, Self::OutputBusVolume(v)))
             }
             0x19 => {
-                let (r, v) = f32::read(rest, ())?;
+                let (r, v) = f32::read(rest, endian)?;
                 Ok((r, Self::OutputBusHPF(v)))
             }
             0x1A => {
-                let (r, v) = f32::read(rest, ())?;
+                let (r, v) = f32::read(rest, endian)?;
                 Ok((r, Self::OutputBusLPF(v)))
             }
             0x1B => {
-                let (r, v) = f32::read(rest, ())?;
+                let (r, v) = f32::read(rest, endian)?;
                 Ok((r, Self::HDRBusThreshold(v)))
             }
             0x1C => {
-                let (r, v) = f32::read(rest, ())?;
+                let (r, v) = f32::read(rest, endian)?;
                 Ok((r, Self::HDRBusRatio(v)))
             }
             0x1D => {
-                let (r, v) = f32::read(rest, ())?;
+                let (r, v) = f32::read(rest, endian)?;
                 Ok((r, Self::HDRBusReleaseTime(v)))
             }
             0x1E => {
-                let (r, v) = f32::read(rest, ())?;
+                let (r, v) = f32::read(rest, endian)?;
                 Ok((r, Self::HDRBusGameParam(v)))
             }
             0x1F => {
-                let (r, v) = f32::read(rest, ())?;
+                let (r, v) = f32::read(rest, endian)?;
                 Ok((r, Self::HDRBusGameParamMin(v)))
             }
             0x20 => {
-                let (r, v) = f32::read(rest, ())?;
+                let (r, v) = f32::read(rest, endian)?;
                 Ok((r, Self::HDRBusGameParamMax(v)))
             }
             0x21 => {
-                let (r, v) = f32::read(rest, ())?;
+                let (r, v) = f32::read(rest, endian)?;
                 Ok((r, Self::HDRActiveRange(v)))
             }
             0x22 => {
-                let (r, v) = f32::read(rest, ())?;
+                let (r, v) = f32::read(rest, endian)?;
                 Ok((r, Self::LoopStart(v)))
             }
             0x23 => {
-                let (r, v) = f32::read(rest, ())?;
+                let (r, v) = f32::read(rest, endian)?;
                 Ok((r, Self::LoopEnd(v)))
             }
             0x24 => {
-                let (r, v) = f32::read(rest, ())?;
+                let (r, v) = f32::read(rest, endian)?;
                 Ok((r, Self::TrimInTime(v)))
             }
             0x25 => {
-                let (r, v) = f32::read(rest, ())?;
+                let (r, v) = f32::read(rest, endian)?;
                 Ok((r, Self::TrimOutTime(v)))
             }
             0x26 => {
-                let (r, v) = f32::read(rest, ())?;
+                let (r, v) = f32::read(rest, endian)?;
                 Ok((r, Self::FadeInTime(v)))
             }
             0x27 => {
-                let (r, v) = f32::read(rest, ())?;
+                let (r, v) = f32::read(rest, endian)?;
                 Ok((r, Self::FadeOutTime(v)))
             }
             0x28 => {
-                let (r, v) = f32::read(rest, ())?;
+                let (r, v) = f32::read(rest, endian)?;
                 Ok((r, Self::FadeInCurve(v)))
             }
             0x29 => {
-                let (r, v) = f32::read(rest, ())?;
+                let (r, v) = f32::read(rest, endian)?;
                 Ok((r, Self::FadeOutCurve(v)))
             }
             0x2A => {
-                let (r, v) = f32::read(rest, ())?;
+                let (r, v) = f32::read(rest, endian)?;
                 Ok((r, Self::LoopCrossfadeDuration(v)))
             }
             0x2B => {
-                let (r, v) = f32::read(rest, ())?;
+                let (r, v) = f32::read(rest, endian)?;
                 Ok((r, Self::CrossfadeUpCurve(v)))
             }
             0x2C => {
-                let (r, v) = f32::read(rest, ())?;
+                let (r, v) = f32::read(rest, endian)?;
                 Ok((r, Self::CrossfadeDownCurve(v)))
             }
             0x2D => {
-                let (r, v) = f32::read(rest, ())?;
+                let (r, v) = f32::read(rest, endian)?;
                 Ok((r, Self::MidiTrackingRootNote(v)))
             }
             0x2E => {
-                let (r, v) = f32::read(rest, ())?;
+                let (r, v) = f32::read(rest, endian)?;
                 Ok((r, Self::MidiPlayOnNoteType(v)))
             }
             0x2F => {
-                let (r, v) = f32::read(rest, ())?;
+                let (r, v) = f32::read(rest, endian)?;
                 Ok((r, Self::MidiTransposition(v)))
             }
             0x30 => {
-                let (r, v) = f32::read(rest, ())?;
+                let (r, v) = f32::read(rest, endian)?;
                 Ok((r, Self::MidiVelocityOffset(v)))
             }
             0x31 => {
-                let (r, v) = f32::read(rest, ())?;
+                let (r, v) = f32::read(rest, endian)?;
                 Ok((r, Self::MidiKeyRangeMin(v)))
             }
             0x32 => {
-                let (r, v) = f32::read(rest, ())?;
+                let (r, v) = f32::read(rest, endian)?;
                 Ok((r, Self::MidiKeyRangeMax(v)))
             }
             0x33 => {
-                let (r, v) = f32::read(rest, ())?;
+                let (r, v) = f32::read(rest, endian)?;
                 Ok((r, Self::MidiVelocityRangeMin(v)))
             }
             0x34 => {
-                let (r, v) = f32::read(rest, ())?;
+                let (r, v) = f32::read(rest, endian)?;
                 Ok((r, Self::MidiVelocityRangeMax(v)))
             }
             0x35 => {
-                let (r, v) = f32::read(rest, ())?;
+                let (r, v) = f32::read(rest, endian)?;
                 Ok((r, Self::MidiChannelMask(v)))
             }
             0x36 => {
-                let (r, v) = f32::read(rest, ())?;
+                let (r, v) = f32::read(rest, endian)?;
                 Ok((r, Self::PlaybackSpeed(v)))
             }
             0x37 => {
-                let (r, v) = f32::read(rest, ())?;
+                let (r, v) = f32::read(rest, endian)?;
                 Ok((r, Self::MidiTempoSource(v)))
             }
             0x38 => {
-                let (r, v) = f32::read(rest, ())?;
+                let (r, v) = f32::read(rest, endian)?;
                 Ok((r, Self::MidiTargetNode(v)))
             }
             0x39 => {
-                let (r, v) = u32::read(rest, ())?;
+                let (r, v) = u32::read(rest, endian)?;
                 Ok((r, Self::AttachedPluginFXID(v)))
             }
             0x3A => {
-                let (r, v) = f32::read(rest, ())?;
+                let (r, v) = f32::read(rest, endian)?;
                 Ok((r, Self::Loop(v)))
             }
             0x3B => {
-                let (r, v) = f32::read(rest, ())?;
+                let (r, v) = f32::read(rest, endian)?;
                 Ok((r, Self::InitialDelay(v)))
             }
             0x3C => {
-                let (r, v) = f32::read(rest, ())?;
+                let (r, v) = f32::read(rest, endian)?;
                 Ok((r, Self::UserAuxSendLPF0(v)))
             }
             0x3D => {
-                let (r, v) = f32::read(rest, ())?;
+                let (r, v) = f32::read(rest, endian)?;
                 Ok((r, Self::UserAuxSendLPF1(v)))
             }
             0x3E => {
-                let (r, v) = f32::read(rest, ())?;
+                let (r, v) = f32::read(rest, endian)?;
                 Ok((r, Self::UserAuxSendLPF2(v)))
             }
             0x3F => {
-                let (r, v) = f32::read(rest, ())?;
+                let (r, v) = f32::read(rest, endian)?;
                 Ok((r, Self::UserAuxSendLPF3(v)))
             }
             0x40 => {
-                let (r, v) = f32::read(rest, ())?;
+                let (r, v) = f32::read(rest, endian)?;
                 Ok((r, Self::UserAuxSendHPF0(v)))
             }
             0x41 => {
-                let (r, v) = f32::read(rest, ())?;
+                let (r, v) = f32::read(rest, endian)?;
                 Ok((r, Self::UserAuxSendHPF1(v)))
             }
             0x42 => {
-                let (r, v) = f32::read(rest, ())?;
+                let (r, v) = f32::read(rest, endian)?;
                 Ok((r, Self::UserAuxSendHPF2(v)))
             }
             0x43 => {
-                let (r, v) = f32::read(rest, ())?;
+                let (r, v) = f32::read(rest, endian)?;
                 Ok((r, Self::UserAuxSendHPF3(v)))
             }
             0x44 => {
-                let (r, v) = f32::read(rest, ())?;
+                let (r, v) = f32::read(rest, endian)?;
                 Ok((r, Self::GameAuxSendLPF(v)))
             }
             0x45 => {
-                let (r, v) = f32::read(rest, ())?;
+                let (r, v) = f32::read(rest, endian)?;
                 Ok((r, Self::GameAuxSendHPF(v)))
             }
             0x46 => {
-                let (r, v) = u32::read(rest, ())?;
+                let (r, v) = u32::read(rest, endian)?;
                 Ok((r, Self::AttenuationID(v)))
             }
             0x47 => {
-                let (r, v) = f32::read(rest, ())?;
+                let (r, v) = f32::read(rest, endian)?;
                 Ok((r, Self::PositioningTypeBlend(v)))
             }
             0x48 => {
-                let (r, v) = f32::read(rest, ())?;
+                let (r, v) = f32::read(rest, endian)?;
                 Ok((r, Self::ReflectionBusVolume(v)))
             }
-            _ => panic!("Unknown prop ID: {}", prop_id),
+            _ => Err(DekuError::Parse(format!("Unknown prop ID: {:#x}", prop_id))),
         }
     }
 
-    fn write_internal(&self, output: &mut BitVec<u8, Msb0>) -> Result<(), DekuError> {
+    fn write_internal(
+        &self,
+        output: &mut BitVec<u8, Msb0>,
+        endian: deku::ctx::Endian,
+    ) -> Result<(), DekuError> {
         match self {
-            Self::Volume(v) => v.write(output, ())?,
-            Self::LFE(v) => v.write(output, ())?,
-            Self::Pitch(v) => v.write(output, ())?,
-            Self::LPF(v) => v.write(output, ())?,
-            Self::HPF(v) => v.write(output, ())?,
-            Self::BusVolume(v) => v.write(output, ())?,
-            Self::MakeUpGain(v) => v.write(output, ())?,
-            Self::Priority(v) => v.write(output, ())?,
-            Self::PriorityDistanceOffset(v) => v.write(output, ())?,
-            Self::FeedbackVolume(v) => v.write(output, ())?,
-            Self::FeedbackLPF(v) => v.write(output, ())?,
-            Self::MuteRatio(v) => v.write(output, ())?,
-            Self::PanLR(v) => v.write(output, ())?,
-            Self::PanFR(v) => v.write(output, ())?,
-            Self::CenterPCT(v) => v.write(output, ())?,
-            Self::DelayTime(v) => v.write(output, ())?,
-            Self::TransitionTime(v) => v.write(output, ())?,
-            Self::Probability(v) => v.write(output, ())?,
-            Self::DialogueMode(v) => v.write(output, ())?,
-            Self::UserAuxSendVolume0(v) => v.write(output, ())?,
-            Self::UserAuxSendVolume1(v) => v.write(output, ())?,
-            Self::UserAuxSendVolume2(v) => v.write(output, ())?,
-            Self::UserAuxSendVolume3(v) => v.write(output, ())?,
-            Self::GameAuxSendVolume(v) => v.write(output, ())?,
-            Self::OutputBusVolume(v) => v.write(output, ())?,
-            Self::OutputBusHPF(v) => v.write(output, ())?,
-            Self::OutputBusLPF(v) => v.write(output, ())?,
-            Self::HDRBusThreshold(v) => v.write(output, ())?,
-            Self::HDRBusRatio(v) => v.write(output, ())?,
-            Self::HDRBusReleaseTime(v) => v.write(output, ())?,
-            Self::HDRBusGameParam(v) => v.write(output, ())?,
-            Self::HDRBusGameParamMin(v) => v.write(output, ())?,
-            Self::HDRBusGameParamMax(v) => v.write(output, ())?,
-            Self::HDRActiveRange(v) => v.write(output, ())?,
-            Self::LoopStart(v) => v.write(output, ())?,
-            Self::LoopEnd(v) => v.write(output, ())?,
-            Self::TrimInTime(v) => v.write(output, ())?,
-            Self::TrimOutTime(v) => v.write(output, ())?,
-            Self::FadeInTime(v) => v.write(output, ())?,
-            Self::FadeOutTime(v) => v.write(output, ())?,
-            Self::FadeInCurve(v) => v.write(output, ())?,
-            Self::FadeOutCurve(v) => v.write(output, ())?,
-            Self::LoopCrossfadeDuration(v) => v.write(output, ())?,
-            Self::CrossfadeUpCurve(v) => v.write(output, ())?,
-            Self::CrossfadeDownCurve(v) => v.write(output, ())?,
-            Self::MidiTrackingRootNote(v) => v.write(output, ())?,
-            Self::MidiPlayOnNoteType(v) => v.write(output, ())?,
-            Self::MidiTransposition(v) => v.write(output, ())?,
-            Self::MidiVelocityOffset(v) => v.write(output, ())?,
-            Self::MidiKeyRangeMin(v) => v.write(output, ())?,
-            Self::MidiKeyRangeMax(v) => v.write(output, ())?,
-            Self::MidiVelocityRangeMin(v) => v.write(output, ())?,
-            Self::MidiVelocityRangeMax(v) => v.write(output, ())?,
-            Self::MidiChannelMask(v) => v.write(output, ())?,
-            Self::PlaybackSpeed(v) => v.write(output, ())?,
-            Self::MidiTempoSource(v) => v.write(output, ())?,
-            Self::MidiTargetNode(v) => v.write(output, ())?,
-            Self::AttachedPluginFXID(v) => v.write(output, ())?,
-            Self::Loop(v) => v.write(output, ())?,
-            Self::InitialDelay(v) => v.write(output, ())?,
-            Self::UserAuxSendLPF0(v) => v.write(output, ())?,
-            Self::UserAuxSendLPF1(v) => v.write(output, ())?,
-            Self::UserAuxSendLPF2(v) => v.write(output, ())?,
-            Self::UserAuxSendLPF3(v) => v.write(output, ())?,
-            Self::UserAuxSendHPF0(v) => v.write(output, ())?,
-            Self::UserAuxSendHPF1(v) => v.write(output, ())?,
-            Self::UserAuxSendHPF2(v) => v.write(output, ())?,
-            Self::UserAuxSendHPF3(v) => v.write(output, ())?,
-            Self::GameAuxSendLPF(v) => v.write(output, ())?,
-            Self::GameAuxSendHPF(v) => v.write(output, ())?,
-            Self::AttenuationID(v) => v.write(output, ())?,
-            Self::PositioningTypeBlend(v) => v.write(output, ())?,
-            Self::ReflectionBusVolume(v) => v.write(output, ())?,
+            Self::Volume(v) => v.write(output, endian)?,
+            Self::LFE(v) => v.write(output, endian)?,
+            Self::Pitch(v) => v.write(output, endian)?,
+            Self::LPF(v) => v.write(output, endian)?,
+            Self::HPF(v) => v.write(output, endian)?,
+            Self::BusVolume(v) => v.write(output, endian)?,
+            Self::MakeUpGain(v) => v.write(output, endian)?,
+            Self::Priority(v) => v.write(output, endian)?,
+            Self::PriorityDistanceOffset(v) => v.write(output, endian)?,
+            Self::FeedbackVolume(v) => v.write(output, endian)?,
+            Self::FeedbackLPF(v) => v.write(output, endian)?,
+            Self::MuteRatio(v) => v.write(output, endian)?,
+            Self::PanLR(v) => v.write(output, endian)?,
+            Self::PanFR(v) => v.write(output, endian)?,
+            Self::CenterPCT(v) => v.write(output, endian)?,
+            Self::DelayTime(v) => v.write(output, endian)?,
+            Self::TransitionTime(v) => v.write(output, endian)?,
+            Self::Probability(v) => v.write(output, endian)?,
+            Self::DialogueMode(v) => v.write(output, endian)?,
+            Self::UserAuxSendVolume0(v) => v.write(output, endian)?,
+            Self::UserAuxSendVolume1(v) => v.write(output, endian)?,
+            Self::UserAuxSendVolume2(v) => v.write(output, endian)?,
+            Self::UserAuxSendVolume3(v) => v.write(output, endian)?,
+            Self::GameAuxSendVolume(v) => v.write(output, endian)?,
+            Self::OutputBusVolume(v) => v.write(output, endian)?,
+            Self::OutputBusHPF(v) => v.write(output, endian)?,
+            Self::OutputBusLPF(v) => v.write(output, endian)?,
+            Self::HDRBusThreshold(v) => v.write(output, endian)?,
+            Self::HDRBusRatio(v) => v.write(output, endian)?,
+            Self::HDRBusReleaseTime(v) => v.write(output, endian)?,
+            Self::HDRBusGameParam(v) => v.write(output, endian)?,
+            Self::HDRBusGameParamMin(v) => v.write(output, endian)?,
+            Self::HDRBusGameParamMax(v) => v.write(output, endian)?,
+            Self::HDRActiveRange(v) => v.write(output, endian)?,
+            Self::LoopStart(v) => v.write(output, endian)?,
+            Self::LoopEnd(v) => v.write(output, endian)?,
+            Self::TrimInTime(v) => v.write(output, endian)?,
+            Self::TrimOutTime(v) => v.write(output, endian)?,
+            Self::FadeInTime(v) => v.write(output, endian)?,
+            Self::FadeOutTime(v) => v.write(output, endian)?,
+            Self::FadeInCurve(v) => v.write(output, endian)?,
+            Self::FadeOutCurve(v) => v.write(output, endian)?,
+            Self::LoopCrossfadeDuration(v) => v.write(output, endian)?,
+            Self::CrossfadeUpCurve(v) => v.write(output, endian)?,
+            Self::CrossfadeDownCurve(v) => v.write(output, endian)?,
+            Self::MidiTrackingRootNote(v) => v.write(output, endian)?,
+            Self::MidiPlayOnNoteType(v) => v.write(output, endian)?,
+            Self::MidiTransposition(v) => v.write(output, endian)?,
+            Self::MidiVelocityOffset(v) => v.write(output, endian)?,
+            Self::MidiKeyRangeMin(v) => v.write(output, endian)?,
+            Self::MidiKeyRangeMax(v) => v.write(output, endian)?,
+            Self::MidiVelocityRangeMin(v) => v.write(output, endian)?,
+            Self::MidiVelocityRangeMax(v) => v.write(output, endian)?,
+            Self::MidiChannelMask(v) => v.write(output, endian)?,
+            Self::PlaybackSpeed(v) => v.write(output, endian)?,
+            Self::MidiTempoSource(v) => v.write(output, endian)?,
+            Self::MidiTargetNode(v) => v.write(output, endian)?,
+            Self::AttachedPluginFXID(v) => v.write(output, endian)?,
+            Self::Loop(v) => v.write(output, endian)?,
+            Self::InitialDelay(v) => v.write(output, endian)?,
+            Self::UserAuxSendLPF0(v) => v.write(output, endian)?,
+            Self::UserAuxSendLPF1(v) => v.write(output, endian)?,
+            Self::UserAuxSendLPF2(v) => v.write(output, endian)?,
+            Self::UserAuxSendLPF3(v) => v.write(output, endian)?,
+            Self::UserAuxSendHPF0(v) => v.write(output, endian)?,
+            Self::UserAuxSendHPF1(v) => v.write(output, endian)?,
+            Self::UserAuxSendHPF2(v) => v.write(output, endian)?,
+            Self::UserAuxSendHPF3(v) => v.write(output, endian)?,
+            Self::GameAuxSendLPF(v) => v.write(output, endian)?,
+            Self::GameAuxSendHPF(v) => v.write(output, endian)?,
+            Self::AttenuationID(v) => v.write(output, endian)?,
+            Self::PositioningTypeBlend(v) => v.write(output, endian)?,
+            Self::ReflectionBusVolume(v) => v.write(output, endian)?,
         }
 
         Ok(())
     }
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Clone, Debug, Serialize, Deserialize)]
 #[deku_derive(DekuRead, DekuWrite)]
+#[deku(ctx = "endian: deku::ctx::Endian", endian = "endian")]
 pub struct PropRangedModifiers {
     #[serde(skip)]
     #[deku(update = "self.entries.len()")]
     count: u8,
-    #[deku(count = "count")]
+    #[deku(count = "checked_count(deku::rest, *count)?")]
     pub entries: Vec<PropRangedModifier>,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Clone, Debug, Serialize, Deserialize)]
 #[deku_derive(DekuRead, DekuWrite)]
+#[deku(ctx = "endian: deku::ctx::Endian", endian = "endian")]
 pub struct PropRangedModifier {
-    pub prop_type: u8,
+    pub prop_type: AkPropID,
     pub min: f32,
     pub max: f32,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Clone, Debug, Serialize, Deserialize)]
 #[deku_derive(DekuRead, DekuWrite)]
+#[deku(ctx = "endian: deku::ctx::Endian", endian = "endian")]
 pub struct PositioningParams {
     #[deku(bits = "1")]
     unk1: bool,
@@ -2814,7 +4454,7 @@ pub struct PositioningParams {
     )]
     vertex_count: u32,
     #[deku(
-        count = "vertex_count",
+        count = "checked_count(deku::rest, *vertex_count)?",
         skip,
         cond = "*three_dimensional_position_type == Ak3DPositionType::Emitter"
     )]
@@ -2827,21 +4467,37 @@ pub struct PositioningParams {
     )]
     path_list_item_count: u32,
     #[deku(
-        count = "path_list_item_count",
+        count = "checked_count(deku::rest, *path_list_item_count)?",
         skip,
         cond = "*three_dimensional_position_type == Ak3DPositionType::Emitter"
     )]
     pub path_list_item_offsets: Vec<AkPathListItemOffset>,
     #[deku(
-        count = "path_list_item_count",
+        count = "checked_count(deku::rest, *path_list_item_count)?",
         skip,
         cond = "*three_dimensional_position_type == Ak3DPositionType::Emitter"
     )]
     pub three_dimensional_automation_params: Vec<Ak3DAutomationParams>,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+impl PositioningParams {
+    /// Splits the vertices into the automation paths they make up, one for
+    /// every path list item. Paths may share vertices. A path whose offsets
+    /// run past the vertices comes back empty.
+    pub fn paths(&self) -> Vec<Vec<AkPathVertex>> {
+        self.path_list_item_offsets.iter()
+            .map(|p| {
+                let start = p.vertices_offset as usize;
+                let end = start.saturating_add(p.vertices_count as usize);
+                self.vertices.get(start..end).map(|v| v.to_vec()).unwrap_or_default()
+            })
+            .collect()
+    }
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
 #[deku_derive(DekuRead, DekuWrite)]
+#[deku(ctx = "endian: deku::ctx::Endian", endian = "endian")]
 pub struct AkPathVertex {
     pub x: f32,
     pub y: f32,
@@ -2849,23 +4505,26 @@ pub struct AkPathVertex {
     pub duration: i32,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Clone, Debug, Serialize, Deserialize)]
 #[deku_derive(DekuRead, DekuWrite)]
+#[deku(ctx = "endian: deku::ctx::Endian", endian = "endian")]
 pub struct AkPathListItemOffset {
     pub vertices_offset: u32,
     pub vertices_count: u32,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Clone, Debug, Serialize, Deserialize)]
 #[deku_derive(DekuRead, DekuWrite)]
+#[deku(ctx = "endian: deku::ctx::Endian", endian = "endian")]
 pub struct Ak3DAutomationParams {
     pub range_x: f32,
     pub range_y: f32,
     pub range_z: f32,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Clone, Debug, Serialize, Deserialize)]
 #[deku_derive(DekuRead, DekuWrite)]
+#[deku(ctx = "endian: deku::ctx::Endian", endian = "endian")]
 pub struct AuxParams {
     #[deku(bits = 1)]
     unk1: bool,
@@ -2892,8 +4551,9 @@ pub struct AuxParams {
     pub reflections_aux_bus: u32,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Clone, Debug, Serialize, Deserialize)]
 #[deku_derive(DekuRead, DekuWrite)]
+#[deku(ctx = "endian: deku::ctx::Endian", endian = "endian")]
 pub struct AdvSettingsParams {
     #[deku(bits = "1")]
     pub unk1: bool,
@@ -2932,60 +4592,68 @@ pub struct AdvSettingsParams {
     pub override_hdr_envelope: bool,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Clone, Debug, Serialize, Deserialize)]
 #[deku_derive(DekuRead, DekuWrite)]
+#[deku(ctx = "endian: deku::ctx::Endian", endian = "endian")]
 pub struct StateChunk {
     #[serde(skip)]
     #[deku(update = "self.state_property_info.len()")]
     state_property_count: u8,
-    #[deku(count = "state_property_count")]
+    #[deku(count = "checked_count(deku::rest, *state_property_count)?")]
     pub state_property_info: Vec<AkStatePropertyInfo>,
     #[serde(skip)]
     #[deku(update = "self.state_group_chunks.len()")]
     state_group_count: u8,
-    #[deku(count = "state_group_count")]
+    #[deku(count = "checked_count(deku::rest, *state_group_count)?")]
     pub state_group_chunks: Vec<AkStateGroupChunk>,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Clone, Debug, Serialize, Deserialize)]
 #[deku_derive(DekuRead, DekuWrite)]
+#[deku(ctx = "endian: deku::ctx::Endian", endian = "endian")]
 pub struct AkStatePropertyInfo {
     pub property: AkPropID,
     pub accum_type: AkRtpcAccum,
     pub in_db: u8,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Clone, Debug, Serialize, Deserialize)]
 #[deku_derive(DekuRead, DekuWrite)]
+#[deku(ctx = "endian: deku::ctx::Endian", endian = "endian")]
 pub struct AkStateGroupChunk {
     pub state_group_id: u32,
     pub sync_type: AkSyncTypeU8,
+    // Wwise caps state groups at 255 states, encoding more errors out in the
+    // update instead of truncating.
     #[serde(skip)]
     #[deku(update = "self.states.len()")]
     state_count: u8,
-    #[deku(count = "state_count")]
+    #[deku(count = "checked_count(deku::rest, *state_count)?")]
     pub states: Vec<AkState>,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Clone, Debug, Serialize, Deserialize)]
 #[deku_derive(DekuRead, DekuWrite)]
+#[deku(ctx = "endian: deku::ctx::Endian", endian = "endian")]
 pub struct AkState {
     pub state_id: u32,
     pub state_instance_id: u32,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Clone, Debug, Serialize, Deserialize)]
 #[deku_derive(DekuRead, DekuWrite)]
+#[deku(ctx = "endian: deku::ctx::Endian", endian = "endian")]
 pub struct InitialRTPC {
     #[serde(skip)]
     #[deku(update = "self.rtpcs.len()")]
     count: u16,
-    #[deku(count = "count")]
+    #[deku(count = "checked_count(deku::rest, *count)?")]
     pub rtpcs: Vec<RTPC>,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Clone, Debug, Serialize, Deserialize)]
 #[deku_derive(DekuRead, DekuWrite)]
+#[deku(ctx = "endian: deku::ctx::Endian", endian = "endian")]
 pub struct RTPC {
     pub id: u32,
     pub rtpc_type: AkRtpcType,
@@ -2996,6 +4664,19 @@ pub struct RTPC {
     #[serde(skip)]
     #[deku(update = "self.graph_points.len()")]
     graph_point_count: u16,
-    #[deku(count = "graph_point_count")]
+    #[deku(count = "checked_count(deku::rest, *graph_point_count)?")]
     pub graph_points: Vec<AkRTPCGraphPoint>,
 }
+
+impl RTPC {
+    /// Adds a point to the RTPC curve at the position its `from` dictates and
+    /// returns its index. The point count is kept in sync so the RTPC doesn't
+    /// need a `prepare_export` to encode correctly.
+    pub fn add_graph_point(&mut self, point: AkRTPCGraphPoint) -> Result<usize, DekuError> {
+        add_graph_point(&mut self.graph_points, &mut self.graph_point_count, point)
+    }
+
+    pub fn remove_graph_point(&mut self, index: usize) -> Option<AkRTPCGraphPoint> {
+        remove_graph_point(&mut self.graph_points, &mut self.graph_point_count, index)
+    }
+}
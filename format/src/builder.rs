@@ -0,0 +1,122 @@
+use crate::*;
+
+/// The language of banks that don't hold voice lines, the hash of "SFX".
+pub const SFX_LANGUAGE_HASH: u32 = 0x17705D3E;
+
+/// Puts together a soundbank from scratch. Only the BKHD fields have to be
+/// decided up front, which default to an Elden Ring SFX bank. Sizes, counts,
+/// object types and the BKHD padding are left for `prepare_export` to settle.
+#[derive(Clone, Debug)]
+pub struct SoundbankBuilder {
+    endian: Endian,
+    version: u32,
+    bank_id: u32,
+    language_fnv_hash: u32,
+    wem_alignment: u32,
+    project_id: u32,
+    objects: Vec<HIRCObject>,
+    media: Vec<(u32, Vec<u8>)>,
+}
+
+impl SoundbankBuilder {
+    pub fn new(bank_id: u32) -> Self {
+        Self {
+            endian: Endian::Little,
+            version: 0x8C,
+            bank_id,
+            language_fnv_hash: SFX_LANGUAGE_HASH,
+            wem_alignment: 0x10,
+            project_id: 0,
+            objects: vec![],
+            media: vec![],
+        }
+    }
+
+    pub fn endian(mut self, endian: Endian) -> Self {
+        self.endian = endian;
+        self
+    }
+
+    pub fn version(mut self, version: u32) -> Self {
+        self.version = version;
+        self
+    }
+
+    pub fn language(mut self, language_fnv_hash: u32) -> Self {
+        self.language_fnv_hash = language_fnv_hash;
+        self
+    }
+
+    pub fn wem_alignment(mut self, wem_alignment: u32) -> Self {
+        self.wem_alignment = wem_alignment;
+        self
+    }
+
+    pub fn project_id(mut self, project_id: u32) -> Self {
+        self.project_id = project_id;
+        self
+    }
+
+    /// Adds a HIRC object. Objects are written in the order they're added, so
+    /// they should come after anything they reference.
+    pub fn object(mut self, id: ObjectId, body: HIRCObjectBody) -> Self {
+        self.objects.push(HIRCObject { body_type: 0, size: 0, id, body });
+        self
+    }
+
+    /// Embeds a WEM, laid out in the order they're added.
+    pub fn media(mut self, id: u32, wem: Vec<u8>) -> Self {
+        self.media.push((id, wem));
+        self
+    }
+
+    pub fn build(self) -> Soundbank {
+        let bkhd = BKHDSection {
+            version: self.version,
+            bank_id: self.bank_id,
+            language_fnv_hash: self.language_fnv_hash,
+            wem_alignment: self.wem_alignment,
+            project_id: self.project_id,
+            padding: vec![],
+        };
+
+        let mut soundbank = Soundbank {
+            endian: self.endian,
+            sections: vec![
+                Section { magic: *b"BKHD", size: 0, body: SectionBody::BKHD(bkhd) },
+                Section { magic: *b"HIRC", size: 0, body: SectionBody::HIRC(HIRCSection::new(self.objects)) },
+            ],
+        };
+
+        soundbank.replace_media(&self.media, self.wem_alignment);
+        soundbank
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::*;
+
+    #[test]
+    fn built_soundbank_encodes_and_parses() {
+        assert_eq!(create_hash("SFX"), SFX_LANGUAGE_HASH);
+
+        let sound = parse_soundbank(&wwise_fixtures::soundbank(0x10)).unwrap()
+            .hirc().unwrap().objects[1].body.clone();
+
+        let soundbank = SoundbankBuilder::new(0xB4D)
+            .version(0x91)
+            .object(ObjectId::Hash(2), sound)
+            .media(100, vec![0xAA; 0x15])
+            .build();
+        let reparsed = parse_soundbank(&soundbank.to_prepared_bytes().unwrap()).unwrap();
+
+        let bkhd = reparsed.bkhd().unwrap();
+        assert_eq!((bkhd.version, bkhd.bank_id, bkhd.wem_alignment), (0x91, 0xB4D, 0x10));
+
+        let sound = reparsed.hirc().unwrap().find_object(&ObjectId::Hash(2)).unwrap();
+        assert!(matches!(sound.body, HIRCObjectBody::Sound(_)));
+        assert_eq!(reparsed.extract_media(), [(100, vec![0xAA; 0x15])]);
+        assert!(reparsed.structurally_eq(&soundbank));
+    }
+}
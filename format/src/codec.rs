@@ -0,0 +1,130 @@
+use crate::*;
+
+/// The codec header of a Vorbis WEM. Codec sources don't carry a params blob in
+/// the bank, Wwise keeps this in the RIFF header of the media instead.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct VorbisSourceParams {
+    pub channels: u16,
+    pub sample_rate: u32,
+    pub average_bytes_per_second: u32,
+    pub sample_count: u32,
+    /// The loop start and end in samples if the WEM loops.
+    pub loop_points: Option<(u32, u32)>,
+}
+
+/// Vorbis WEMs use the extensible format tag in their fmt chunk.
+const VORBIS_FORMAT_TAG: u16 = 0xFFFF;
+
+impl AkBankSourceData {
+    /// Reads the codec header from `media`, the WEM this source plays. Returns
+    /// `None` for anything that isn't a Vorbis source or if the header can't be
+    /// made sense of.
+    pub fn vorbis_params(&self, media: &[u8]) -> Option<VorbisSourceParams> {
+        if !matches!(self.plugin, PluginId::VORBIS) {
+            return None;
+        }
+
+        let endian = match media.get(0..4)? {
+            b"RIFF" => Endian::Little,
+            b"RIFX" => Endian::Big,
+            _ => return None,
+        };
+
+        let chunks = riff_chunks(media, endian);
+        let chunk = |magic: &[u8; 4]| chunks.iter()
+            .find(|(m, _)| m == magic)
+            .map(|(_, c)| *c);
+
+        let fmt = chunk(b"fmt ")?;
+        let format_tag = read_u16_at(fmt, 0x00, endian)?;
+        if format_tag != VORBIS_FORMAT_TAG {
+            return None;
+        }
+
+        // Older WEMs have a separate vorb chunk, newer ones append it to fmt
+        let sample_count = match chunk(b"vorb") {
+            Some(vorb) => read_u32_at(vorb, 0x00, endian)?,
+            None => read_u32_at(fmt, 0x18, endian)?,
+        };
+
+        let loop_points = chunk(b"smpl").and_then(|smpl| {
+            match read_u32_at(smpl, 0x1C, endian)? {
+                0 => None,
+                _ => Some((read_u32_at(smpl, 0x2C, endian)?, read_u32_at(smpl, 0x30, endian)?)),
+            }
+        });
+
+        Some(VorbisSourceParams {
+            channels: read_u16_at(fmt, 0x02, endian)?,
+            sample_rate: read_u32_at(fmt, 0x04, endian)?,
+            average_bytes_per_second: read_u32_at(fmt, 0x08, endian)?,
+            sample_count,
+            loop_points,
+        })
+    }
+}
+
+/// Splits the RIFF body into its chunks, stopping at the first one that runs
+/// past the end of the input.
+fn riff_chunks(media: &[u8], endian: Endian) -> Vec<([u8; 4], &[u8])> {
+    let mut result = vec![];
+    let mut offset = 12;
+
+    while let Some(size) = read_u32_at(media, offset + 4, endian) {
+        let start = offset + 8;
+        let end = match start.checked_add(size as usize) {
+            Some(end) if end <= media.len() => end,
+            _ => break,
+        };
+
+        result.push((media[offset..offset + 4].try_into().unwrap(), &media[start..end]));
+
+        // Chunks are padded to an even size
+        offset = end + (size as usize & 1);
+    }
+
+    result
+}
+
+fn read_u32_at(bytes: &[u8], offset: usize, endian: Endian) -> Option<u32> {
+    Some(read_u32(bytes.get(offset..offset.checked_add(4)?)?.try_into().unwrap(), endian))
+}
+
+fn read_u16_at(bytes: &[u8], offset: usize, endian: Endian) -> Option<u16> {
+    let bytes: [u8; 2] = bytes.get(offset..offset.checked_add(2)?)?.try_into().unwrap();
+    Some(match endian {
+        Endian::Little => u16::from_le_bytes(bytes),
+        Endian::Big => u16::from_be_bytes(bytes),
+    })
+}
+
+#[cfg(test)]
+mod test {
+    use crate::*;
+    use wwise_fixtures as fixtures;
+
+    #[test]
+    fn reads_vorbis_header_from_media() {
+        let wem = fixtures::vorbis_wem(2, 48000, 96000, Some((100, 90000)));
+        let soundbank = parse_soundbank(&fixtures::soundbank(0x10)).unwrap();
+        let source = match &soundbank.hirc().unwrap().objects[1].body {
+            HIRCObjectBody::Sound(s) => &s.bank_source_data,
+            _ => unreachable!(),
+        };
+
+        assert_eq!(source.vorbis_params(&wem), Some(VorbisSourceParams {
+            channels: 2,
+            sample_rate: 48000,
+            average_bytes_per_second: 0x4000,
+            sample_count: 96000,
+            loop_points: Some((100, 90000)),
+        }));
+
+        let params = source.vorbis_params(&fixtures::vorbis_wem(1, 22050, 10, None)).unwrap();
+        assert_eq!(params.loop_points, None);
+
+        // Truncated headers and other media are ignored
+        assert_eq!(source.vorbis_params(&wem[..0x20]), None);
+        assert_eq!(source.vorbis_params(&[0xAA; 0x15]), None);
+    }
+}
@@ -0,0 +1,124 @@
+use std::f32::consts::PI;
+
+use crate::*;
+
+impl AkCurveInterpolation {
+    /// Maps `t`, the position between two graph points from 0 to 1, onto how
+    /// far the value has moved from the first point to the second.
+    pub fn interpolate(&self, t: f32) -> f32 {
+        let t = t.clamp(0.0, 1.0);
+
+        match self {
+            AkCurveInterpolation::Log3 => 1.0 - (1.0 - t).powi(3),
+            AkCurveInterpolation::Sine => (t * PI / 2.0).sin(),
+            AkCurveInterpolation::Log1 => 1.0 - (1.0 - t).powf(1.41),
+            AkCurveInterpolation::InvSCurve => (1.0 - 2.0 * t).acos() / PI,
+            AkCurveInterpolation::Linear => t,
+            AkCurveInterpolation::SCurve => (1.0 - (t * PI).cos()) / 2.0,
+            AkCurveInterpolation::Exp1 => t.powf(1.41),
+            AkCurveInterpolation::SineRecip => 1.0 - (t * PI / 2.0).cos(),
+            AkCurveInterpolation::Exp3 => t.powi(3),
+            AkCurveInterpolation::Constant => 0.0,
+        }
+    }
+}
+
+/// Evaluates an RTPC curve at `x`. Every segment is shaped by the
+/// interpolation of the point it starts at and `x` is clamped to the first and
+/// last point. An empty curve evaluates to 0 and NaN to the first point. If
+/// NaN points leave no segment around `x` the last point is used.
+pub fn evaluate_curve(points: &[AkRTPCGraphPoint], x: f32) -> f32 {
+    let (first, last) = match (points.first(), points.last()) {
+        (Some(first), Some(last)) => (first, last),
+        _ => return 0.0,
+    };
+
+    if x.is_nan() || x <= first.from {
+        return first.to;
+    } else if x >= last.from {
+        return last.to;
+    }
+
+    let end = match points.iter().position(|p| p.from > x) {
+        Some(end) => end,
+        None => return last.to,
+    };
+    let (start, end) = (&points[end - 1], &points[end]);
+
+    let t = (x - start.from) / (end.from - start.from);
+    start.to + (end.to - start.to) * start.interpolation.interpolate(t)
+}
+
+#[cfg(test)]
+mod test {
+    use crate::*;
+
+    fn point(from: f32, to: f32, interpolation: AkCurveInterpolation) -> AkRTPCGraphPoint {
+        AkRTPCGraphPoint { from, to, interpolation }
+    }
+
+    #[test]
+    fn evaluates_known_shapes() {
+        let linear = [
+            point(0.0, -96.0, AkCurveInterpolation::Linear),
+            point(100.0, 0.0, AkCurveInterpolation::Linear),
+        ];
+        assert_eq!(evaluate_curve(&linear, 25.0), -72.0);
+        assert_eq!(evaluate_curve(&linear, -10.0), -96.0);
+        assert_eq!(evaluate_curve(&linear, 150.0), 0.0);
+
+        // Constant holds the value until the next point
+        let constant = [
+            point(0.0, 1.0, AkCurveInterpolation::Constant),
+            point(10.0, 2.0, AkCurveInterpolation::Constant),
+            point(20.0, 3.0, AkCurveInterpolation::Linear),
+        ];
+        assert_eq!(evaluate_curve(&constant, 9.9), 1.0);
+        assert_eq!(evaluate_curve(&constant, 10.0), 2.0);
+        assert_eq!(evaluate_curve(&constant, 15.0), 2.0);
+
+        let exp3 = [
+            point(0.0, 0.0, AkCurveInterpolation::Exp3),
+            point(1.0, 1.0, AkCurveInterpolation::Linear),
+        ];
+        assert_eq!(evaluate_curve(&exp3, 0.5), 0.125);
+        assert_eq!(evaluate_curve(&[], 0.5), 0.0);
+        assert_eq!(evaluate_curve(&exp3, f32::NAN), 0.0);
+    }
+
+    #[test]
+    fn tolerates_nan_points() {
+        let nan = [
+            point(f32::NAN, 1.0, AkCurveInterpolation::Linear),
+            point(f32::NAN, 2.0, AkCurveInterpolation::Linear),
+        ];
+        assert_eq!(evaluate_curve(&nan, 0.5), 2.0);
+
+        let middle = [
+            point(0.0, 0.0, AkCurveInterpolation::Linear),
+            point(f32::NAN, 1.0, AkCurveInterpolation::Linear),
+            point(10.0, 2.0, AkCurveInterpolation::Linear),
+        ];
+        assert!(evaluate_curve(&middle, 5.0).is_nan());
+    }
+
+    #[test]
+    fn interpolations_span_the_segment() {
+        let interpolations = [
+            AkCurveInterpolation::Log3,
+            AkCurveInterpolation::Sine,
+            AkCurveInterpolation::Log1,
+            AkCurveInterpolation::InvSCurve,
+            AkCurveInterpolation::Linear,
+            AkCurveInterpolation::SCurve,
+            AkCurveInterpolation::Exp1,
+            AkCurveInterpolation::SineRecip,
+            AkCurveInterpolation::Exp3,
+        ];
+
+        for interpolation in interpolations {
+            assert!(interpolation.interpolate(0.0).abs() < 1e-6, "{:?}", interpolation);
+            assert!((interpolation.interpolate(1.0) - 1.0).abs() < 1e-6, "{:?}", interpolation);
+        }
+    }
+}
@@ -0,0 +1,83 @@
+use std::collections;
+
+use crate::*;
+
+/// Maps FNV hashes back to the names they were created from.
+pub type FNVDictionary = collections::HashMap<u32, String>;
+
+impl Soundbank {
+    /// Swaps in `ObjectId::String` for the ID of every HIRC object found in
+    /// the dictionary, as well as for the parent, output bus and action target
+    /// IDs referencing other objects. Names that don't hash to the ID they're
+    /// listed under are ignored so the encoded soundbank can't change.
+    pub fn resolve_names(&mut self, dictionary: &FNVDictionary) {
+        let resolve = |id: &mut ObjectId| {
+            if let ObjectId::Hash(hash) = *id {
+                if let Some(name) = dictionary.get(&hash).filter(|n| create_hash(n) == hash) {
+                    *id = ObjectId::String(name.clone());
+                }
+            }
+        };
+
+        self.visit_mut(&mut |object| {
+            resolve(&mut object.id);
+            object_id_references_mut(&mut object.body).into_iter().for_each(resolve);
+        });
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::*;
+    use wwise_fixtures as fixtures;
+
+    #[test]
+    fn resolve_names_keeps_the_encoding() {
+        let input = fixtures::soundbank(0x10);
+        let mut soundbank = parse_soundbank(&input).unwrap();
+
+        let mut dictionary = FNVDictionary::new();
+        dictionary.insert(create_hash("Play_Footstep"), "Play_Footstep".to_string());
+        // Listed under the wrong hash
+        dictionary.insert(2, "Sound_Footstep".to_string());
+
+        soundbank.visit_mut(&mut |o| if o.id == ObjectId::Hash(5) {
+            o.id = ObjectId::Hash(create_hash("Play_Footstep"));
+        });
+        let renamed = soundbank.to_bytes().unwrap();
+        soundbank.resolve_names(&dictionary);
+
+        let hirc = soundbank.hirc().unwrap();
+        assert_eq!(hirc.objects[4].id, ObjectId::String("Play_Footstep".to_string()));
+        assert_eq!(hirc.objects[1].id, ObjectId::Hash(2));
+        assert_eq!(soundbank.to_bytes().unwrap(), renamed);
+    }
+
+    #[test]
+    fn resolve_names_follows_references() {
+        let (bus, sound) = (create_hash("Master"), create_hash("Footstep"));
+        let input = fixtures::minimal_bank(&[
+            fixtures::hirc_object(0x08, bus, &fixtures::bus(0)),
+            fixtures::hirc_object(0x02, sound, &fixtures::sound(100, bus, 0, &[])),
+            fixtures::hirc_object(0x02, 3, &fixtures::sound(200, 0, sound, &[])),
+            fixtures::hirc_object(0x03, 4, &fixtures::action_play(sound, 0xB4D)),
+        ]);
+        let mut soundbank = parse_soundbank(&input).unwrap();
+
+        let dictionary = FNVDictionary::from([
+            (bus, "Master".to_string()),
+            (sound, "Footstep".to_string()),
+        ]);
+        soundbank.resolve_names(&dictionary);
+
+        let name = |n: &str| ObjectId::String(n.to_string());
+        let hirc = soundbank.hirc().unwrap();
+        assert_eq!(hirc.objects[1].body.node_base_params().unwrap().override_bus_id, name("Master"));
+        assert_eq!(hirc.objects[2].body.node_base_params().unwrap().direct_parent_id, name("Footstep"));
+        match &hirc.objects[3].body {
+            HIRCObjectBody::Action(a) => assert_eq!(a.external_id, name("Footstep")),
+            _ => unreachable!(),
+        }
+        assert_eq!(soundbank.to_bytes().unwrap(), input);
+    }
+}
@@ -1,11 +1,38 @@
+use std::io;
+
 use crate::*;
 use deku::bitvec::BitVec;
 
 #[derive(Debug)]
 pub enum PrepareExportError {
-    Deku(deku::DekuError)
+    Deku(deku::DekuError),
+    /// The DIDX doesn't match the DATA section, see `Soundbank::validate_media`.
+    Media(Vec<MediaError>),
+    /// The soundbank has media but no BKHD to align it with.
+    MissingBKHD,
 }
 
+impl std::fmt::Display for PrepareExportError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            PrepareExportError::Deku(e) => write!(f, "Could not prepare soundbank for export: {}", e),
+            PrepareExportError::Media(errors) => {
+                write!(f, "Soundbank media is invalid: ")?;
+                for (i, e) in errors.iter().enumerate() {
+                    if i != 0 {
+                        write!(f, ", ")?;
+                    }
+                    write!(f, "{}", e)?;
+                }
+                Ok(())
+            },
+            PrepareExportError::MissingBKHD => write!(f, "Soundbank has media but no BKHD section"),
+        }
+    }
+}
+
+impl std::error::Error for PrepareExportError {}
+
 /// Trait that applies some additional logic to the soundbank to prepare it for
 /// export/encoding. This includes things like:
 /// - Determining the required BKHD padding
@@ -16,20 +43,24 @@ pub trait PrepareExport {
 
 impl PrepareExport for Soundbank {
     fn prepare_export(&mut self) -> Result<(), PrepareExportError> {
+        // Writing out a broken DIDX only blows up once the game loads the bank
+        self.validate_media().map_err(PrepareExportError::Media)?;
+
+        let version = self.version();
+
         // Prepare BKHD padding if there is a DATA section
-        let descriptor_count_result = self.sections.iter()
-            .find_map(|s| match &s.body {
-                SectionBody::DIDX(d) => Some(d.descriptors.len()),
-                _ => None,
-            });
+        let descriptor_count_result = self.didx()
+            .map(|d| d.descriptors.len());
 
         if let Some(descriptor_count) = descriptor_count_result {
-            let bkhd = self.sections.iter_mut()
-                .find_map(|s| match &mut s.body {
-                    SectionBody::BKHD(b) => Some(b),
-                    _ => None,
-                })
-                .expect("Can not create a soundbank without BKHD section");
+            let bkhd = self.bkhd_mut()
+                .ok_or(PrepareExportError::MissingBKHD)?;
+
+            // Newer versions have fields in front of the padding
+            let fields_size = bkhd.trim_padding();
+            let bkhd_size = 4 * 5 + fields_size;
+            // An alignment of 0 is what an unaligned bank looks like
+            let alignment = bkhd.wem_alignment.max(1);
 
             // Calculate the offset in the file to the first WEM
             // This consists of the BKHD header, the DIDX header, the DATA
@@ -65,44 +96,91 @@ impl PrepareExport for Soundbank {
                 // Account for the three section headers
                 8 * 3 + 
                 // Account for the unpadded BKHD contents
-                0x14 +
+                bkhd_size +
                 // Account for the DIDX WEM descriptors
                 (descriptor_count * 0xC)
             ) as u32;
 
             let padding_size = {
-                if first_wem_offset % bkhd.wem_alignment == 0x0 {
+                if first_wem_offset.is_multiple_of(alignment) {
                     // Do nothing if first WEM already aligns
                     0x0
                 } else {
                     // Calculate remaining bytes
                     (
                         first_wem_offset + (
-                            bkhd.wem_alignment - first_wem_offset % bkhd.wem_alignment
+                            alignment - first_wem_offset % alignment
                         )
                     ) - first_wem_offset
                 }
             };
 
-            bkhd.padding = vec![0u8; padding_size as usize];
+            bkhd.padding.resize(fields_size + padding_size as usize, 0);
         }
 
         for section in self.sections.iter_mut() {
-            section.prepare_export()?;
+            section.prepare_export(version)?;
         }
 
         Ok(())
     }
 }
 
-impl PrepareExport for Section {
-    fn prepare_export(&mut self) -> Result<(), PrepareExportError> {
+impl Soundbank {
+    /// Encodes the soundbank into `w` as-is, without running `prepare_export`
+    /// first.
+    pub fn to_writer<W: io::Write>(&self, w: &mut W) -> Result<(), DekuError> {
+        let mut buffer = BitVec::default();
+        self.write(&mut buffer, ())?;
+
+        w.write_all(buffer.as_raw_slice())
+            .map_err(|e| DekuError::Unexpected(format!("Could not write soundbank: {}", e)))
+    }
+
+    /// Encodes a prepared copy of the soundbank, so sizes, counts and the
+    /// BKHD padding reflect the current contents. `to_bytes` encodes it
+    /// as-is.
+    pub fn to_prepared_bytes(&self) -> Result<Vec<u8>, DekuError> {
+        let mut copy = self.clone();
+        copy.prepare_export().map_err(|e| match e {
+            PrepareExportError::Deku(e) => e,
+            e => DekuError::InvalidParam(e.to_string()),
+        })?;
+
+        let mut result = vec![];
+        copy.to_writer(&mut result)?;
+        Ok(result)
+    }
+
+    /// Compares the content of two soundbanks while ignoring anything
+    /// `prepare_export` regenerates, like the BKHD padding and the size and
+    /// count fields. Soundbanks that fail to encode are never equal.
+    pub fn structurally_eq(&self, other: &Soundbank) -> bool {
+        match (canonical_bytes(self), canonical_bytes(other)) {
+            (Some(a), Some(b)) => a == b,
+            _ => false,
+        }
+    }
+}
+
+fn canonical_bytes(soundbank: &Soundbank) -> Option<Vec<u8>> {
+    let mut copy = soundbank.clone();
+    copy.prepare_export().ok()?;
+    let mut result = vec![];
+    copy.to_writer(&mut result).ok()?;
+    Some(result)
+}
+
+// Sections need the bank version to be encoded so they can't implement
+// PrepareExport themselves.
+impl Section {
+    fn prepare_export(&mut self, version: u32) -> Result<(), PrepareExportError> {
         match &mut self.body {
             SectionBody::BKHD(s) => de(s.update()),
             SectionBody::DIDX(s) => de(s.update()),
             SectionBody::DATA(s) => de(s.update()),
             SectionBody::ENVS(s) => s.prepare_export(),
-            SectionBody::FXPR(s) => de(s.update()),
+            SectionBody::FXPR(s) => s.prepare_export(),
             SectionBody::HIRC(s) => s.prepare_export(),
             SectionBody::STID(s) => s.prepare_export(),
             SectionBody::STMG(s) => s.prepare_export(),
@@ -110,7 +188,7 @@ impl PrepareExport for Section {
             SectionBody::PLAT(s) => de(s.update()),
         }?;
 
-        self.size = sample_section_body_size(self)
+        self.size = self.body.encoded_size(version)
             .map_err(PrepareExportError::Deku)?;
 
         self.update().map_err(PrepareExportError::Deku)?;
@@ -119,6 +197,22 @@ impl PrepareExport for Section {
     }
 }
 
+impl PrepareExport for FXPRSection {
+    fn prepare_export(&mut self) -> Result<(), PrepareExportError> {
+        let effects = match self {
+            FXPRSection::Effects(e) => e,
+            FXPRSection::Raw(_) => return Ok(()),
+        };
+
+        for effect in effects.effects.iter_mut() {
+            effect.update().map_err(PrepareExportError::Deku)?
+        }
+
+        effects.update().map_err(PrepareExportError::Deku)?;
+        Ok(())
+    }
+}
+
 impl PrepareExport for STIDSection {
     fn prepare_export(&mut self) -> Result<(), PrepareExportError> {
         for entry in self.entries.iter_mut() {
@@ -156,13 +250,16 @@ impl PrepareExport for INITSection {
     }
 }
 
-fn sample_section_body_size(s: &Section) -> Result<u32, deku::DekuError> {
-    // Encode the body once
-    let mut buffer = BitVec::default();
-    s.body.write(&mut buffer, (s.magic, 0x100))?;
-
-    // Get the encoded body length and add the header size
-    Ok(buffer.as_raw_slice().len() as u32)
+impl SectionBody {
+    /// The number of bytes the body takes up when encoded as-is, which is
+    /// what the `size` of its section should hold. Sections change layout
+    /// between Wwise versions so this needs the bank's `version`.
+    pub fn encoded_size(&self, version: u32) -> Result<u32, deku::DekuError> {
+        // The byte order doesn't affect the size
+        let mut buffer = BitVec::default();
+        self.write(&mut buffer, (Endian::Little, self.deku_id()?, 0x100, version))?;
+        Ok(buffer.as_raw_slice().len() as u32)
+    }
 }
 
 impl PrepareExport for HIRCSection {
@@ -196,14 +293,14 @@ impl PrepareExport for HIRCObject {
             HIRCObjectBody::EffectShareSet(o) => o.prepare_export(),
             HIRCObjectBody::EffectCustom(o) => o.prepare_export(),
             HIRCObjectBody::AuxiliaryBus(o) => o.prepare_export(),
-            HIRCObjectBody::LFOModulator(o) => de(o.update()),
-            HIRCObjectBody::EnvelopeModulator(o) => de(o.update()),
+            HIRCObjectBody::LFOModulator(o) => o.prepare_export(),
+            HIRCObjectBody::EnvelopeModulator(o) => o.prepare_export(),
             HIRCObjectBody::AudioDevice(o) => o.prepare_export(),
             HIRCObjectBody::TimeModulator(o) => o.prepare_export(),
         }?;
 
-        self.size = sample_hirc_body_size(self)
-            .map_err(PrepareExportError::Deku)?;
+        self.size = self.body.encoded_size()
+            .map_err(PrepareExportError::Deku)? + HIRC_OBJECT_ID_SIZE;
 
         self.update().map_err(PrepareExportError::Deku)?;
 
@@ -211,13 +308,39 @@ impl PrepareExport for HIRCObject {
     }
 }
 
-fn sample_hirc_body_size(s: &mut HIRCObject) -> Result<u32, deku::DekuError> {
-    // Encode the body once
+impl HIRCObjectBody {
+    /// The number of bytes the body takes up when encoded as-is. This leaves
+    /// out the object ID, which the `size` of the object does count, see
+    /// `HIRC_OBJECT_ID_SIZE`.
+    pub fn encoded_size(&self) -> Result<u32, deku::DekuError> {
+        let mut buffer = BitVec::default();
+        self.write(&mut buffer, (Endian::Little, self.deku_id()?, 0x100))?;
+        Ok(buffer.as_raw_slice().len() as u32)
+    }
+}
+
+fn encode_hirc_body(s: &HIRCObject, endian: Endian) -> Result<Vec<u8>, deku::DekuError> {
     let mut buffer = BitVec::default();
-    s.body.write(&mut buffer, (s.body_type, 0x100))?;
+    s.body.write(&mut buffer, (endian, s.body_type, 0x100))?;
+    Ok(buffer.into_vec())
+}
 
-    // Get the encoded body length and add the header size
-    Ok(buffer.as_raw_slice().len() as u32 + 4)
+impl HIRCObject {
+    /// Encodes the object as it would appear in the HIRC, including the type,
+    /// size and ID header. The header is derived from the encoded body rather
+    /// than taken from the possibly stale fields.
+    pub fn raw_bytes(&self, endian: Endian) -> Result<Vec<u8>, deku::DekuError> {
+        let body = encode_hirc_body(self, endian)?;
+
+        let mut header = BitVec::default();
+        self.body.deku_id()?.write(&mut header, endian)?;
+        (body.len() as u32 + HIRC_OBJECT_ID_SIZE).write(&mut header, endian)?;
+        self.id.as_hash().write(&mut header, endian)?;
+
+        let mut result = header.into_vec();
+        result.extend(body);
+        Ok(result)
+    }
 }
 
 impl PrepareExport for CAkSound {
@@ -302,6 +425,14 @@ impl PrepareExport for CAkActionParams {
         match self {
             CAkActionParams::SetState(p) => de(p.update()),
             CAkActionParams::SetSwitch(p) => de(p.update()),
+            CAkActionParams::BypassFXM(p) => p.prepare_export(),
+            CAkActionParams::BypassFXO(p) => p.prepare_export(),
+            CAkActionParams::ResetBypassFXM(p) => p.prepare_export(),
+            CAkActionParams::ResetBypassFXO(p) => p.prepare_export(),
+            CAkActionParams::ResetBypassFXALL(p) => p.prepare_export(),
+            CAkActionParams::ResetBypassFXALLO(p) => p.prepare_export(),
+            CAkActionParams::ResetBypassFXAE(p) => p.prepare_export(),
+            CAkActionParams::ResetBypassFXAEO(p) => p.prepare_export(),
             CAkActionParams::Play(p) => de(p.update()),
             CAkActionParams::PauseE(p) => p.prepare_export(),
             CAkActionParams::StopE(p) => p.prepare_export(),
@@ -322,7 +453,20 @@ impl PrepareExport for CAkActionParams {
             CAkActionParams::ResetLPFM(p) => p.prepare_export(),
             CAkActionParams::SetBusVolumeM(p) => p.prepare_export(),
             CAkActionParams::ResetBusVolumeM(p) => p.prepare_export(),
+            CAkActionParams::SeekE(p) => p.prepare_export(),
+            CAkActionParams::SeekEO(p) => p.prepare_export(),
+            CAkActionParams::SeekALL(p) => p.prepare_export(),
+            CAkActionParams::SeekALLO(p) => p.prepare_export(),
+            CAkActionParams::SeekAE(p) => p.prepare_export(),
+            CAkActionParams::SeekAEO(p) => p.prepare_export(),
+            CAkActionParams::SetGameParameter(p) => p.prepare_export(),
+            CAkActionParams::SetGameParameterO(p) => p.prepare_export(),
+            CAkActionParams::ResetGameParameter(p) => p.prepare_export(),
+            CAkActionParams::ResetGameParameterO(p) => p.prepare_export(),
             CAkActionParams::PlayEvent => { Ok(()) },
+            CAkActionParams::Duck(_) => { Ok(()) },
+            CAkActionParams::Trigger => { Ok(()) },
+            CAkActionParams::TriggerO => { Ok(()) },
         }?;
 
         Ok(())
@@ -355,6 +499,14 @@ impl PrepareExport for CAkActionMute {
     }
 }
 
+impl PrepareExport for CAkActionBypassFX {
+    fn prepare_export(&mut self) -> Result<(), PrepareExportError> {
+        self.except.update().map_err(PrepareExportError::Deku)?;
+        self.update().map_err(PrepareExportError::Deku)?;
+        Ok(())
+    }
+}
+
 impl PrepareExport for CAkActionSetAkProp {
     fn prepare_export(&mut self) -> Result<(), PrepareExportError> {
         self.set_ak_prop.update().map_err(PrepareExportError::Deku)?;
@@ -364,6 +516,24 @@ impl PrepareExport for CAkActionSetAkProp {
     }
 }
 
+impl PrepareExport for CAkActionSeek {
+    fn prepare_export(&mut self) -> Result<(), PrepareExportError> {
+        self.seek_value.update().map_err(PrepareExportError::Deku)?;
+        self.except.update().map_err(PrepareExportError::Deku)?;
+        self.update().map_err(PrepareExportError::Deku)?;
+        Ok(())
+    }
+}
+
+impl PrepareExport for CAkActionSetGameParameter {
+    fn prepare_export(&mut self) -> Result<(), PrepareExportError> {
+        self.set_game_parameter.update().map_err(PrepareExportError::Deku)?;
+        self.except.update().map_err(PrepareExportError::Deku)?;
+        self.update().map_err(PrepareExportError::Deku)?;
+        Ok(())
+    }
+}
+
 impl PrepareExport for CAkRanSeqCntr {
     fn prepare_export(&mut self) -> Result<(), PrepareExportError> {
         self.node_base_params.prepare_export()?;
@@ -484,24 +654,10 @@ impl PrepareExport for CAkMusicTrack {
 impl PrepareExport for CAkMusicSwitchCntr {
     fn prepare_export(&mut self) -> Result<(), PrepareExportError> {
         self.music_trans_node_params.prepare_export()?;
-        // for node in self.tree.iter_mut() {
-        //     node.prepare_export()?;
-        // }
-        // self.tree_size = sample_tree_size(&self.tree)
-        //     .map_err(PrepareExportError::Deku)? as u32;
-
         self.update().map_err(PrepareExportError::Deku)
     }
 }
 
-fn sample_tree_size(s: &[AkDecisionTreeNode]) -> Result<usize, deku::DekuError> {
-    let mut buffer = BitVec::default();
-    AkDecisionTreeNode::write(&mut buffer, &s.iter().collect::<Vec<_>>())?;
-
-    // Get the encoded body length and add the header size
-    Ok(buffer.as_raw_slice().len())
-}
-
 impl PrepareExport for MusicTransNodeParams {
     fn prepare_export(&mut self) -> Result<(), PrepareExportError> {
         self.music_node_params.prepare_export()?;
@@ -582,6 +738,22 @@ impl PrepareExport for CAkAudioDevice {
     }
 }
 
+impl PrepareExport for CAkLFOModulator {
+    fn prepare_export(&mut self) -> Result<(), PrepareExportError> {
+        self.ranged_modifiers.update().map_err(PrepareExportError::Deku)?;
+        self.initial_rtpc.prepare_export()?;
+        Ok(())
+    }
+}
+
+impl PrepareExport for CAkEnvelopeModulator {
+    fn prepare_export(&mut self) -> Result<(), PrepareExportError> {
+        self.ranged_modifiers.update().map_err(PrepareExportError::Deku)?;
+        self.initial_rtpc.prepare_export()?;
+        Ok(())
+    }
+}
+
 impl PrepareExport for CAkTimeModulator {
     fn prepare_export(&mut self) -> Result<(), PrepareExportError> {
         //self.prop_bundle.update().map_err(PrepareExportError::Deku)?;
@@ -612,3 +784,214 @@ impl PrepareExport for ConversionTable {
 fn de(input: Result<(), deku::DekuError>) -> Result<(), PrepareExportError> {
     input.map_err(PrepareExportError::Deku)
 }
+
+#[cfg(test)]
+mod test {
+    use crate::*;
+    use crate::export::PrepareExport;
+    use wwise_fixtures as fixtures;
+
+    #[test]
+    fn to_prepared_bytes_prepares_a_copy() {
+        let input = fixtures::soundbank(0x10);
+        let mut soundbank = parse_soundbank(&input).unwrap();
+        soundbank.visit_mut(&mut |o| o.size = 0);
+
+        let bytes = soundbank.to_prepared_bytes().unwrap();
+        assert_eq!(bytes, input);
+        assert!(parse_soundbank(&bytes).unwrap().structurally_eq(&soundbank));
+
+        // The soundbank itself is left alone
+        soundbank.visit(&mut |o| assert_eq!(o.size, 0));
+    }
+
+    #[test]
+    fn prepare_export_is_idempotent() {
+        let input = fixtures::soundbank(0x10);
+        let mut soundbank = parse_soundbank(&input).unwrap();
+
+        soundbank.prepare_export().unwrap();
+        let first = soundbank.to_bytes().unwrap();
+
+        soundbank.prepare_export().unwrap();
+        let second = soundbank.to_bytes().unwrap();
+
+        assert_eq!(first, input);
+        assert_eq!(first, second);
+    }
+
+    #[test]
+    fn raw_bytes_matches_input() {
+        let sound = fixtures::sound(100, 1, 0, &[(0x00, -3.0)]);
+        let input = fixtures::hirc_object(0x02, 2, &sound);
+
+        let bytes = fixtures::minimal_bank(std::slice::from_ref(&input));
+        let soundbank = parse_soundbank(&bytes).unwrap();
+
+        let mut raw = vec![];
+        soundbank.visit(&mut |o| raw.push(o.raw_bytes(Endian::Little).unwrap()));
+        assert_eq!(raw, vec![input]);
+    }
+
+    #[test]
+    fn hirc_object_size_covers_id_and_body() {
+        let input = fixtures::soundbank(0x10);
+        let mut soundbank = parse_soundbank(&input).unwrap();
+
+        let mut parsed_sizes = vec![];
+        soundbank.visit(&mut |o| parsed_sizes.push(o.size));
+        soundbank.prepare_export().unwrap();
+
+        soundbank.visit(&mut |o| {
+            let raw = o.raw_bytes(Endian::Little).unwrap();
+            let encoded_size = u32::from_le_bytes(raw[1..5].try_into().unwrap());
+
+            assert_eq!(encoded_size, raw.len() as u32 - HIRC_OBJECT_HEADER_SIZE);
+            assert_eq!(o.size, encoded_size);
+            assert_eq!(o.size, parsed_sizes.remove(0));
+        });
+    }
+
+    #[test]
+    fn structurally_eq_ignores_regenerated_fields() {
+        let input = fixtures::soundbank(0x10);
+        let original = parse_soundbank(&input).unwrap();
+
+        let mut padded = parse_soundbank(&input).unwrap();
+        for section in padded.sections.iter_mut() {
+            section.size = 0;
+            if let SectionBody::BKHD(b) = &mut section.body {
+                b.padding = vec![0; 0x40];
+            }
+        }
+        padded.visit_mut(&mut |o| o.size = 0);
+        assert!(original.structurally_eq(&padded));
+
+        let mut edited = parse_soundbank(&input).unwrap();
+        edited.visit_mut(&mut |o| {
+            if let Some(params) = o.body.node_base_params_mut() {
+                params.node_initial_params.prop_initial_values.clear();
+            }
+        });
+        assert!(!original.structurally_eq(&edited));
+    }
+
+    #[test]
+    fn prepare_export_aligns_first_wem() {
+        for alignment in [16, 256, 2048] {
+            // The WEMs have to be laid out for the new alignment as well
+            let mut soundbank = parse_soundbank(&fixtures::soundbank(0x10)).unwrap();
+            soundbank.replace_media(&soundbank.extract_media(), alignment);
+
+            soundbank.prepare_export().unwrap();
+            let bytes = soundbank.to_bytes().unwrap();
+
+            // Walk the section headers to find where the DATA contents start
+            let mut offset = 0;
+            while &bytes[offset..offset + 4] != b"DATA" {
+                let size = u32::from_le_bytes(bytes[offset + 4..offset + 8].try_into().unwrap());
+                offset += 8 + size as usize;
+            }
+            let first_wem = offset + 8;
+
+            assert_eq!(first_wem % alignment as usize, 0, "alignment {}", alignment);
+            assert!(first_wem < 8 * 3 + 0x14 + 2 * 0xC + alignment as usize);
+            assert_eq!(bytes[first_wem], 0xAA);
+        }
+    }
+
+    #[test]
+    fn encoded_size_matches_encode() {
+        let soundbank = parse_soundbank(&fixtures::soundbank(0x10)).unwrap();
+        let version = soundbank.version();
+
+        for section in soundbank.sections.iter() {
+            let mut buffer = deku::bitvec::BitVec::default();
+            section.write(&mut buffer, (Endian::Little, version)).unwrap();
+            let size = section.body.encoded_size(version).unwrap();
+            assert_eq!(size as usize, buffer.as_raw_slice().len() - 8);
+            assert_eq!(size, section.size);
+        }
+
+        for object in soundbank.hirc().unwrap().objects.iter() {
+            let raw = object.raw_bytes(Endian::Little).unwrap();
+            let size = object.body.encoded_size().unwrap();
+            assert_eq!(size + HIRC_OBJECT_ID_SIZE, object.size);
+            assert_eq!((size + HIRC_OBJECT_HEADER_SIZE + HIRC_OBJECT_ID_SIZE) as usize, raw.len());
+        }
+    }
+
+    #[test]
+    fn prepare_export_reports_instead_of_panicking() {
+        let mut soundbank = parse_soundbank(&fixtures::soundbank(0x10)).unwrap();
+        soundbank.bkhd_mut().unwrap().wem_alignment = 0;
+        assert!(soundbank.prepare_export().is_ok());
+
+        soundbank.sections.retain(|s| !matches!(s.body, SectionBody::BKHD(_)));
+        assert!(matches!(soundbank.prepare_export(), Err(PrepareExportError::MissingBKHD)));
+    }
+
+    #[test]
+    fn prepare_export_measures_bkhd_fields() {
+        let wems = vec![(100, vec![0xAA; 0x15]), (200, vec![0xBB; 0x33])];
+
+        // A BKHD with fields following the project ID
+        let fields = [0x5A; 0x14];
+        let bkhd = fixtures::bkhd(0x91, 0xB4D, 0x10, 0);
+        // Newer versions don't need any
+        assert!(parse_soundbank(&bkhd).unwrap().bkhd().unwrap().padding.is_empty());
+
+        let mut input = fixtures::section(b"BKHD", &[&bkhd[8..], &fields].concat());
+        input.extend(fixtures::media(&wems, 0x10));
+        let mut soundbank = parse_soundbank(&input).unwrap();
+        assert_eq!(soundbank.bkhd().unwrap().padding, fields);
+
+        for alignment in [16, 256, 2048] {
+            soundbank.replace_media(&wems, alignment);
+            let bytes = soundbank.to_prepared_bytes().unwrap();
+
+            // The BKHD with its fields and padding, the DIDX and the DATA
+            // header
+            let reparsed = parse_soundbank(&bytes).unwrap();
+            let padding = &reparsed.bkhd().unwrap().padding;
+            let first_wem = 8 + 0x14 + padding.len() + 8 + 2 * 0xC + 8;
+
+            assert_eq!(first_wem % alignment as usize, 0, "alignment {}", alignment);
+            assert_eq!(bytes[first_wem], 0xAA);
+            assert_eq!(padding[..0x14], fields);
+            assert_eq!(reparsed.extract_media(), wems);
+        }
+    }
+
+    #[test]
+    fn prepare_export_restores_every_object_type() {
+        for (body_type, body) in fixtures::every_object_type() {
+            let input = fixtures::minimal_bank(&[fixtures::hirc_object(body_type, 1, &body)]);
+
+            let soundbank = parse_soundbank(&input).unwrap();
+            assert_eq!(soundbank.to_bytes().unwrap(), input, "body type {}", body_type);
+
+            // The JSON leaves out every count and size so all of them have to
+            // be restored by prepare_export.
+            let json = serde_json::to_string(&soundbank).unwrap();
+            let stripped: Soundbank = serde_json::from_str(&json).unwrap();
+
+            let bytes = stripped.to_prepared_bytes().unwrap();
+            assert_eq!(bytes, input, "body type {}", body_type);
+            assert!(parse_soundbank(&bytes).unwrap().structurally_eq(&soundbank));
+        }
+    }
+
+    #[test]
+    fn inline_media_survives_json() {
+        let input = fixtures::soundbank(0x10);
+        let soundbank = parse_soundbank(&input).unwrap();
+
+        // DATA is kept as a single base64 blob, DIDX as plain descriptors
+        let json = serde_json::to_string(&soundbank).unwrap();
+        let inlined: Soundbank = serde_json::from_str(&json).unwrap();
+        assert_eq!(inlined.extract_media(), soundbank.extract_media());
+
+        assert_eq!(inlined.to_prepared_bytes().unwrap(), input);
+    }
+}
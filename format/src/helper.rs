@@ -1,4 +1,23 @@
-use crate::{HIRCObjectBody, ObjectId, Soundbank};
+use deku::bitvec::BitView;
+use deku::prelude::*;
+
+use crate::{
+    AkPropID,
+    BKHDSection,
+    DATASection,
+    DIDXSection,
+    Endian,
+    HIRCObject,
+    HIRCObjectBody,
+    HIRCSection,
+    NodeBaseParams,
+    NodeInitialParams,
+    ObjectId,
+    PropBundle,
+    PropRangedModifier,
+    SectionBody,
+    Soundbank,
+};
 
 pub trait SoundbankHelper {
     fn hirc_object(&self, object: &ObjectId) -> Option<&HIRCObjectBody>;
@@ -6,16 +25,420 @@ pub trait SoundbankHelper {
 
 impl SoundbankHelper for Soundbank {
     fn hirc_object(&self, object: &ObjectId) -> Option<&HIRCObjectBody> {
-        self.sections.iter()
-            .find_map(|s| match &s.body {
-                crate::SectionBody::HIRC(h) => Some(
-                    h.objects.iter()
-                        .find(|o| &o.id == object)
-                        .map(|o| &o.body),
-                ),
+        self.hirc()?.objects.iter()
+            .find(|o| &o.id == object)
+            .map(|o| &o.body)
+    }
+}
+
+impl Soundbank {
+    /// The BKHD section, which every well-formed soundbank starts with.
+    pub fn bkhd(&self) -> Option<&BKHDSection> {
+        self.sections.iter().find_map(|s| match &s.body {
+            SectionBody::BKHD(s) => Some(s),
+            _ => None,
+        })
+    }
 
+    pub fn bkhd_mut(&mut self) -> Option<&mut BKHDSection> {
+        self.sections.iter_mut().find_map(|s| match &mut s.body {
+            SectionBody::BKHD(s) => Some(s),
+            _ => None,
+        })
+    }
+
+    /// The DIDX section, only present when the bank embeds WEMs.
+    pub fn didx(&self) -> Option<&DIDXSection> {
+        self.sections.iter().find_map(|s| match &s.body {
+            SectionBody::DIDX(s) => Some(s),
+            _ => None,
+        })
+    }
+
+    pub fn didx_mut(&mut self) -> Option<&mut DIDXSection> {
+        self.sections.iter_mut().find_map(|s| match &mut s.body {
+            SectionBody::DIDX(s) => Some(s),
+            _ => None,
+        })
+    }
+
+    /// The DATA section holding the embedded WEMs.
+    pub fn data(&self) -> Option<&DATASection> {
+        self.sections.iter().find_map(|s| match &s.body {
+            SectionBody::DATA(s) => Some(s),
+            _ => None,
+        })
+    }
+
+    pub fn data_mut(&mut self) -> Option<&mut DATASection> {
+        self.sections.iter_mut().find_map(|s| match &mut s.body {
+            SectionBody::DATA(s) => Some(s),
+            _ => None,
+        })
+    }
+
+    /// The HIRC section holding the soundbank's objects.
+    ///
+    /// ```
+    /// # use wwise_format::Soundbank;
+    /// # fn object_count(soundbank: &Soundbank) -> Option<usize> {
+    /// let count = soundbank.hirc()?.objects.len();
+    /// # Some(count)
+    /// # }
+    /// ```
+    pub fn hirc(&self) -> Option<&HIRCSection> {
+        self.sections.iter().find_map(|s| match &s.body {
+            SectionBody::HIRC(s) => Some(s),
+            _ => None,
+        })
+    }
+
+    pub fn hirc_mut(&mut self) -> Option<&mut HIRCSection> {
+        self.sections.iter_mut().find_map(|s| match &mut s.body {
+            SectionBody::HIRC(s) => Some(s),
+            _ => None,
+        })
+    }
+
+    /// Calls `visitor` for every HIRC object in the soundbank, allowing several
+    /// reports to be assembled in a single pass.
+    pub fn visit(&self, visitor: &mut impl FnMut(&HIRCObject)) {
+        for section in self.sections.iter() {
+            if let SectionBody::HIRC(h) = &section.body {
+                h.objects.iter().for_each(&mut *visitor);
+            }
+        }
+    }
+
+    /// Like `visit` but hands out mutable references for bulk edits.
+    pub fn visit_mut(&mut self, visitor: &mut impl FnMut(&mut HIRCObject)) {
+        for section in self.sections.iter_mut() {
+            if let SectionBody::HIRC(h) = &mut section.body {
+                h.objects.iter_mut().for_each(&mut *visitor);
+            }
+        }
+    }
+
+    /// Puts the soundbank in a fixed order so banks with the same content
+    /// encode to the same bytes, which keeps diffs between them small. HIRC
+    /// objects are sorted by their hash and the BKHD, DIDX and DATA are moved
+    /// to the front in that order. The other sections keep their order.
+    ///
+    /// This is opt-in as the game may rely on the order: the BKHD has to come
+    /// first and the DIDX has to directly precede the DATA it describes. Wwise
+    /// also writes objects after the objects they reference, which sorting by
+    /// hash doesn't preserve, so check that the game accepts the result.
+    pub fn canonicalize(&mut self) {
+        self.sections.sort_by_key(|s| match s.body {
+            SectionBody::BKHD(_) => 0,
+            SectionBody::DIDX(_) => 1,
+            SectionBody::DATA(_) => 2,
+            _ => 3,
+        });
+
+        for section in self.sections.iter_mut() {
+            if let SectionBody::HIRC(h) = &mut section.body {
+                h.objects.sort_by_key(|o| o.id.as_hash());
+            }
+        }
+    }
+
+    /// Applies `f` to every prop of every HIRC object, covering the node and
+    /// bus initial params as well as actions and modulators. Run
+    /// `prepare_export` afterwards so the sizes match the new props.
+    ///
+    /// ```
+    /// # use wwise_format::{PropBundle, Soundbank};
+    /// # fn quieter(soundbank: &mut Soundbank) {
+    /// soundbank.map_props(|p| if let PropBundle::Volume(v) = p {
+    ///     *v = -3.0;
+    /// });
+    /// # }
+    /// ```
+    pub fn map_props(&mut self, mut f: impl FnMut(&mut PropBundle)) {
+        self.visit_mut(&mut |object| {
+            if let Some(props) = object.body.prop_bundles_mut() {
+                props.iter_mut().for_each(&mut f);
+            }
+        });
+    }
+
+    /// Collects every prop that matches `predicate` along with the object it
+    /// belongs to.
+    pub fn find_props(
+        &self,
+        mut predicate: impl FnMut(&PropBundle) -> bool,
+    ) -> Vec<(&HIRCObject, &PropBundle)> {
+        self.sections.iter()
+            .filter_map(|s| match &s.body {
+                SectionBody::HIRC(h) => Some(h.objects.iter()),
                 _ => None,
             })
             .flatten()
+            .flat_map(|o| o.body.prop_bundles()
+                .unwrap_or_default()
+                .iter()
+                .map(move |p| (o, p)))
+            .filter(|(_, p)| predicate(p))
+            .collect()
+    }
+}
+
+/// Routes an object to the given bus instead of its parent's output. The route
+/// itself is only `override_bus_id`, the positioning `override_parent` flag is
+/// set along with it and has no say in which bus is used. Passing a `bus_id`
+/// of 0 routes the object back through its parent and clears the flag again.
+/// Returns false if the object doesn't exist or can't be routed.
+pub fn set_output_bus(soundbank: &mut Soundbank, object_id: u32, bus_id: u32) -> bool {
+    let mut found = false;
+
+    soundbank.visit_mut(&mut |object| {
+        if found || object.id.as_hash() != object_id {
+            return;
+        }
+
+        match &mut object.body {
+            HIRCObjectBody::Bus(b) => b.initial_values.override_bus_id = ObjectId::Hash(bus_id),
+            HIRCObjectBody::AuxiliaryBus(b) => b.initial_values.override_bus_id = ObjectId::Hash(bus_id),
+            body => match body.node_base_params_mut() {
+                Some(params) => {
+                    params.override_bus_id = ObjectId::Hash(bus_id);
+                    params.positioning_params.override_parent = bus_id != 0;
+                },
+                None => return,
+            },
+        }
+
+        found = true;
+    });
+
+    found
+}
+
+impl NodeInitialParams {
+    /// Sets `prop` to `base` and has the engine randomize it by an offset
+    /// between `min` and `max` on every playback. The prop value and its ranged
+    /// modifier are added or replaced together so they can't drift apart. Only
+    /// float props can be randomized.
+    pub fn set_randomized_prop(
+        &mut self,
+        prop: AkPropID,
+        base: f32,
+        min: f32,
+        max: f32,
+    ) -> Result<(), DekuError> {
+        let prop_id = prop.id();
+        let (_, value) = PropBundle::read(base.to_le_bytes().view_bits(), (Endian::Little, prop_id))?;
+
+        if let PropBundle::DelayTime(_)
+            | PropBundle::TransitionTime(_)
+            | PropBundle::AttachedPluginFXID(_)
+            | PropBundle::AttenuationID(_) = value {
+            return Err(DekuError::InvalidParam(format!("Prop {:#x} can not be randomized", prop_id)));
+        }
+
+        let existing = self.prop_initial_values.iter_mut()
+            .find(|p| p.deku_id().is_ok_and(|id| id == prop_id));
+        match existing {
+            Some(p) => *p = value,
+            None => self.prop_initial_values.push(value),
+        }
+
+        let existing = self.prop_ranged_modifiers.entries.iter_mut()
+            .find(|m| m.prop_type.id() == prop_id);
+        match existing {
+            Some(m) => {
+                m.min = min;
+                m.max = max;
+            },
+            None => self.prop_ranged_modifiers.entries.push(PropRangedModifier {
+                prop_type: prop,
+                min,
+                max,
+            }),
+        }
+
+        Ok(())
+    }
+}
+
+impl HIRCObjectBody {
+    /// Returns the node base params for the object types that are part of the
+    /// actor-mixer or interactive music hierarchy.
+    pub fn node_base_params(&self) -> Option<&NodeBaseParams> {
+        Some(match self {
+            HIRCObjectBody::Sound(o) => &o.node_base_params,
+            HIRCObjectBody::RandomSequenceContainer(o) => &o.node_base_params,
+            HIRCObjectBody::SwitchContainer(o) => &o.node_base_params,
+            HIRCObjectBody::ActorMixer(o) => &o.node_base_params,
+            HIRCObjectBody::LayerContainer(o) => &o.node_base_params,
+            HIRCObjectBody::MusicTrack(o) => &o.node_base_params,
+            HIRCObjectBody::MusicSegment(o) => &o.music_node_params.node_base_params,
+            HIRCObjectBody::MusicSwitchContainer(o)
+                => &o.music_trans_node_params.music_node_params.node_base_params,
+            HIRCObjectBody::MusicRandomSequenceContainer(o)
+                => &o.music_trans_node_params.music_node_params.node_base_params,
+            _ => return None,
+        })
+    }
+
+    pub fn node_base_params_mut(&mut self) -> Option<&mut NodeBaseParams> {
+        Some(match self {
+            HIRCObjectBody::Sound(o) => &mut o.node_base_params,
+            HIRCObjectBody::RandomSequenceContainer(o) => &mut o.node_base_params,
+            HIRCObjectBody::SwitchContainer(o) => &mut o.node_base_params,
+            HIRCObjectBody::ActorMixer(o) => &mut o.node_base_params,
+            HIRCObjectBody::LayerContainer(o) => &mut o.node_base_params,
+            HIRCObjectBody::MusicTrack(o) => &mut o.node_base_params,
+            HIRCObjectBody::MusicSegment(o) => &mut o.music_node_params.node_base_params,
+            HIRCObjectBody::MusicSwitchContainer(o)
+                => &mut o.music_trans_node_params.music_node_params.node_base_params,
+            HIRCObjectBody::MusicRandomSequenceContainer(o)
+                => &mut o.music_trans_node_params.music_node_params.node_base_params,
+            _ => return None,
+        })
+    }
+
+    /// Returns the props of the object types that have them.
+    pub fn prop_bundles(&self) -> Option<&[PropBundle]> {
+        Some(match self {
+            HIRCObjectBody::Bus(o) => &o.initial_values.bus_initial_params.prop_bundle,
+            HIRCObjectBody::AuxiliaryBus(o) => &o.initial_values.bus_initial_params.prop_bundle,
+            HIRCObjectBody::Action(o) => &o.prop_bundle,
+            HIRCObjectBody::DialogueEvent(o) => &o.prop_bundle,
+            HIRCObjectBody::LFOModulator(o) => &o.prop_bundle,
+            HIRCObjectBody::EnvelopeModulator(o) => &o.prop_bundle,
+            HIRCObjectBody::TimeModulator(o) => &o.prop_bundle,
+            body => &body.node_base_params()?.node_initial_params.prop_initial_values,
+        })
+    }
+
+    pub fn prop_bundles_mut(&mut self) -> Option<&mut Vec<PropBundle>> {
+        Some(match self {
+            HIRCObjectBody::Bus(o) => &mut o.initial_values.bus_initial_params.prop_bundle,
+            HIRCObjectBody::AuxiliaryBus(o) => &mut o.initial_values.bus_initial_params.prop_bundle,
+            HIRCObjectBody::Action(o) => &mut o.prop_bundle,
+            HIRCObjectBody::DialogueEvent(o) => &mut o.prop_bundle,
+            HIRCObjectBody::LFOModulator(o) => &mut o.prop_bundle,
+            HIRCObjectBody::EnvelopeModulator(o) => &mut o.prop_bundle,
+            HIRCObjectBody::TimeModulator(o) => &mut o.prop_bundle,
+            body => &mut body.node_base_params_mut()?.node_initial_params.prop_initial_values,
+        })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::*;
+    use wwise_fixtures as fixtures;
+
+    #[test]
+    fn visit_covers_every_object() {
+        let mut soundbank = parse_soundbank(&fixtures::soundbank(0x10)).unwrap();
+
+        let mut ids = vec![];
+        soundbank.visit(&mut |o| ids.push(o.id.as_hash()));
+        assert_eq!(ids, vec![1, 2, 3, 4, 5]);
+
+        soundbank.visit_mut(&mut |o| o.id = ObjectId::Hash(o.id.as_hash() + 10));
+        let mut ids = vec![];
+        soundbank.visit(&mut |o| ids.push(o.id.as_hash()));
+        assert_eq!(ids, vec![11, 12, 13, 14, 15]);
+    }
+
+    #[test]
+    fn canonicalize_orders_sections_and_objects() {
+        let mut soundbank = parse_soundbank(&fixtures::soundbank(0x10)).unwrap();
+        let expected = soundbank.to_prepared_bytes().unwrap();
+
+        soundbank.sections.reverse();
+        soundbank.hirc_mut().unwrap().objects.reverse();
+        soundbank.canonicalize();
+
+        let magics = soundbank.sections.iter().map(|s| &s.magic).collect::<Vec<_>>();
+        assert_eq!(magics, [b"BKHD", b"DIDX", b"DATA", b"HIRC"]);
+        assert_eq!(soundbank.to_prepared_bytes().unwrap(), expected);
+    }
+
+    #[test]
+    fn map_props_reaches_every_prop_list() {
+        use crate::export::PrepareExport;
+
+        let mut soundbank = parse_soundbank(&fixtures::soundbank(0x10)).unwrap();
+        soundbank.map_props(|p| if let PropBundle::Volume(v) = p {
+            *v = -6.0;
+        });
+        soundbank.prepare_export().unwrap();
+        let soundbank = parse_soundbank(&soundbank.to_prepared_bytes().unwrap()).unwrap();
+
+        let volumes = soundbank.find_props(|p| matches!(p, PropBundle::Volume(_)));
+        assert!(volumes.iter().all(|(_, p)| matches!(p, PropBundle::Volume(v) if *v == -6.0)));
+
+        let ids = volumes.iter().map(|(o, _)| o.id.as_hash()).collect::<Vec<_>>();
+        assert_eq!(ids, vec![2]);
+    }
+
+    #[test]
+    fn set_output_bus_overrides_parent() {
+        let mut soundbank = parse_soundbank(&fixtures::soundbank(0x10)).unwrap();
+
+        assert!(set_output_bus(&mut soundbank, 3, 1));
+        assert!(!set_output_bus(&mut soundbank, 4, 1));
+        assert!(!set_output_bus(&mut soundbank, 99, 1));
+
+        let params = soundbank.hirc_object(&ObjectId::Hash(3))
+            .and_then(|o| o.node_base_params())
+            .unwrap();
+        assert_eq!(params.override_bus_id, ObjectId::Hash(1));
+        assert_eq!(params.direct_parent_id, ObjectId::Hash(2));
+        assert!(params.positioning_params.override_parent);
+    }
+
+    #[test]
+    fn set_output_bus_to_zero_follows_parent() {
+        let mut soundbank = parse_soundbank(&fixtures::soundbank(0x10)).unwrap();
+
+        assert!(set_output_bus(&mut soundbank, 3, 1));
+        assert!(set_output_bus(&mut soundbank, 3, 0));
+
+        let params = soundbank.hirc_object(&ObjectId::Hash(3))
+            .and_then(|o| o.node_base_params())
+            .unwrap();
+        assert_eq!(params.override_bus_id, ObjectId::Hash(0));
+        assert!(!params.positioning_params.override_parent);
+    }
+
+    #[test]
+    fn set_randomized_prop_keeps_lists_aligned() {
+        use crate::export::PrepareExport;
+
+        let mut soundbank = parse_soundbank(&fixtures::soundbank(0x10)).unwrap();
+        soundbank.visit_mut(&mut |o| {
+            if let Some(params) = o.body.node_base_params_mut() {
+                let initial = &mut params.node_initial_params;
+                initial.set_randomized_prop(AkPropID::Volume, -6.0, -2.0, 2.0).unwrap();
+                initial.set_randomized_prop(AkPropID::Pitch, 0.0, -100.0, 100.0).unwrap();
+                initial.set_randomized_prop(AkPropID::Pitch, 0.0, -50.0, 50.0).unwrap();
+                assert!(initial.set_randomized_prop(AkPropID::AttenuationID, 0.0, 0.0, 1.0).is_err());
+            }
+        });
+
+        soundbank.prepare_export().unwrap();
+        let soundbank = parse_soundbank(&soundbank.to_prepared_bytes().unwrap()).unwrap();
+
+        let params = &soundbank.hirc_object(&ObjectId::Hash(2))
+            .and_then(|o| o.node_base_params())
+            .unwrap()
+            .node_initial_params;
+
+        assert!(matches!(
+            params.prop_initial_values.as_slice(),
+            [PropBundle::Volume(v), PropBundle::Pitch(p)] if *v == -6.0 && *p == 0.0,
+        ));
+
+        let ranges = params.prop_ranged_modifiers.entries.iter()
+            .map(|m| (m.prop_type.id(), m.min, m.max))
+            .collect::<Vec<_>>();
+        assert_eq!(ranges, vec![(0x00, -2.0, 2.0), (0x02, -50.0, 50.0)]);
     }
 }
@@ -0,0 +1,81 @@
+use crate::*;
+
+/// The languages Wwise projects start out with, keyed by the hash of their
+/// name. Projects can add their own, those won't be found here.
+const LANGUAGES: &[(u32, &str)] = &[
+    (SFX_LANGUAGE_HASH, "SFX"),
+    (0xC1F63175, "Arabic"),
+    (0xFCA0F40C, "Bulgarian"),
+    (0x0D059AEA, "Chinese(HK)"),
+    (0x8B3A36B4, "Chinese(Malaysia)"),
+    (0xEB588B30, "Chinese(PRC)"),
+    (0xB1DBABCB, "Chinese(Taiwan)"),
+    (0x344E7052, "Czech"),
+    (0xF2B93396, "Danish"),
+    (0x150AC109, "Dutch"),
+    (0x0897D17E, "English(Australia)"),
+    (0x41C9ABDF, "English(India)"),
+    (0x20CCE3BE, "English(UK)"),
+    (0x28CCF006, "English(US)"),
+    (0x03065CDE, "Finnish"),
+    (0x3D0EF1F2, "French(Canada)"),
+    (0x134795B3, "French(France)"),
+    (0xFFB9E71B, "German"),
+    (0xF73297B7, "Greek"),
+    (0x36C8FE7C, "Hebrew"),
+    (0x160FB000, "Hungarian"),
+    (0x40250161, "Indonesian"),
+    (0x49D84887, "Italian"),
+    (0x77BA6750, "Japanese"),
+    (0xCA1EF6F9, "Korean"),
+    (0xD963DB59, "Latin"),
+    (0x29CD57FB, "Norwegian"),
+    (0x215A058A, "Polish"),
+    (0x393E9711, "Portuguese(Brazil)"),
+    (0xEA28FBC9, "Portuguese(Portugal)"),
+    (0xF509A124, "Romanian"),
+    (0x99A5BFBC, "Russian"),
+    (0xCFAFAE22, "Slovenian"),
+    (0xDAD254F9, "Spanish(Mexico)"),
+    (0x0E07A43D, "Spanish(Spain)"),
+    (0xF74BF486, "Spanish(US)"),
+    (0x2DF81A20, "Swedish"),
+    (0xF09590DF, "Turkish"),
+    (0xF2517349, "Ukrainian"),
+    (0xA9BF50C0, "Vietnamese"),
+];
+
+impl BKHDSection {
+    /// The name of the bank's language if it's one of Wwise's stock
+    /// languages.
+    pub fn language_name(&self) -> Option<&'static str> {
+        LANGUAGES.iter()
+            .find(|(hash, _)| *hash == self.language_fnv_hash)
+            .map(|(_, name)| *name)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::*;
+    use wwise_fixtures as fixtures;
+
+    use super::LANGUAGES;
+
+    #[test]
+    fn names_known_languages() {
+        let mut soundbank = parse_soundbank(&fixtures::soundbank(0x10)).unwrap();
+        let bkhd = soundbank.bkhd_mut().unwrap();
+        assert_eq!(bkhd.language_name(), None);
+
+        bkhd.language_fnv_hash = SFX_LANGUAGE_HASH;
+        assert_eq!(bkhd.language_name(), Some("SFX"));
+
+        bkhd.language_fnv_hash = create_hash("English(US)");
+        assert_eq!(bkhd.language_name(), Some("English(US)"));
+
+        for (hash, name) in LANGUAGES {
+            assert_eq!(*hash, create_hash(name), "{}", name);
+        }
+    }
+}
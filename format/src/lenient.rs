@@ -0,0 +1,199 @@
+use deku::bitvec::BitView;
+use deku::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use crate::*;
+
+/// Something `parse_soundbank_lenient` had to leave out of the soundbank.
+#[derive(Debug)]
+pub struct ParseWarning {
+    /// Offset of the skipped section or HIRC object within the input.
+    pub offset: usize,
+    pub message: String,
+    /// The bytes that could not be parsed.
+    pub raw: Vec<u8>,
+}
+
+/// Why `parse_soundbank_located` failed, along with where.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct ParseError {
+    /// The error message deku gave.
+    pub message: String,
+    /// Offset of the section or HIRC object that failed to parse, `None` if
+    /// that couldn't be narrowed down.
+    pub offset: Option<usize>,
+}
+
+impl ParseError {
+    /// Finds the section or HIRC object in `bytes` that caused `error`.
+    pub fn locate(bytes: &[u8], error: DekuError) -> Self {
+        let (_, warnings) = parse_soundbank_lenient(bytes);
+
+        Self {
+            message: error.to_string(),
+            offset: warnings.first().map(|w| w.offset),
+        }
+    }
+}
+
+impl std::fmt::Display for ParseError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self.offset {
+            Some(offset) => write!(f, "{} at {:#x}", self.message, offset),
+            None => write!(f, "{}", self.message),
+        }
+    }
+}
+
+impl std::error::Error for ParseError {}
+
+/// Like `parse_soundbank` but reports where parsing failed, for tools that
+/// show the error to users.
+pub fn parse_soundbank_located(bytes: &[u8]) -> Result<Soundbank, ParseError> {
+    parse_soundbank(bytes).map_err(|e| ParseError::locate(bytes, e))
+}
+
+/// Best effort parse for exploring banks from unsupported versions. Sections
+/// and HIRC objects that fail to parse, for example because of an unknown enum
+/// ID, are left out of the soundbank and reported as warnings instead of
+/// aborting the parse. Writing the result back out drops the skipped data.
+pub fn parse_soundbank_lenient(bytes: &[u8]) -> (Soundbank, Vec<ParseWarning>) {
+    let mut sections = vec![];
+    let mut warnings = vec![];
+    let endian = detect_endian(bytes).unwrap_or(Endian::Little);
+    let mut version = 0;
+    let mut offset = 0;
+
+    while offset < bytes.len() {
+        let header = bytes.get(offset..offset + 8);
+        let size = header.map(|h| read_u32(h[4..8].try_into().unwrap(), endian) as usize);
+        let section_bytes = size.and_then(|s| bytes.get(offset..offset + 8 + s));
+
+        let (header, section_bytes) = match (header, section_bytes) {
+            (Some(h), Some(s)) => (h, s),
+            _ => {
+                warnings.push(ParseWarning {
+                    offset,
+                    message: "Truncated section".to_string(),
+                    raw: bytes[offset..].to_vec(),
+                });
+                break;
+            },
+        };
+
+        let section = match &header[0..4] {
+            b"HIRC" => Ok(parse_hirc_lenient(section_bytes, offset, endian, &mut warnings)),
+            _ => Section::read(section_bytes.view_bits(), (endian, version)).map(|(_, s)| s),
+        };
+
+        match section {
+            Ok(section) => {
+                if let SectionBody::BKHD(b) = &section.body {
+                    version = b.version;
+                }
+                sections.push(section);
+            },
+            Err(e) => warnings.push(ParseWarning {
+                offset,
+                message: e.to_string(),
+                raw: section_bytes.to_vec(),
+            }),
+        }
+
+        offset += section_bytes.len();
+    }
+
+    (Soundbank { endian, sections }, warnings)
+}
+
+fn parse_hirc_lenient(
+    section_bytes: &[u8],
+    section_offset: usize,
+    endian: Endian,
+    warnings: &mut Vec<ParseWarning>,
+) -> Section {
+    let header_size = HIRC_OBJECT_HEADER_SIZE as usize;
+    let body = &section_bytes[8..];
+    let object_count = body.get(0..4)
+        .map(|c| read_u32(c.try_into().unwrap(), endian))
+        .unwrap_or(0);
+
+    let mut objects = vec![];
+    let mut offset = 4;
+    for _ in 0..object_count {
+        let object_bytes = body.get(offset + 1..offset + header_size)
+            .map(|s| read_u32(s.try_into().unwrap(), endian) as usize)
+            .and_then(|size| body.get(offset..offset + header_size + size));
+
+        let object_bytes = match object_bytes {
+            Some(o) => o,
+            None => {
+                warnings.push(ParseWarning {
+                    offset: section_offset + 8 + offset,
+                    message: "Truncated HIRC object".to_string(),
+                    raw: body[offset.min(body.len())..].to_vec(),
+                });
+                break;
+            },
+        };
+
+        match HIRCObject::read(object_bytes.view_bits(), endian) {
+            Ok((_, object)) => objects.push(object),
+            Err(e) => warnings.push(ParseWarning {
+                offset: section_offset + 8 + offset,
+                message: e.to_string(),
+                raw: object_bytes.to_vec(),
+            }),
+        }
+
+        offset += object_bytes.len();
+    }
+
+    Section {
+        magic: *b"HIRC",
+        size: body.len() as u32,
+        body: SectionBody::HIRC(HIRCSection::new(objects)),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::*;
+    use wwise_fixtures as fixtures;
+
+    #[test]
+    fn skips_unknown_objects() {
+        let bytes = fixtures::minimal_bank(&[
+            fixtures::hirc_object(0x04, 1, &fixtures::event(&[])),
+            // An action type that doesn't exist
+            fixtures::hirc_object(0x03, 2, &[0xFF, 0xFF, 0x0, 0x0, 0x0, 0x0]),
+            fixtures::hirc_object(0x04, 3, &fixtures::event(&[2])),
+        ]);
+        assert!(parse_soundbank(&bytes).is_err());
+
+        let (soundbank, warnings) = parse_soundbank_lenient(&bytes);
+
+        let mut ids = vec![];
+        soundbank.visit(&mut |o| ids.push(o.id.as_hash()));
+        assert_eq!(ids, vec![1, 3]);
+
+        assert_eq!(warnings.len(), 1);
+        assert_eq!(warnings[0].offset, 0x1C + 8 + 4 + 10);
+        assert_eq!(&warnings[0].raw[5..9], &2u32.to_le_bytes());
+    }
+
+    #[test]
+    fn locates_parse_errors() {
+        let bytes = fixtures::minimal_bank(&[
+            fixtures::hirc_object(0x04, 1, &fixtures::event(&[])),
+            fixtures::hirc_object(0x03, 2, &[0xFF, 0xFF, 0x0, 0x0, 0x0, 0x0]),
+        ]);
+
+        let error = parse_soundbank_located(&bytes).unwrap_err();
+        assert_eq!(error.offset, Some(0x1C + 8 + 4 + 10));
+        assert_eq!(error.message, parse_soundbank(&bytes).unwrap_err().to_string());
+        assert!(error.to_string().ends_with(" at 0x32"));
+
+        assert!(parse_soundbank_located(&fixtures::soundbank(0x10)).is_ok());
+    }
+}
@@ -1,20 +1,106 @@
+
 use deku::prelude::*;
 
 mod bnk;
+mod builder;
+mod codec;
+mod curve;
+mod dictionary;
 mod export;
 mod helper;
+mod language;
+mod lenient;
+mod limits;
+mod media;
+mod reader;
+mod remap;
+mod repair;
+mod roundtrip;
 mod serialization;
+mod strict;
 
 pub use bnk::*;
+pub use builder::*;
+pub use codec::*;
+pub use curve::*;
+pub use dictionary::*;
 pub use helper::*;
+pub use lenient::*;
+pub use limits::*;
+pub use media::*;
+pub use reader::*;
+pub use remap::*;
+pub use repair::*;
+pub use roundtrip::*;
+pub use strict::*;
+
+pub use deku::ctx::Endian;
 
+use deku::bitvec::BitView;
 use export::PrepareExport;
 
+pub use export::PrepareExportError;
+
+/// Parses a little-endian soundbank, see `parse_soundbank_detect` for banks
+/// whose byte order isn't known up front.
 pub fn parse_soundbank(bytes: &[u8]) -> Result<Soundbank, DekuError> {
-    Soundbank::from_bytes((bytes, 0))
+    parse_soundbank_with(bytes, Endian::Little)
+}
+
+/// Parses a soundbank in the byte order reported by `detect_endian`, falling
+/// back to little-endian.
+pub fn parse_soundbank_detect(bytes: &[u8]) -> Result<Soundbank, DekuError> {
+    parse_soundbank_with(bytes, detect_endian(bytes).unwrap_or(Endian::Little))
+}
+
+pub fn parse_soundbank_with(bytes: &[u8], endian: Endian) -> Result<Soundbank, DekuError> {
+    Soundbank::read(bytes.view_bits(), endian)
         .map(|r| r.1)
 }
 
-pub fn prepare_soundbank(soundbank: &mut Soundbank) {
-    soundbank.prepare_export().unwrap();
+/// Parses a soundbank and names its HIRC objects through `dictionary`, see
+/// `Soundbank::resolve_names`.
+pub fn parse_soundbank_with_dictionary(
+    bytes: &[u8],
+    dictionary: &FNVDictionary,
+) -> Result<Soundbank, DekuError> {
+    let mut soundbank = parse_soundbank(bytes)?;
+    soundbank.resolve_names(dictionary);
+    Ok(soundbank)
+}
+
+/// Guesses the byte order of a soundbank from its BKHD version. Known versions
+/// all fit in two bytes, so the right byte order yields the smaller number.
+/// Returns `None` if the bank doesn't start with a BKHD.
+pub fn detect_endian(bytes: &[u8]) -> Option<Endian> {
+    if bytes.get(0..4)? != b"BKHD" {
+        return None;
+    }
+
+    let version: [u8; 4] = bytes.get(8..12)?.try_into().unwrap();
+    match u32::from_le_bytes(version) > u32::from_be_bytes(version) {
+        true => Some(Endian::Big),
+        false => Some(Endian::Little),
+    }
+}
+
+pub(crate) fn read_u32(bytes: [u8; 4], endian: Endian) -> u32 {
+    match endian {
+        Endian::Little => u32::from_le_bytes(bytes),
+        Endian::Big => u32::from_be_bytes(bytes),
+    }
+}
+
+/// Like `parse_soundbank` but refuses banks exceeding `limits`, for opening
+/// files from untrusted sources.
+pub fn parse_soundbank_with_limits(bytes: &[u8], limits: &ParseLimits) -> Result<Soundbank, DekuError> {
+    let endian = Endian::Little;
+    limits.check_headers(bytes, endian)?;
+    let soundbank = parse_soundbank_with(bytes, endian)?;
+    limits.check_soundbank(&soundbank)?;
+    Ok(soundbank)
+}
+
+pub fn prepare_soundbank(soundbank: &mut Soundbank) -> Result<(), PrepareExportError> {
+    soundbank.prepare_export()
 }
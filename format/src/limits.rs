@@ -0,0 +1,157 @@
+use deku::prelude::*;
+
+use crate::*;
+
+/// Upper bounds for `parse_soundbank_with_limits`. Section and object headers
+/// are checked before anything is decoded so a bank claiming a 4 GB section or
+/// millions of objects is rejected without attempting the allocation.
+#[derive(Clone, Debug)]
+pub struct ParseLimits {
+    pub max_sections: usize,
+    pub max_hirc_objects: usize,
+    pub max_object_size: u32,
+    pub max_tree_depth: u32,
+}
+
+impl Default for ParseLimits {
+    fn default() -> Self {
+        Self {
+            max_sections: 0x40,
+            max_hirc_objects: 0x100000,
+            max_object_size: 0x1000000,
+            max_tree_depth: 0x40,
+        }
+    }
+}
+
+impl ParseLimits {
+    pub(crate) fn check_headers(&self, bytes: &[u8], endian: Endian) -> Result<(), DekuError> {
+        let mut offset = 0;
+        let mut section_count = 0;
+
+        while offset < bytes.len() {
+            section_count += 1;
+            if section_count > self.max_sections {
+                return Err(limit_error(format!("more than {} sections", self.max_sections)));
+            }
+
+            let magic = read_slice(bytes, offset, 4)?;
+            let size = read_u32(bytes, offset + 4, endian)? as usize;
+            let body = read_slice(bytes, offset + 8, size)?;
+
+            if magic == b"HIRC" {
+                self.check_hirc(body, endian)?;
+            }
+
+            offset += 8 + size;
+        }
+
+        Ok(())
+    }
+
+    fn check_hirc(&self, body: &[u8], endian: Endian) -> Result<(), DekuError> {
+        let object_count = read_u32(body, 0, endian)? as usize;
+        if object_count > self.max_hirc_objects {
+            return Err(limit_error(format!(
+                "{} HIRC objects, the limit is {}",
+                object_count,
+                self.max_hirc_objects,
+            )));
+        }
+
+        let mut offset = 4;
+        for _ in 0..object_count {
+            // The size follows the one byte object type
+            let size = read_u32(body, offset + 1, endian)?;
+            if size > self.max_object_size {
+                return Err(limit_error(format!(
+                    "HIRC object of {} bytes, the limit is {}",
+                    size,
+                    self.max_object_size,
+                )));
+            }
+
+            let header_size = HIRC_OBJECT_HEADER_SIZE as usize;
+            read_slice(body, offset + header_size, size as usize)?;
+            offset += header_size + size as usize;
+        }
+
+        Ok(())
+    }
+
+    pub(crate) fn check_soundbank(&self, soundbank: &Soundbank) -> Result<(), DekuError> {
+        let mut result = Ok(());
+
+        soundbank.visit(&mut |o| {
+            let tree_depth = match &o.body {
+                HIRCObjectBody::MusicSwitchContainer(c) => c.tree_depth,
+                HIRCObjectBody::DialogueEvent(d) => d.tree_depth,
+                _ => return,
+            };
+
+            if tree_depth > self.max_tree_depth && result.is_ok() {
+                result = Err(limit_error(format!(
+                    "decision tree of depth {}, the limit is {}",
+                    tree_depth,
+                    self.max_tree_depth,
+                )));
+            }
+        });
+
+        result
+    }
+}
+
+fn limit_error(message: String) -> DekuError {
+    DekuError::Parse(format!("Soundbank exceeds parse limits: {}", message))
+}
+
+fn read_slice(bytes: &[u8], offset: usize, length: usize) -> Result<&[u8], DekuError> {
+    offset.checked_add(length)
+        .and_then(|end| bytes.get(offset..end))
+        .ok_or_else(|| limit_error(format!(
+            "{:#x} bytes claimed at offset {:#x} but only {:#x} available",
+            length,
+            offset,
+            bytes.len().saturating_sub(offset),
+        )))
+}
+
+fn read_u32(bytes: &[u8], offset: usize, endian: Endian) -> Result<u32, DekuError> {
+    Ok(crate::read_u32(read_slice(bytes, offset, 4)?.try_into().unwrap(), endian))
+}
+
+#[cfg(test)]
+mod test {
+    use crate::*;
+    use wwise_fixtures as fixtures;
+
+    #[test]
+    fn accepts_bank_within_limits() {
+        let bytes = fixtures::soundbank(0x10);
+        assert!(parse_soundbank_with_limits(&bytes, &ParseLimits::default()).is_ok());
+    }
+
+    #[test]
+    fn rejects_bank_over_limits() {
+        let bytes = fixtures::soundbank(0x10);
+
+        let limits = ParseLimits { max_sections: 3, ..Default::default() };
+        assert!(parse_soundbank_with_limits(&bytes, &limits).is_err());
+
+        let limits = ParseLimits { max_hirc_objects: 4, ..Default::default() };
+        assert!(parse_soundbank_with_limits(&bytes, &limits).is_err());
+
+        let limits = ParseLimits { max_object_size: 0x10, ..Default::default() };
+        assert!(parse_soundbank_with_limits(&bytes, &limits).is_err());
+    }
+
+    #[test]
+    fn rejects_oversized_section() {
+        let mut bytes = fixtures::soundbank(0x10);
+        // Claim a 4 GB BKHD
+        bytes[4..8].copy_from_slice(&u32::MAX.to_le_bytes());
+
+        assert!(parse_soundbank_with_limits(&bytes, &ParseLimits::default()).is_err());
+    }
+}
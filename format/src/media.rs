@@ -0,0 +1,220 @@
+use crate::*;
+
+/// A DIDX descriptor that doesn't fit the DATA section, see
+/// `Soundbank::validate_media`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum MediaError {
+    /// The WEM runs past the end of the DATA section.
+    OutOfBounds { id: u32 },
+    /// The WEM doesn't start on a multiple of the BKHD's WEM alignment.
+    Misaligned { id: u32, offset: u32 },
+    /// The WEM shares bytes with another one.
+    Overlap { id: u32, other: u32 },
+}
+
+impl std::fmt::Display for MediaError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            MediaError::OutOfBounds { id } => write!(f, "WEM {} runs past the DATA section", id),
+            MediaError::Misaligned { id, offset } => {
+                write!(f, "WEM {} at offset {:#x} is not aligned", id, offset)
+            },
+            MediaError::Overlap { id, other } => write!(f, "WEM {} overlaps WEM {}", id, other),
+        }
+    }
+}
+
+impl std::error::Error for MediaError {}
+
+/// How much media a soundbank embeds, see `Soundbank::media_stats`.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct MediaStats {
+    /// The number of embedded WEMs.
+    pub count: usize,
+    /// The combined size of the WEMs, leaving out the alignment padding.
+    pub total_bytes: u64,
+    /// The ID of the biggest WEM, `None` if there are none.
+    pub largest_id: Option<u32>,
+}
+
+impl Soundbank {
+    /// Sums up the DIDX descriptors without touching the DATA section.
+    /// Descriptors that point outside the DATA are counted all the same.
+    pub fn media_stats(&self) -> MediaStats {
+        let descriptors = match self.didx() {
+            Some(didx) => &didx.descriptors,
+            None => return MediaStats::default(),
+        };
+
+        MediaStats {
+            count: descriptors.len(),
+            total_bytes: descriptors.iter().map(|d| d.size as u64).sum(),
+            largest_id: descriptors.iter().max_by_key(|d| d.size).map(|d| d.id),
+        }
+    }
+
+    /// Checks that every DIDX descriptor lies within the DATA section, starts
+    /// on the WEM alignment and doesn't overlap another. Banks without a DIDX
+    /// are always valid.
+    pub fn validate_media(&self) -> Result<(), Vec<MediaError>> {
+        let didx = match self.didx() {
+            Some(didx) => didx,
+            None => return Ok(()),
+        };
+
+        let data_size = self.data().map_or(0, |d| d.data.len() as u64);
+        let alignment = self.bkhd().map_or(1, |b| b.wem_alignment.max(1));
+
+        let mut errors = vec![];
+        for d in didx.descriptors.iter() {
+            if d.offset as u64 + d.size as u64 > data_size {
+                errors.push(MediaError::OutOfBounds { id: d.id });
+            }
+            if !d.offset.is_multiple_of(alignment) {
+                errors.push(MediaError::Misaligned { id: d.id, offset: d.offset });
+            }
+        }
+
+        let mut sorted = didx.descriptors.iter()
+            .filter(|d| d.size != 0)
+            .collect::<Vec<_>>();
+        sorted.sort_by_key(|d| d.offset);
+        for pair in sorted.windows(2) {
+            if (pair[0].offset as u64 + pair[0].size as u64) > pair[1].offset as u64 {
+                errors.push(MediaError::Overlap { id: pair[1].id, other: pair[0].id });
+            }
+        }
+
+        match errors.is_empty() {
+            true => Ok(()),
+            false => Err(errors),
+        }
+    }
+
+    /// Carves the embedded WEMs out of the DATA section, in DIDX order.
+    /// Descriptors pointing outside of the DATA section are skipped.
+    pub fn extract_media(&self) -> Vec<(u32, Vec<u8>)> {
+        let (didx, data) = match (self.didx(), self.data()) {
+            (Some(didx), Some(data)) => (didx, data),
+            _ => return vec![],
+        };
+
+        didx.descriptors.iter()
+            .filter_map(|d| {
+                let start = d.offset as usize;
+                let end = start.checked_add(d.size as usize)?;
+                Some((d.id, data.data.get(start..end)?.to_vec()))
+            })
+            .collect()
+    }
+
+    /// Replaces the embedded WEMs with `entries`, laid out in the given order
+    /// with every WEM but the last padded to `alignment`. The DIDX and DATA
+    /// are created right after the BKHD if they don't exist yet and removed
+    /// when `entries` is empty. The BKHD padding is settled on export.
+    pub fn replace_media(&mut self, entries: &[(u32, Vec<u8>)], alignment: u32) {
+        let alignment = alignment.max(1);
+
+        if let Some(bkhd) = self.bkhd_mut() {
+            bkhd.wem_alignment = alignment;
+            bkhd.trim_padding();
+        }
+
+        self.sections.retain(|s| !matches!(s.body, SectionBody::DIDX(_) | SectionBody::DATA(_)));
+        if entries.is_empty() {
+            return;
+        }
+
+        let mut descriptors = vec![];
+        let mut data = vec![];
+        for (i, (id, bytes)) in entries.iter().enumerate() {
+            descriptors.push(DIDXDescriptor {
+                id: *id,
+                offset: data.len() as u32,
+                size: bytes.len() as u32,
+            });
+            data.extend(bytes);
+
+            if i != entries.len() - 1 {
+                data.resize(data.len().next_multiple_of(alignment as usize), 0);
+            }
+        }
+
+        let position = self.sections.iter()
+            .position(|s| matches!(s.body, SectionBody::BKHD(_)))
+            .map_or(0, |p| p + 1);
+        self.sections.splice(position..position, [
+            Section { magic: *b"DIDX", size: 0, body: SectionBody::DIDX(DIDXSection { descriptors }) },
+            Section { magic: *b"DATA", size: 0, body: SectionBody::DATA(DATASection { data }) },
+        ]);
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::*;
+    use wwise_fixtures as fixtures;
+
+    #[test]
+    fn extract_media_carves_data() {
+        let mut soundbank = parse_soundbank(&fixtures::soundbank(0x10)).unwrap();
+        assert_eq!(soundbank.extract_media(), vec![
+            (100, vec![0xAA; 0x15]),
+            (200, vec![0xBB; 0x33]),
+        ]);
+
+        soundbank.didx_mut().unwrap().descriptors[1].size += 1;
+        assert_eq!(soundbank.extract_media().len(), 1);
+    }
+
+    #[test]
+    fn replace_media_rebuilds_didx_and_data() {
+        let wems = vec![
+            (100, vec![0xAA; 0x15]),
+            (200, vec![0xBB; 0x33]),
+        ];
+
+        // Start from a bank without any media
+        let mut soundbank = parse_soundbank(&fixtures::soundbank(0x10)).unwrap();
+        soundbank.replace_media(&[], 0x10);
+        assert!(soundbank.didx().is_none() && soundbank.data().is_none());
+
+        soundbank.replace_media(&wems, 0x10);
+        assert_eq!(soundbank.extract_media(), wems);
+        assert_eq!(soundbank.to_prepared_bytes().unwrap(), fixtures::soundbank(0x10));
+    }
+
+    #[test]
+    fn media_stats_sums_descriptors() {
+        let mut soundbank = parse_soundbank(&fixtures::soundbank(0x10)).unwrap();
+        assert_eq!(soundbank.media_stats(), MediaStats {
+            count: 2,
+            total_bytes: 0x15 + 0x33,
+            largest_id: Some(200),
+        });
+
+        soundbank.replace_media(&[], 0x10);
+        assert_eq!(soundbank.media_stats(), MediaStats::default());
+    }
+
+    #[test]
+    fn validate_media_reports_descriptors() {
+        let mut soundbank = parse_soundbank(&fixtures::soundbank(0x10)).unwrap();
+        assert_eq!(soundbank.validate_media(), Ok(()));
+
+        let descriptors = &mut soundbank.didx_mut().unwrap().descriptors;
+        descriptors[0].size = 0x28;
+        descriptors[1].size += 1;
+        assert_eq!(soundbank.validate_media(), Err(vec![
+            MediaError::OutOfBounds { id: 200 },
+            MediaError::Overlap { id: 200, other: 100 },
+        ]));
+
+        soundbank.didx_mut().unwrap().descriptors[1].offset = 0x28;
+        let errors = soundbank.validate_media().unwrap_err();
+        assert!(errors.contains(&MediaError::Misaligned { id: 200, offset: 0x28 }));
+
+        assert!(matches!(prepare_soundbank(&mut soundbank), Err(PrepareExportError::Media(_))));
+        assert!(soundbank.to_prepared_bytes().is_err());
+    }
+}
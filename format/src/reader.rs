@@ -0,0 +1,217 @@
+use std::io;
+use std::io::Read;
+use std::io::Seek;
+
+use deku::bitvec::BitView;
+use deku::prelude::*;
+
+use crate::*;
+
+#[derive(Debug)]
+pub enum ReadSectionError {
+    Io(io::Error),
+    Deku(DekuError),
+}
+
+impl std::fmt::Display for ReadSectionError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ReadSectionError::Io(e) => write!(f, "Could not read section: {}", e),
+            ReadSectionError::Deku(e) => write!(f, "Could not parse section: {}", e),
+        }
+    }
+}
+
+impl std::error::Error for ReadSectionError {}
+
+impl From<io::Error> for ReadSectionError {
+    fn from(e: io::Error) -> Self {
+        ReadSectionError::Io(e)
+    }
+}
+
+/// The magic and size of a section along with where it starts in the stream.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SectionHeader {
+    pub magic: [u8; 4],
+    pub size: u32,
+    pub offset: u64,
+}
+
+/// Walks the sections of a soundbank without reading it into memory. Iterating
+/// only reads the section headers, bodies are parsed on request with
+/// `read_section` so large sections like DATA can be skipped entirely.
+///
+/// ```no_run
+/// # use std::fs;
+/// # use wwise_format::*;
+/// let mut reader = SoundbankReader::new(fs::File::open("bank.bnk").unwrap());
+/// while let Some(header) = reader.next() {
+///     let header = header.unwrap();
+///     if &header.magic == b"HIRC" {
+///         let section = reader.read_section(&header).unwrap();
+///     }
+/// }
+/// ```
+pub struct SoundbankReader<R> {
+    inner: R,
+    offset: u64,
+    endian: Endian,
+    version: u32,
+    finished: bool,
+}
+
+impl<R: Read + Seek> SoundbankReader<R> {
+    pub fn new(inner: R) -> Self {
+        Self { inner, offset: 0, endian: Endian::Little, version: 0, finished: false }
+    }
+
+    /// The byte order detected from the BKHD, little-endian until then.
+    pub fn endian(&self) -> Endian {
+        self.endian
+    }
+
+    /// The version from the BKHD, or 0 if it hasn't been encountered yet.
+    pub fn version(&self) -> u32 {
+        self.version
+    }
+
+    pub fn into_inner(self) -> R {
+        self.inner
+    }
+
+    pub fn read_section(&mut self, header: &SectionHeader) -> Result<Section, ReadSectionError> {
+        self.inner.seek(io::SeekFrom::Start(header.offset))?;
+
+        // Don't trust the size for the allocation, a truncated stream just
+        // comes up short.
+        let mut bytes = vec![];
+        (&mut self.inner).take(8 + header.size as u64).read_to_end(&mut bytes)?;
+
+        Section::read(bytes.view_bits(), (self.endian, self.version))
+            .map(|(_, s)| s)
+            .map_err(ReadSectionError::Deku)
+    }
+
+    fn read_header(&mut self) -> Result<Option<SectionHeader>, ReadSectionError> {
+        self.inner.seek(io::SeekFrom::Start(self.offset))?;
+
+        let mut bytes = [0u8; 8];
+        let read = read_up_to(&mut self.inner, &mut bytes)?;
+        if read == 0 {
+            return Ok(None);
+        } else if read != bytes.len() {
+            return Err(io::Error::from(io::ErrorKind::UnexpectedEof).into());
+        }
+
+        // Later sections need the version even if the BKHD itself is skipped,
+        // it also gives away the byte order.
+        if &bytes[0..4] == b"BKHD" {
+            let mut version = [0u8; 4];
+            self.inner.read_exact(&mut version)?;
+
+            let mut start = bytes.to_vec();
+            start.extend(version);
+            self.endian = detect_endian(&start).unwrap();
+            self.version = read_u32(version, self.endian);
+        }
+
+        let header = SectionHeader {
+            magic: bytes[0..4].try_into().unwrap(),
+            size: read_u32(bytes[4..8].try_into().unwrap(), self.endian),
+            offset: self.offset,
+        };
+
+        self.offset += 8 + header.size as u64;
+        Ok(Some(header))
+    }
+}
+
+impl<R: Read + Seek> Iterator for SoundbankReader<R> {
+    type Item = Result<SectionHeader, ReadSectionError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.finished {
+            return None;
+        }
+
+        // Stop after the first error as the stream position can't be trusted
+        let result = self.read_header();
+        self.finished = !matches!(result, Ok(Some(_)));
+        result.transpose()
+    }
+}
+
+fn read_up_to(reader: &mut impl Read, buffer: &mut [u8]) -> io::Result<usize> {
+    let mut read = 0;
+    while read < buffer.len() {
+        match reader.read(&mut buffer[read..]) {
+            Ok(0) => break,
+            Ok(n) => read += n,
+            Err(e) if e.kind() == io::ErrorKind::Interrupted => {},
+            Err(e) => return Err(e),
+        }
+    }
+    Ok(read)
+}
+
+#[cfg(test)]
+mod test {
+    use std::io;
+
+    use crate::*;
+    use wwise_fixtures as fixtures;
+
+    #[test]
+    fn reads_sections_on_request() {
+        let bytes = fixtures::soundbank(0x10);
+        let mut reader = SoundbankReader::new(io::Cursor::new(&bytes));
+
+        let mut magics = vec![];
+        let mut hirc = None;
+        while let Some(header) = reader.next() {
+            let header = header.unwrap();
+            magics.push(header.magic);
+
+            if &header.magic == b"HIRC" {
+                hirc = Some(reader.read_section(&header).unwrap());
+            }
+        }
+
+        assert_eq!(magics, vec![*b"BKHD", *b"DIDX", *b"DATA", *b"HIRC"]);
+        assert_eq!(reader.version(), 0x8C);
+        match hirc.unwrap().body {
+            SectionBody::HIRC(h) => assert_eq!(h.objects.len(), 5),
+            _ => unreachable!(),
+        }
+    }
+
+    #[test]
+    fn detects_big_endian_banks() {
+        let mut soundbank = parse_soundbank(&fixtures::soundbank(0x10)).unwrap();
+        soundbank.endian = Endian::Big;
+        let bytes = soundbank.to_prepared_bytes().unwrap();
+
+        let mut reader = SoundbankReader::new(io::Cursor::new(&bytes));
+        let headers = reader.by_ref().collect::<Result<Vec<_>, _>>().unwrap();
+        assert_eq!(headers.len(), 4);
+        assert_eq!(reader.endian(), Endian::Big);
+        assert_eq!(reader.version(), 0x8C);
+
+        match reader.read_section(&headers[3]).unwrap().body {
+            SectionBody::HIRC(h) => assert_eq!(h.objects.len(), 5),
+            _ => unreachable!(),
+        }
+    }
+
+    #[test]
+    fn truncated_header_is_an_error() {
+        let mut bytes = fixtures::bkhd(0x8C, 0xB4D, 0x10, 0);
+        bytes.extend(b"HIRC");
+
+        let mut reader = SoundbankReader::new(io::Cursor::new(&bytes));
+        assert!(reader.next().unwrap().is_ok());
+        assert!(matches!(reader.next(), Some(Err(ReadSectionError::Io(_)))));
+        assert!(reader.next().is_none());
+    }
+}
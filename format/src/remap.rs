@@ -0,0 +1,339 @@
+use std::collections;
+
+use crate::*;
+
+/// Rewrites the ID of every HIRC object found in `mapping` as well as all the
+/// references other objects hold to it. IDs that are not in the mapping, like
+/// objects defined in other soundbanks, are left untouched.
+pub fn remap_object_ids(soundbank: &mut Soundbank, mapping: &collections::HashMap<u32, u32>) {
+    let remap = |id: &mut u32| {
+        if let Some(new) = mapping.get(id) {
+            *id = *new;
+        }
+    };
+
+    let endian = soundbank.endian;
+    soundbank.visit_mut(&mut |object| {
+        if let Some(new) = mapping.get(&object.id.as_hash()) {
+            object.id = ObjectId::Hash(*new);
+        }
+
+        remap_body(&mut object.body, endian, &remap);
+    });
+}
+
+/// The references to other objects that are stored as an `ObjectId`, so they
+/// can be named like the objects themselves.
+pub(crate) fn object_id_references_mut(body: &mut HIRCObjectBody) -> Vec<&mut ObjectId> {
+    match body {
+        HIRCObjectBody::Action(a) => vec![&mut a.external_id],
+        HIRCObjectBody::Bus(b) => vec![&mut b.initial_values.override_bus_id],
+        HIRCObjectBody::AuxiliaryBus(b) => vec![&mut b.initial_values.override_bus_id],
+        body => match body.node_base_params_mut() {
+            Some(p) => vec![&mut p.override_bus_id, &mut p.direct_parent_id],
+            None => vec![],
+        },
+    }
+}
+
+fn remap_body(body: &mut HIRCObjectBody, endian: Endian, remap: &impl Fn(&mut u32)) {
+    for id in object_id_references_mut(body) {
+        let mut hash = id.as_hash();
+        remap(&mut hash);
+        if hash != id.as_hash() {
+            *id = ObjectId::Hash(hash);
+        }
+    }
+
+    if let Some(params) = body.node_base_params_mut() {
+        remap_node_base_params(params, remap);
+    }
+
+    match body {
+        HIRCObjectBody::Action(a) => {
+            remap_props(&mut a.prop_bundle, remap);
+            let except = match &mut a.params {
+                CAkActionParams::PauseE(p) => Some(&mut p.except),
+                CAkActionParams::StopE(p) | CAkActionParams::StopEO(p) => Some(&mut p.except),
+                CAkActionParams::MuteM(p) | CAkActionParams::MuteO(p)
+                | CAkActionParams::UnmuteM(p) | CAkActionParams::UnmuteO(p)
+                | CAkActionParams::UnmuteALL(p) | CAkActionParams::UnmuteALLO(p)
+                | CAkActionParams::UnmuteAE(p) | CAkActionParams::UnmuteAEO(p)
+                    => Some(&mut p.except),
+                CAkActionParams::BypassFXM(p) | CAkActionParams::BypassFXO(p)
+                | CAkActionParams::ResetBypassFXM(p) | CAkActionParams::ResetBypassFXO(p)
+                | CAkActionParams::ResetBypassFXALL(p) | CAkActionParams::ResetBypassFXALLO(p)
+                | CAkActionParams::ResetBypassFXAE(p) | CAkActionParams::ResetBypassFXAEO(p)
+                    => Some(&mut p.except),
+                CAkActionParams::SetVolumeM(p) | CAkActionParams::SetVolumeO(p)
+                | CAkActionParams::ResetVolumeM(p) | CAkActionParams::ResetVolumeO(p)
+                | CAkActionParams::SetLPFM(p) | CAkActionParams::ResetLPFM(p)
+                | CAkActionParams::SetBusVolumeM(p) | CAkActionParams::ResetBusVolumeM(p)
+                    => Some(&mut p.except),
+                CAkActionParams::SeekE(p) | CAkActionParams::SeekEO(p)
+                | CAkActionParams::SeekALL(p) | CAkActionParams::SeekALLO(p)
+                | CAkActionParams::SeekAE(p) | CAkActionParams::SeekAEO(p)
+                    => Some(&mut p.except),
+                CAkActionParams::SetGameParameter(p) | CAkActionParams::SetGameParameterO(p)
+                | CAkActionParams::ResetGameParameter(p) | CAkActionParams::ResetGameParameterO(p)
+                    => Some(&mut p.except),
+                _ => None,
+            };
+            for exception in except.into_iter().flat_map(|e| e.exceptions.iter_mut()) {
+                remap(&mut exception.object_id);
+            }
+        },
+        HIRCObjectBody::Event(e) => e.actions.iter_mut().for_each(remap),
+        HIRCObjectBody::RandomSequenceContainer(c) => {
+            c.children.items.iter_mut().for_each(remap);
+            for item in c.playlist.items.iter_mut() {
+                remap(&mut item.play_id);
+            }
+        },
+        HIRCObjectBody::SwitchContainer(c) => {
+            c.children.items.iter_mut().for_each(remap);
+            for group in c.switch_groups.iter_mut() {
+                group.nodes.iter_mut().for_each(remap);
+            }
+            for param in c.switch_params.iter_mut() {
+                remap(&mut param.node_id);
+            }
+        },
+        HIRCObjectBody::ActorMixer(c) => c.children.items.iter_mut().for_each(remap),
+        HIRCObjectBody::LayerContainer(c) => {
+            c.children.items.iter_mut().for_each(remap);
+            for layer in c.layers.iter_mut() {
+                remap_initial_rtpc(&mut layer.initial_rtpc, remap);
+                for child in layer.associated_children.iter_mut() {
+                    remap(&mut child.associated_child_id);
+                }
+            }
+        },
+        HIRCObjectBody::Bus(b) => remap_bus_initial_values(&mut b.initial_values, remap),
+        HIRCObjectBody::AuxiliaryBus(b) => remap_bus_initial_values(&mut b.initial_values, remap),
+        HIRCObjectBody::MusicSegment(s) => remap_music_node_params(&mut s.music_node_params, remap),
+        HIRCObjectBody::MusicTrack(t) => {
+            for item in t.playlist.iter_mut() {
+                remap(&mut item.event_id);
+            }
+        },
+        HIRCObjectBody::MusicSwitchContainer(c) => {
+            remap_music_trans_node_params(&mut c.music_trans_node_params, remap);
+            remap_decision_tree(&mut c.tree_data, c.tree_depth, endian, remap);
+        },
+        HIRCObjectBody::MusicRandomSequenceContainer(c) => {
+            remap_music_trans_node_params(&mut c.music_trans_node_params, remap);
+            for item in c.playlist_items.iter_mut() {
+                remap(&mut item.segment_id);
+            }
+        },
+        HIRCObjectBody::Attenuation(a) => remap_initial_rtpc(&mut a.initial_rtpc, remap),
+        HIRCObjectBody::DialogueEvent(d) => {
+            remap_decision_tree(&mut d.tree_data, d.tree_depth, endian, remap);
+            remap_props(&mut d.prop_bundle, remap);
+        },
+        HIRCObjectBody::EffectShareSet(f) => remap_initial_rtpc(&mut f.fx_base_initial_values.initial_rtpc, remap),
+        HIRCObjectBody::EffectCustom(f) => remap_initial_rtpc(&mut f.fx_base_initial_values.initial_rtpc, remap),
+        HIRCObjectBody::AudioDevice(f) => remap_initial_rtpc(&mut f.fx_base_initial_values.initial_rtpc, remap),
+        HIRCObjectBody::LFOModulator(m) => remap_initial_rtpc(&mut m.initial_rtpc, remap),
+        HIRCObjectBody::EnvelopeModulator(m) => remap_initial_rtpc(&mut m.initial_rtpc, remap),
+        HIRCObjectBody::TimeModulator(m) => remap_initial_rtpc(&mut m.initial_rtpc, remap),
+        _ => {},
+    }
+}
+
+fn remap_node_base_params(params: &mut NodeBaseParams, remap: &impl Fn(&mut u32)) {
+    for chunk in params.node_initial_fx_parameters.fx_chunks.iter_mut() {
+        remap(&mut chunk.fx_id);
+    }
+    remap_props(&mut params.node_initial_params.prop_initial_values, remap);
+    remap_aux_params(&mut params.aux_params, remap);
+    remap_initial_rtpc(&mut params.initial_rtpc, remap);
+}
+
+fn remap_bus_initial_values(values: &mut BusInitialValues, remap: &impl Fn(&mut u32)) {
+    remap_props(&mut values.bus_initial_params.prop_bundle, remap);
+    remap_aux_params(&mut values.bus_initial_params.aux_params, remap);
+    for duck in values.ducks.iter_mut() {
+        remap(&mut duck.bus_id);
+    }
+    for chunk in values.bus_initial_fx_params.fx.iter_mut() {
+        remap(&mut chunk.fx_id);
+    }
+    remap(&mut values.bus_initial_fx_params.fx_id_0);
+    remap_initial_rtpc(&mut values.initial_rtpc, remap);
+}
+
+fn remap_music_node_params(params: &mut MusicNodeParams, remap: &impl Fn(&mut u32)) {
+    params.children.items.iter_mut().for_each(remap);
+    for stinger in params.stingers.iter_mut() {
+        remap(&mut stinger.segment_id);
+    }
+}
+
+fn remap_music_trans_node_params(params: &mut MusicTransNodeParams, remap: &impl Fn(&mut u32)) {
+    remap_music_node_params(&mut params.music_node_params, remap);
+
+    // Transition rules store their IDs signed as -1 is used to denote "any"
+    let remap_signed = |id: &mut i32| {
+        let mut unsigned = *id as u32;
+        remap(&mut unsigned);
+        *id = unsigned as i32;
+    };
+
+    for rule in params.transition_rules.iter_mut() {
+        rule.source_ids.iter_mut().for_each(remap_signed);
+        rule.destination_ids.iter_mut().for_each(remap_signed);
+        remap(&mut rule.transition_object.segment_id);
+    }
+}
+
+// The leaves are patched in place so the tree layout is preserved exactly.
+fn remap_decision_tree(tree_data: &mut [u8], tree_depth: u32, endian: Endian, remap: &impl Fn(&mut u32)) {
+    let tree = match AkDecisionTreeNode::read(tree_data, tree_depth, endian) {
+        Ok(t) => t,
+        Err(_) => return,
+    };
+
+    let mut pending = vec![(0usize, &tree)];
+    while let Some((offset, node)) = pending.pop() {
+        if node.children.is_empty() && node.node_id != 0 {
+            let mut node_id = node.node_id;
+            remap(&mut node_id);
+
+            let start = offset * 0xC + 4;
+            tree_data[start..start + 4].copy_from_slice(&match endian {
+                Endian::Little => node_id.to_le_bytes(),
+                Endian::Big => node_id.to_be_bytes(),
+            });
+        }
+
+        for (i, child) in node.children.iter().enumerate() {
+            pending.push((node.index as usize + i, child));
+        }
+    }
+}
+
+fn remap_aux_params(params: &mut AuxParams, remap: &impl Fn(&mut u32)) {
+    remap(&mut params.aux1);
+    remap(&mut params.aux2);
+    remap(&mut params.aux3);
+    remap(&mut params.aux4);
+    remap(&mut params.reflections_aux_bus);
+}
+
+fn remap_initial_rtpc(initial_rtpc: &mut InitialRTPC, remap: &impl Fn(&mut u32)) {
+    // Only modulator driven RTPCs point at HIRC objects
+    for rtpc in initial_rtpc.rtpcs.iter_mut() {
+        if let AkRtpcType::Modulator = rtpc.rtpc_type {
+            remap(&mut rtpc.id);
+        }
+    }
+}
+
+fn remap_props(props: &mut [PropBundle], remap: &impl Fn(&mut u32)) {
+    for prop in props.iter_mut() {
+        match prop {
+            PropBundle::AttenuationID(id) => remap(id),
+            PropBundle::AttachedPluginFXID(id) => remap(id),
+            _ => {},
+        }
+    }
+}
+
+/// Strips identifying names from a soundbank so it can be shared without
+/// leaking project names. Named object IDs are turned back into hashes and the
+/// STID bank names are replaced with their IDs. When `remap_ids` is set every
+/// HIRC object also gets a new ID through a deterministic permutation.
+pub fn anonymize(soundbank: &mut Soundbank, remap_ids: bool) {
+    soundbank.visit_mut(&mut |object| object.id = ObjectId::Hash(object.id.as_hash()));
+
+    for section in soundbank.sections.iter_mut() {
+        if let SectionBody::STID(s) = &mut section.body {
+            for entry in s.entries.iter_mut() {
+                entry.name = entry.bnk_id.to_string().into_bytes();
+            }
+        }
+    }
+
+    if !remap_ids {
+        return;
+    }
+
+    let mut mapping = collections::HashMap::new();
+    soundbank.visit(&mut |o| {
+        mapping.insert(o.id.as_hash(), permute_id(o.id.as_hash()));
+    });
+
+    remap_object_ids(soundbank, &mapping);
+}
+
+// Multiplying by an odd constant is a bijection over u32, so no two IDs can end
+// up colliding.
+fn permute_id(id: u32) -> u32 {
+    id.wrapping_mul(0x9E3779B1) ^ 0x5BD1E995
+}
+
+#[cfg(test)]
+mod test {
+    use crate::*;
+    use wwise_fixtures as fixtures;
+
+    #[test]
+    fn anonymize_remaps_references() {
+        let mut soundbank = parse_soundbank(&fixtures::soundbank(0x10)).unwrap();
+        anonymize(&mut soundbank, true);
+
+        let hirc = soundbank.hirc().unwrap();
+
+        let ids = hirc.objects.iter()
+            .map(|o| o.id.as_hash())
+            .collect::<Vec<_>>();
+        assert!(!ids.contains(&1) && !ids.contains(&2));
+
+        // Sound 3 is parented to sound 2 and the action plays sound 2
+        let parent = &hirc.objects[2].body.node_base_params().unwrap().direct_parent_id;
+        assert_eq!(*parent, ObjectId::Hash(ids[1]));
+        match &hirc.objects[3].body {
+            HIRCObjectBody::Action(a) => assert_eq!(a.external_id, ObjectId::Hash(ids[1])),
+            _ => unreachable!(),
+        }
+        match &hirc.objects[4].body {
+            HIRCObjectBody::Event(e) => assert_eq!(e.actions, vec![ids[3]]),
+            _ => unreachable!(),
+        }
+    }
+
+    #[test]
+    fn remaps_big_endian_decision_trees() {
+        let bytes = fixtures::minimal_bank(&[fixtures::hirc_object(15, 1, &fixtures::dialogue_event(2))]);
+        let mut soundbank = parse_soundbank(&bytes).unwrap();
+
+        // Swap the tree over to big-endian, a root branch and a single leaf
+        soundbank.endian = Endian::Big;
+        soundbank.visit_mut(&mut |o| if let HIRCObjectBody::DialogueEvent(d) = &mut o.body {
+            for (i, node) in d.tree_data.chunks_mut(0xC).enumerate() {
+                node[0..4].reverse();
+                match i {
+                    0 => {
+                        node[4..6].reverse();
+                        node[6..8].reverse();
+                    },
+                    _ => node[4..8].reverse(),
+                }
+                node[8..10].reverse();
+                node[10..12].reverse();
+            }
+        });
+
+        remap_object_ids(&mut soundbank, &[(2, 0x1234)].into_iter().collect());
+
+        match &soundbank.hirc().unwrap().objects[0].body {
+            HIRCObjectBody::DialogueEvent(d) => {
+                let tree = d.decision_tree(Endian::Big).unwrap();
+                assert_eq!(tree.children[0].node_id, 0x1234);
+            },
+            _ => unreachable!(),
+        }
+    }
+}
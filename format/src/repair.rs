@@ -0,0 +1,130 @@
+use deku::bitvec::BitView;
+use deku::prelude::*;
+
+use crate::*;
+
+const SECTION_MAGICS: [&[u8; 4]; 10] = [
+    b"BKHD", b"DIDX", b"DATA", b"ENVS", b"FXPR", b"HIRC", b"STID", b"STMG", b"INIT", b"PLAT",
+];
+
+/// Parses a soundbank whose section or HIRC object sizes don't match their
+/// contents, as happens with hand-edited banks. A section size is trusted if it
+/// ends on the next section header or the end of the input. Otherwise the
+/// section is measured by parsing it, or for the sections whose layout depends
+/// on their size, by searching for the next section header. Every size that
+/// was off is reported as a warning and `prepare_export` writes the correct
+/// ones when encoding the result.
+pub fn parse_soundbank_repair(bytes: &[u8]) -> Result<(Soundbank, Vec<ParseWarning>), DekuError> {
+    let endian = detect_endian(bytes).unwrap_or(Endian::Little);
+    let mut sections = vec![];
+    let mut warnings = vec![];
+    let mut version = 0;
+    let mut offset = 0;
+
+    while offset < bytes.len() {
+        let header = bytes.get(offset..offset + 8)
+            .ok_or_else(|| DekuError::Parse(format!("Truncated section header at {:#x}", offset)))?;
+        let magic = String::from_utf8_lossy(&header[0..4]);
+        let declared = read_u32(header[4..8].try_into().unwrap(), endian) as usize;
+
+        let size = match offset.checked_add(8 + declared) {
+            Some(end) if is_section_boundary(bytes, end) => declared,
+            _ => measure_section(&bytes[offset..], endian, version).ok_or_else(|| DekuError::Parse(
+                format!("Could not find the end of the {} section at {:#x}", magic, offset),
+            ))?,
+        };
+
+        if size != declared {
+            warnings.push(ParseWarning {
+                offset,
+                message: format!("{} section declares {:#x} bytes but takes up {:#x}", magic, declared, size),
+                raw: vec![],
+            });
+        }
+
+        let mut section_bytes = bytes[offset..offset + 8 + size].to_vec();
+        section_bytes[4..8].copy_from_slice(&match endian {
+            Endian::Little => (size as u32).to_le_bytes(),
+            Endian::Big => (size as u32).to_be_bytes(),
+        });
+        let (_, section) = Section::read(section_bytes.view_bits(), (endian, version))?;
+
+        match &section.body {
+            SectionBody::BKHD(b) => version = b.version,
+            SectionBody::HIRC(h) => for object in h.objects.iter() {
+                let size = object.body.encoded_size()? + HIRC_OBJECT_ID_SIZE;
+                if size != object.size {
+                    warnings.push(ParseWarning {
+                        offset,
+                        message: format!(
+                            "HIRC object {} declares {:#x} bytes but takes up {:#x}",
+                            object.id.as_hash(),
+                            object.size,
+                            size,
+                        ),
+                        raw: vec![],
+                    });
+                }
+            },
+            _ => {},
+        }
+
+        sections.push(section);
+        offset += 8 + size;
+    }
+
+    Ok((Soundbank { endian, sections }, warnings))
+}
+
+fn is_section_boundary(bytes: &[u8], offset: usize) -> bool {
+    offset == bytes.len() || bytes.get(offset..offset + 4)
+        .is_some_and(|m| SECTION_MAGICS.iter().any(|s| &s[..] == m))
+}
+
+/// Finds the body size of the section at the start of `bytes`.
+fn measure_section(bytes: &[u8], endian: Endian, version: u32) -> Option<usize> {
+    if let Ok((rest, _)) = Section::read(bytes.view_bits(), (endian, version)) {
+        let end = bytes.len() - rest.len() / 8;
+        if is_section_boundary(bytes, end) {
+            return Some(end - 8);
+        }
+    }
+
+    (8..=bytes.len())
+        .find(|o| is_section_boundary(bytes, *o))
+        .map(|o| o - 8)
+}
+
+#[cfg(test)]
+mod test {
+    use crate::*;
+    use wwise_fixtures as fixtures;
+
+    #[test]
+    fn repairs_stale_sizes() {
+        let expected = fixtures::soundbank(0x10);
+
+        let mut bytes = expected.clone();
+        let size_at = |bytes: &[u8], magic: &[u8]| bytes.windows(4).position(|w| w == magic).unwrap() + 4;
+        // A DIDX that runs into the DATA and a HIRC that runs past the end
+        let didx = size_at(&bytes, b"DIDX");
+        bytes[didx..didx + 4].copy_from_slice(&0x30u32.to_le_bytes());
+        let hirc = size_at(&bytes, b"HIRC");
+        bytes[hirc] += 3;
+        // The size of the event, the last HIRC object
+        let event = bytes.len() - 13;
+        bytes[event] += 1;
+        assert!(parse_soundbank(&bytes).is_err());
+
+        let (soundbank, warnings) = parse_soundbank_repair(&bytes).unwrap();
+        let messages = warnings.iter().map(|w| w.message.as_str()).collect::<Vec<_>>();
+        assert_eq!(messages, vec![
+            "DIDX section declares 0x30 bytes but takes up 0x18",
+            "HIRC section declares 0xd2 bytes but takes up 0xcf",
+            "HIRC object 5 declares 0xa bytes but takes up 0x9",
+        ]);
+
+        assert_eq!(soundbank.to_prepared_bytes().unwrap(), expected);
+        assert!(parse_soundbank_repair(&expected).unwrap().1.is_empty());
+    }
+}
@@ -0,0 +1,152 @@
+use deku::prelude::*;
+
+use crate::*;
+
+#[derive(Debug)]
+pub enum RoundtripError {
+    Parse(DekuError),
+    Encode(DekuError),
+    /// The re-encoded soundbank differs from the original.
+    Mismatch {
+        /// Offset of the first differing byte in the original.
+        offset: usize,
+        /// The section containing the difference, `None` if the sections
+        /// themselves don't line up.
+        section: Option<[u8; 4]>,
+        /// The ID of the HIRC object containing the difference.
+        object_id: Option<u32>,
+    },
+}
+
+impl std::fmt::Display for RoundtripError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            RoundtripError::Parse(e) => write!(f, "Could not parse soundbank: {}", e),
+            RoundtripError::Encode(e) => write!(f, "Could not encode soundbank: {}", e),
+            RoundtripError::Mismatch { offset, section: None, .. } => {
+                write!(f, "Section layout diverges at offset {:#x}", offset)
+            },
+            RoundtripError::Mismatch { offset, section: Some(magic), object_id } => {
+                write!(f, "{} section", String::from_utf8_lossy(magic))?;
+                if let Some(id) = object_id {
+                    write!(f, " object {}", id)?;
+                }
+                write!(f, " diverges at offset {:#x}", offset)
+            },
+        }
+    }
+}
+
+impl std::error::Error for RoundtripError {}
+
+impl Soundbank {
+    /// Checks that parsing `original`, preparing it for export and encoding it
+    /// again reproduces `original` byte for byte. On a mismatch the first
+    /// differing offset is reported along with the section and HIRC object it
+    /// falls in.
+    pub fn verify_roundtrip(original: &[u8]) -> Result<(), RoundtripError> {
+        let soundbank = parse_soundbank(original)
+            .map_err(RoundtripError::Parse)?;
+        let output = soundbank.to_prepared_bytes()
+            .map_err(RoundtripError::Encode)?;
+
+        match output == original {
+            true => Ok(()),
+            false => Err(find_mismatch(original, &output, soundbank.endian)),
+        }
+    }
+}
+
+fn find_mismatch(original: &[u8], output: &[u8], endian: Endian) -> RoundtripError {
+    let original_sections = split_sections(original, endian);
+    let output_sections = split_sections(output, endian);
+
+    for (a, b) in original_sections.iter().zip(output_sections.iter()) {
+        let original_bytes = &original[a.1..a.2];
+        let output_bytes = &output[b.1..b.2];
+        if a.1 != b.1 {
+            break;
+        } else if original_bytes == output_bytes {
+            continue;
+        }
+
+        let offset = a.1 + first_difference(original_bytes, output_bytes);
+        let object_id = match &a.0 == b"HIRC" && &b.0 == b"HIRC" {
+            true => find_object(original_bytes, offset - a.1, endian),
+            false => None,
+        };
+
+        return RoundtripError::Mismatch { offset, section: Some(a.0), object_id };
+    }
+
+    RoundtripError::Mismatch {
+        offset: first_difference(original, output),
+        section: None,
+        object_id: None,
+    }
+}
+
+/// Finds the ID of the object in a HIRC section covering `offset`.
+fn find_object(section: &[u8], offset: usize, endian: Endian) -> Option<u32> {
+    let header_size = HIRC_OBJECT_HEADER_SIZE as usize;
+    let mut start = 8 + 4;
+
+    while start + header_size + 4 <= section.len() {
+        let size = read_u32(section[start + 1..start + 5].try_into().unwrap(), endian) as usize;
+        let end = start.saturating_add(header_size + size);
+        if offset < end {
+            return Some(read_u32(section[start + 5..start + 9].try_into().unwrap(), endian));
+        }
+
+        start = end;
+    }
+
+    None
+}
+
+/// Splits the soundbank into (magic, start, end) for every section.
+fn split_sections(bytes: &[u8], endian: Endian) -> Vec<([u8; 4], usize, usize)> {
+    let mut result = vec![];
+    let mut offset = 0;
+
+    while offset + 8 <= bytes.len() {
+        let size = read_u32(bytes[offset + 4..offset + 8].try_into().unwrap(), endian) as usize;
+        let end = offset.saturating_add(8 + size).min(bytes.len());
+
+        result.push((bytes[offset..offset + 4].try_into().unwrap(), offset, end));
+        offset = end;
+    }
+
+    result
+}
+
+fn first_difference(a: &[u8], b: &[u8]) -> usize {
+    a.iter().zip(b.iter())
+        .position(|(a, b)| a != b)
+        .unwrap_or(a.len().min(b.len()))
+}
+
+#[cfg(test)]
+mod test {
+    use crate::*;
+    use wwise_fixtures as fixtures;
+
+    #[test]
+    fn reports_first_divergent_object() {
+        let input = fixtures::soundbank(0x10);
+        assert!(Soundbank::verify_roundtrip(&input).is_ok());
+
+        // A stale object size still parses but prepare_export fixes it up
+        let mut soundbank = parse_soundbank(&input).unwrap();
+        soundbank.hirc_mut().unwrap().objects[2].size += 1;
+        let stale = soundbank.to_bytes().unwrap();
+
+        match Soundbank::verify_roundtrip(&stale) {
+            Err(RoundtripError::Mismatch { section, object_id, .. }) => {
+                assert_eq!(section, Some(*b"HIRC"));
+                assert_eq!(object_id, Some(3));
+            },
+            r => panic!("Expected a mismatch, got {:?}", r),
+        }
+    }
+}
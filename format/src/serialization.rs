@@ -2,13 +2,18 @@ pub mod cstring {
     use std::ffi;
     use serde::{Serialize, Deserialize};
     use serde::{Deserializer, Serializer};
+    use serde::de::Error;
 
     pub fn serialize<S: Serializer>(v: &ffi::CStr, s: S) -> Result<S::Ok, S::Error> {
         String::serialize(&v.to_string_lossy().to_string(), s)
     }
     
+    /// Trailing NULs, like the padding of a fixed-length name, are dropped.
+    /// Any NUL before the end of the name can't be encoded and is an error.
     pub fn deserialize<'de, D: Deserializer<'de>>(d: D) -> Result<ffi::CString, D::Error> {
-        Ok(ffi::CString::new(String::deserialize(d)?).unwrap())
+        let string = String::deserialize(d)?;
+        ffi::CString::new(string.trim_end_matches('\0'))
+            .map_err(|e| D::Error::custom(format!("name contains a NUL at {}", e.nul_position())))
     }
 }
 
@@ -29,15 +34,87 @@ pub mod bytestring {
 
 pub mod base64 {
     use base64::Engine;
+    use base64::engine::general_purpose::{STANDARD, STANDARD_NO_PAD};
     use serde::{Serialize, Deserialize};
     use serde::{Deserializer, Serializer};
+    use serde::de::Error;
 
     pub fn serialize<S: Serializer>(v: &Vec<u8>, s: S) -> Result<S::Ok, S::Error> {
-        String::serialize(&base64::engine::general_purpose::STANDARD_NO_PAD.encode(v), s)
+        String::serialize(&STANDARD_NO_PAD.encode(v), s)
     }
     
+    /// Accepts both padded and unpadded input, padded base64 is what most
+    /// other tools will produce when the JSON is edited by hand.
     pub fn deserialize<'de, D: Deserializer<'de>>(d: D) -> Result<Vec<u8>, D::Error> {
-        Ok(base64::engine::general_purpose::STANDARD_NO_PAD.decode(String::deserialize(d)?)
-            .unwrap())
+        let string = String::deserialize(d)?;
+        STANDARD.decode(&string)
+            .or_else(|_| STANDARD_NO_PAD.decode(&string))
+            .map_err(|e| D::Error::custom(format!("invalid base64: {}", e)))
+    }
+}
+
+pub mod endian {
+    use deku::ctx::Endian;
+    use serde::{Serialize, Deserialize};
+    use serde::{Deserializer, Serializer};
+    use serde::de::Error;
+
+    pub fn serialize<S: Serializer>(v: &Endian, s: S) -> Result<S::Ok, S::Error> {
+        match v {
+            Endian::Little => "little",
+            Endian::Big => "big",
+        }.serialize(s)
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(d: D) -> Result<Endian, D::Error> {
+        String::deserialize(d)?.parse()
+            .map_err(|_| D::Error::custom("expected either little or big"))
+    }
+
+    pub fn little() -> Endian {
+        Endian::Little
+    }
+
+    pub fn is_little(v: &Endian) -> bool {
+        v.is_le()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::ffi;
+
+    #[derive(Debug, serde::Deserialize)]
+    struct Named {
+        #[serde(with = "super::cstring")]
+        name: ffi::CString,
+    }
+
+    #[derive(Debug, serde::Serialize, serde::Deserialize)]
+    struct Blob {
+        #[serde(with = "super::base64")]
+        data: Vec<u8>,
+    }
+
+    #[test]
+    fn cstring_rejects_interior_nul() {
+        let named: Named = serde_json::from_str(r#"{"name": "Play_Footstep\u0000\u0000"}"#).unwrap();
+        assert_eq!(named.name.as_bytes(), b"Play_Footstep");
+
+        let result = serde_json::from_str::<Named>(r#"{"name": "Play\u0000Footstep"}"#);
+        assert!(result.unwrap_err().to_string().contains("NUL at 4"));
+    }
+
+    #[test]
+    fn base64_accepts_padded_and_unpadded() {
+        let padded: Blob = serde_json::from_str(r#"{"data": "AQID/w=="}"#).unwrap();
+        let unpadded: Blob = serde_json::from_str(r#"{"data": "AQID/w"}"#).unwrap();
+        assert_eq!(padded.data, [0x01, 0x02, 0x03, 0xFF]);
+        assert_eq!(unpadded.data, [0x01, 0x02, 0x03, 0xFF]);
+
+        // Output stays unpadded
+        assert_eq!(serde_json::to_string(&padded).unwrap(), r#"{"data":"AQID/w"}"#);
+
+        assert!(serde_json::from_str::<Blob>(r#"{"data": "AQ!D"}"#).is_err());
     }
 }
@@ -0,0 +1,61 @@
+use deku::prelude::*;
+
+use crate::*;
+
+/// Like `parse_soundbank` but fails when a section or HIRC object didn't take
+/// up exactly the size its header declares. Fields that aren't modeled leave
+/// bytes behind that would otherwise be dropped silently on re-encode. What a
+/// body took up is measured by encoding it again, see `SectionBody::encoded_size`.
+pub fn parse_soundbank_strict(bytes: &[u8]) -> Result<Soundbank, DekuError> {
+    let soundbank = parse_soundbank(bytes)?;
+    let version = soundbank.version();
+
+    for section in soundbank.sections.iter() {
+        let size = section.body.encoded_size(version)?;
+        if size != section.size {
+            return Err(DekuError::Parse(format!(
+                "{} section declares {:#x} bytes but {:#x} were read",
+                String::from_utf8_lossy(&section.magic),
+                section.size,
+                size,
+            )));
+        }
+    }
+
+    for object in soundbank.hirc().iter().flat_map(|h| h.objects.iter()) {
+        let size = object.body.encoded_size()? + HIRC_OBJECT_ID_SIZE;
+        if size != object.size {
+            return Err(DekuError::Parse(format!(
+                "HIRC object {} declares {:#x} bytes but {:#x} were read",
+                object.id.as_hash(),
+                object.size,
+                size,
+            )));
+        }
+    }
+
+    Ok(soundbank)
+}
+
+#[cfg(test)]
+mod test {
+    use crate::*;
+    use wwise_fixtures as fixtures;
+
+    #[test]
+    fn strict_parse_checks_declared_sizes() {
+        let bytes = fixtures::soundbank(0x10);
+        assert!(parse_soundbank_strict(&bytes).is_ok());
+
+        // Bump the size of the event, the last HIRC object
+        let mut soundbank = parse_soundbank(&bytes).unwrap();
+        soundbank.hirc_mut().unwrap().objects[4].size += 1;
+        let bytes = soundbank.to_bytes().unwrap();
+
+        assert!(parse_soundbank(&bytes).is_ok());
+        match parse_soundbank_strict(&bytes) {
+            Err(DekuError::Parse(e)) => assert!(e.starts_with("HIRC object 5"), "{}", e),
+            r => panic!("Expected a parse error, got {:?}", r),
+        }
+    }
+}
@@ -1,12 +1,15 @@
 use std::fs;
 use std::path;
 use std::io::Read;
+use std::collections;
 
-use clap::Parser;
-use wwise_format::*;
+use clap::{Parser, ValueEnum};
+use clap::builder::PossibleValuesParser;
+use wwise_format::HIRCObject;
 use wwise_analysis::dictionary::parse_dictionary;
-use wwise_analysis::label::get_label;
-use wwise_analysis::audio_routable::get_output_nodes;
+use wwise_analysis::label::{get_label, get_type_label, TYPE_LABELS};
+use wwise_analysis::audio_routable::{get_action_edges, get_output_nodes};
+use wwise_analysis::hierarchy::depths;
 use tabbycat::{GraphBuilder, GraphType, Identity, StmtList, Edge, AttrType, AttrList, SubGraph};
 
 #[derive(Parser)]
@@ -17,15 +20,137 @@ struct Arguments {
 
     #[arg(short, long, num_args = 0..)]
     soundbanks: Vec<path::PathBuf>,
+
+    /// How to print the routing graph
+    #[arg(long, value_enum, default_value_t = Format::Dot)]
+    format: Format,
+
+    /// Only draw objects of these types, edges are routed past the others
+    #[arg(long, num_args = 1.., value_parser = PossibleValuesParser::new(TYPE_LABELS))]
+    only: Vec<String>,
+
+    /// Leave out objects of these types, edges are routed past them
+    #[arg(long, num_args = 1.., value_parser = PossibleValuesParser::new(TYPE_LABELS))]
+    exclude: Vec<String>,
+
+    /// Lay the graph out top-down, ranking objects by their depth in the bus
+    /// hierarchy
+    #[arg(long)]
+    rank_by_depth: bool,
+
+    /// Fill colors of the bus, sound, container, music, action and other
+    /// objects in that order, empty or left out colors keep their default
+    #[arg(long, value_delimiter = ',')]
+    palette: Vec<String>,
+}
+
+impl Arguments {
+    fn includes(&self, object: &HIRCObject) -> bool {
+        let type_label = get_type_label(object);
+        (self.only.is_empty() || self.only.iter().any(|t| t == type_label))
+            && !self.exclude.iter().any(|t| t == type_label)
+    }
+
+    /// Follows an edge past any pruned objects, yielding the nearest objects
+    /// that are drawn. Objects from other soundbanks can't be typed and are
+    /// always kept.
+    fn reroute(&self, target: u32, objects: &collections::HashMap<u32, &HIRCObject>) -> Vec<u32> {
+        let mut result = vec![];
+        let mut visited = collections::HashSet::new();
+        let mut pending = vec![target];
+
+        while let Some(id) = pending.pop() {
+            if !visited.insert(id) {
+                continue;
+            }
+
+            match objects.get(&id) {
+                Some(object) if !self.includes(object) => {
+                    pending.extend(get_output_nodes(object).into_iter().flatten());
+                    pending.extend(get_action_edges(object).into_iter().flatten());
+                },
+                _ => result.push(id),
+            }
+        }
+
+        result
+    }
+}
+
+/// The groups of object types that share a fill color.
+#[derive(Clone, Copy)]
+enum Category {
+    Bus,
+    Sound,
+    Container,
+    Music,
+    Action,
+    Other,
+}
+
+impl Category {
+    const ALL: [Category; 6] = [
+        Category::Bus,
+        Category::Sound,
+        Category::Container,
+        Category::Music,
+        Category::Action,
+        Category::Other,
+    ];
+
+    fn of(object: &HIRCObject) -> Self {
+        match get_type_label(object) {
+            "Bus" | "AuxiliaryBus" => Category::Bus,
+            "Sound" => Category::Sound,
+            "RandomSequenceContainer" | "SwitchContainer" | "ActorMixer" | "LayerContainer"
+                => Category::Container,
+            "MusicSegment" | "MusicTrack" | "MusicSwitchContainer" | "MusicRandomSequenceContainer"
+                => Category::Music,
+            "Action" | "Event" | "DialogueEvent" => Category::Action,
+            _ => Category::Other,
+        }
+    }
+
+    fn name(&self) -> &'static str {
+        match self {
+            Category::Bus => "Bus",
+            Category::Sound => "Sound",
+            Category::Container => "Container",
+            Category::Music => "Music",
+            Category::Action => "Action",
+            Category::Other => "Other",
+        }
+    }
+
+    fn color<'a>(&self, palette: &'a [String]) -> &'a str {
+        const DEFAULT: [&str; 6] = ["#80b1d3", "#b3de69", "#fdb462", "#bc80bd", "#fb8072", "#d9d9d9"];
+
+        let index = *self as usize;
+        palette.get(index)
+            .filter(|c| !c.is_empty())
+            .map(|c| c.as_str())
+            .unwrap_or(DEFAULT[index])
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, ValueEnum)]
+enum Format {
+    /// A Graphviz digraph with a cluster per soundbank
+    Dot,
+    /// The nodes and edges of every soundbank as a JSON object
+    Json,
 }
 
 fn main() {
     let args = Arguments::parse();
 
-    let dictionary_file = fs::read_to_string(args.dictionary)
+    let dictionary_file = fs::read_to_string(&args.dictionary)
         .expect("Could not read dictionary");
     let dictionary = parse_dictionary(&dictionary_file);
 
+    let mut json_nodes = vec![];
+    let mut json_edges = vec![];
+
     let mut stmt = StmtList::new()
         .add_attr(
             AttrType::Graph,
@@ -56,8 +181,21 @@ fn main() {
                 )
         );
 
-    for path in args.soundbanks {
-        let mut handle = fs::File::open(&path)
+    // Edges run from child to parent, so the master bus ends up on top when
+    // ranking bottom to top.
+    if args.rank_by_depth {
+        stmt = stmt.add_attr(
+            AttrType::Graph,
+            AttrList::default()
+                .add(
+                    Identity::String("rankdir".into()),
+                    Identity::String("BT".into()),
+                )
+        );
+    }
+
+    for path in args.soundbanks.iter() {
+        let mut handle = fs::File::open(path)
             .expect("Could not acquire file handle");
 
         let mut file_buffer = vec![];
@@ -71,7 +209,7 @@ fn main() {
         let parsed = wwise_format::parse_soundbank(&file_buffer)
             .expect("Could not parse bnk");
 
-        let hirc = match get_hirc(&parsed) {
+        let hirc = match parsed.hirc() {
             None => continue,
             Some(h) => h,
         };
@@ -110,11 +248,51 @@ fn main() {
                     )
             );
 
+        let objects = hirc.objects.iter()
+            .map(|o| (o.id.as_hash(), o))
+            .collect::<collections::HashMap<_, _>>();
+
+        let depths = depths(&parsed);
+        let mut ranks = collections::BTreeMap::<usize, Vec<u32>>::new();
+
         for object in hirc.objects.iter() {
-            let output_nodes = match get_output_nodes(object) {
-                Some(r) => r,
-                None => continue,
-            };
+            let output_nodes = get_output_nodes(object);
+            let action_edges = get_action_edges(object);
+            if (output_nodes.is_none() && action_edges.is_none()) || !args.includes(object) {
+                continue;
+            }
+
+            let output_nodes = output_nodes.into_iter().flatten()
+                .flat_map(|to| args.reroute(to, &objects))
+                .collect::<Vec<_>>();
+            let action_edges = action_edges.into_iter().flatten()
+                .flat_map(|to| args.reroute(to, &objects))
+                .collect::<Vec<_>>();
+
+            if args.format == Format::Json {
+                let mut node = serde_json::Map::new();
+                node.insert("id".into(), object.id.as_hash().into());
+                node.insert("label".into(), get_label(object, Some(&dictionary)).into());
+                node.insert("type".into(), get_type_label(object).into());
+                node.insert("soundbank".into(), bnk_name.to_string().into());
+                json_nodes.push(serde_json::Value::Object(node));
+
+                let edges = output_nodes.into_iter().map(|to| (to, "output"))
+                    .chain(action_edges.into_iter().map(|to| (to, "action")));
+                for (to, kind) in edges {
+                    let mut edge = serde_json::Map::new();
+                    edge.insert("from".into(), object.id.as_hash().into());
+                    edge.insert("to".into(), to.into());
+                    edge.insert("kind".into(), kind.into());
+                    json_edges.push(serde_json::Value::Object(edge));
+                }
+
+                continue;
+            }
+
+            if let Some(depth) = depths.get(&object.id.as_hash()) {
+                ranks.entry(*depth).or_default().push(object.id.as_hash());
+            }
 
             subgraph_stmt = subgraph_stmt.add_node(
                 Identity::from(object.id.as_hash()),
@@ -125,6 +303,10 @@ fn main() {
                             Identity::String("label".into()),
                             Identity::quoted(get_label(object, Some(&dictionary))),
                         )
+                        .add(
+                            Identity::String("fillcolor".into()),
+                            Identity::quoted(Category::of(object).color(&args.palette)),
+                        )
                 )
             );
 
@@ -141,6 +323,43 @@ fn main() {
                     )
                 )
             }
+
+            // Dashed to tell triggering apart from audio flowing
+            for target in action_edges.into_iter() {
+                subgraph_stmt = subgraph_stmt.add_edge(
+                    Edge::head_node(
+                        Identity::from(object.id.as_hash()),
+                        None
+                    )
+                    .arrow_to_node(
+                        Identity::from(target),
+                        None
+                    )
+                    .add_attribute(
+                        Identity::String("style".into()),
+                        Identity::String("dashed".into()),
+                    )
+                )
+            }
+        }
+
+        if args.rank_by_depth {
+            for ids in ranks.into_values() {
+                let mut rank_stmt = StmtList::new()
+                    .add_attr(
+                        AttrType::Graph,
+                        AttrList::default()
+                            .add(
+                                Identity::String("rank".into()),
+                                Identity::String("same".into()),
+                            )
+                    );
+                for id in ids.into_iter() {
+                    rank_stmt = rank_stmt.add_node(Identity::from(id), None, None);
+                }
+
+                subgraph_stmt = subgraph_stmt.add_subgraph(SubGraph::subgraph(None, rank_stmt));
+            }
         }
 
         let subgraph = SubGraph::subgraph(Some(Identity::quoted(format!("cluster_{}", bnk_name))), subgraph_stmt);
@@ -148,6 +367,50 @@ fn main() {
         stmt = stmt.add_subgraph(subgraph);
     }
 
+    if args.format == Format::Json {
+        let mut graph = serde_json::Map::new();
+        graph.insert("nodes".into(), json_nodes.into());
+        graph.insert("edges".into(), json_edges.into());
+        println!("{}", serde_json::Value::Object(graph));
+        return;
+    }
+
+    let mut legend_stmt = StmtList::new()
+        .add_attr(
+            AttrType::Graph,
+            AttrList::default()
+                .add(
+                    Identity::String("label".into()),
+                    Identity::quoted("Legend"),
+                )
+        )
+        .add_attr(
+            AttrType::Node,
+            AttrList::default()
+                .add(
+                    Identity::String("style".into()),
+                    Identity::String("filled".into())
+                )
+        );
+    for category in Category::ALL {
+        legend_stmt = legend_stmt.add_node(
+            Identity::quoted(format!("legend_{}", category.name())),
+            None,
+            Some(
+                AttrList::default()
+                    .add(
+                        Identity::String("label".into()),
+                        Identity::quoted(category.name()),
+                    )
+                    .add(
+                        Identity::String("fillcolor".into()),
+                        Identity::quoted(category.color(&args.palette)),
+                    )
+            )
+        );
+    }
+    stmt = stmt.add_subgraph(SubGraph::subgraph(Some(Identity::quoted("cluster_legend")), legend_stmt));
+
     let graph = GraphBuilder::default()
         .graph_type(GraphType::DiGraph)
         .strict(true)
@@ -158,13 +421,3 @@ fn main() {
 
     println!("{}", graph);
 }
-
-fn get_hirc(sb: &Soundbank) -> Option<&HIRCSection> {
-    for section in sb.sections.iter() {
-        if let SectionBody::HIRC(h) = &section.body {
-            return Some(h)
-        }
-    }
-
-    None
-}
@@ -0,0 +1,223 @@
+use std::fs;
+use std::path;
+use std::collections;
+
+use clap::Parser;
+use serde_json::Value;
+use wwise_format::*;
+use wwise_analysis::label::get_type_label;
+
+#[derive(Parser)]
+#[command(author, version, about, long_about = None)]
+struct Arguments {
+    /// The soundbank to compare against, usually the vanilla one
+    original: path::PathBuf,
+
+    /// The altered soundbank
+    modified: path::PathBuf,
+
+    #[arg(long)]
+    /// Emit the differences as a single JSON object
+    json: bool,
+}
+
+/// A value that differs at `path`, `Null` on the side it's missing from.
+struct FieldDiff {
+    path: String,
+    original: Value,
+    modified: Value,
+}
+
+fn main() {
+    let args = Arguments::parse();
+
+    let original = load(&args.original);
+    let modified = load(&args.modified);
+
+    let original_sections = sections(&original);
+    let modified_sections = sections(&modified);
+
+    let mut sections_added = vec![];
+    let mut sections_removed = vec![];
+    let mut sections_changed = vec![];
+    for magic in original_sections.keys().chain(modified_sections.keys()).collect::<collections::BTreeSet<_>>() {
+        match (original_sections.get(magic), modified_sections.get(magic)) {
+            (Some(_), None) => sections_removed.push(magic.clone()),
+            (None, Some(_)) => sections_added.push(magic.clone()),
+            (Some(a), Some(b)) => {
+                let mut fields = vec![];
+                diff_values("", a, b, &mut fields);
+                if !fields.is_empty() {
+                    sections_changed.push((magic.clone(), fields));
+                }
+            },
+            (None, None) => unreachable!(),
+        }
+    }
+
+    let original_objects = objects(&original);
+    let modified_objects = objects(&modified);
+
+    let mut objects_added = vec![];
+    let mut objects_removed = vec![];
+    let mut objects_changed = vec![];
+    for id in original_objects.keys().chain(modified_objects.keys()).collect::<collections::BTreeSet<_>>() {
+        match (original_objects.get(id), modified_objects.get(id)) {
+            (Some(a), None) => objects_removed.push((*id, get_type_label(a))),
+            (None, Some(b)) => objects_added.push((*id, get_type_label(b))),
+            (Some(a), Some(b)) => {
+                let mut fields = vec![];
+                diff_values(
+                    "",
+                    &serde_json::to_value(&a.body).unwrap(),
+                    &serde_json::to_value(&b.body).unwrap(),
+                    &mut fields,
+                );
+                if !fields.is_empty() {
+                    objects_changed.push((*id, get_type_label(b), fields));
+                }
+            },
+            (None, None) => unreachable!(),
+        }
+    }
+
+    if args.json {
+        let mut result = serde_json::Map::new();
+        result.insert("sections_added".into(), sections_added.into());
+        result.insert("sections_removed".into(), sections_removed.into());
+        result.insert("sections_changed".into(), sections_changed.into_iter()
+            .map(|(magic, fields)| {
+                let mut section = serde_json::Map::new();
+                section.insert("magic".into(), magic.into());
+                section.insert("fields".into(), fields_json(fields));
+                Value::Object(section)
+            })
+            .collect());
+        result.insert("objects_added".into(), objects_json(&objects_added));
+        result.insert("objects_removed".into(), objects_json(&objects_removed));
+        result.insert("objects_changed".into(), objects_changed.into_iter()
+            .map(|(id, type_label, fields)| {
+                let mut object = serde_json::Map::new();
+                object.insert("id".into(), id.into());
+                object.insert("type".into(), type_label.into());
+                object.insert("fields".into(), fields_json(fields));
+                Value::Object(object)
+            })
+            .collect());
+
+        println!("{}", Value::Object(result));
+        return;
+    }
+
+    for magic in sections_added.iter() {
+        println!("+ section {}", magic);
+    }
+    for magic in sections_removed.iter() {
+        println!("- section {}", magic);
+    }
+    for (magic, fields) in sections_changed.iter() {
+        println!("~ section {}", magic);
+        print_fields(fields);
+    }
+    for (id, type_label) in objects_added.iter() {
+        println!("+ object {} ({})", id, type_label);
+    }
+    for (id, type_label) in objects_removed.iter() {
+        println!("- object {} ({})", id, type_label);
+    }
+    for (id, type_label, fields) in objects_changed.iter() {
+        println!("~ object {} ({})", id, type_label);
+        print_fields(fields);
+    }
+}
+
+fn load(path: &path::Path) -> Soundbank {
+    let file_buffer = fs::read(path)
+        .expect("Could not read input file");
+    parse_soundbank(&file_buffer)
+        .expect("Could not parse bnk")
+}
+
+/// The serialized body of every section but the HIRC, whose objects are
+/// compared one by one instead.
+fn sections(soundbank: &Soundbank) -> collections::BTreeMap<String, Value> {
+    soundbank.sections.iter()
+        .filter(|s| !matches!(s.body, SectionBody::HIRC(_)))
+        .map(|s| (
+            String::from_utf8_lossy(&s.magic).to_string(),
+            serde_json::to_value(&s.body).unwrap(),
+        ))
+        .collect()
+}
+
+fn objects(soundbank: &Soundbank) -> collections::BTreeMap<u32, &HIRCObject> {
+    soundbank.hirc().into_iter()
+        .flat_map(|h| h.objects.iter())
+        .map(|o| (o.id.as_hash(), o))
+        .collect()
+}
+
+fn diff_values(path: &str, original: &Value, modified: &Value, result: &mut Vec<FieldDiff>) {
+    match (original, modified) {
+        (Value::Object(a), Value::Object(b)) => {
+            let keys = a.keys().chain(b.keys()).collect::<collections::BTreeSet<_>>();
+            for key in keys {
+                diff_values(
+                    &format!("{}.{}", path, key),
+                    a.get(key).unwrap_or(&Value::Null),
+                    b.get(key).unwrap_or(&Value::Null),
+                    result,
+                );
+            }
+        },
+        (Value::Array(a), Value::Array(b)) if a.len() == b.len() => {
+            for (i, (a, b)) in a.iter().zip(b.iter()).enumerate() {
+                diff_values(&format!("{}[{}]", path, i), a, b, result);
+            }
+        },
+        (a, b) if a != b => result.push(FieldDiff {
+            path: path.trim_start_matches('.').to_string(),
+            original: a.clone(),
+            modified: b.clone(),
+        }),
+        _ => {},
+    }
+}
+
+fn fields_json(fields: Vec<FieldDiff>) -> Value {
+    fields.into_iter()
+        .map(|f| {
+            let mut field = serde_json::Map::new();
+            field.insert("path".into(), f.path.into());
+            field.insert("original".into(), f.original);
+            field.insert("modified".into(), f.modified);
+            Value::Object(field)
+        })
+        .collect()
+}
+
+fn objects_json(objects: &[(u32, &str)]) -> Value {
+    objects.iter()
+        .map(|(id, type_label)| {
+            let mut object = serde_json::Map::new();
+            object.insert("id".into(), (*id).into());
+            object.insert("type".into(), (*type_label).into());
+            Value::Object(object)
+        })
+        .collect()
+}
+
+fn print_fields(fields: &[FieldDiff]) {
+    for field in fields {
+        println!("    {}: {} -> {}", field.path, shorten(&field.original), shorten(&field.modified));
+    }
+}
+
+// Keeps large blobs like the DATA from flooding the terminal
+fn shorten(value: &Value) -> String {
+    let result = value.to_string();
+    match result.char_indices().nth(80) {
+        Some((i, _)) => format!("{}...", &result[..i]),
+        None => result,
+    }
+}
@@ -0,0 +1,46 @@
+use std::fs;
+use std::path;
+
+use clap::Parser;
+use wwise_format::*;
+
+#[derive(Parser)]
+#[command(author, version, about, long_about = None)]
+struct Arguments {
+    /// The soundbank to extract the WEMs from
+    soundbank: path::PathBuf,
+
+    /// Directory to write the <id>.wem files to
+    output: path::PathBuf,
+
+    #[arg(long)]
+    /// Also write a manifest.csv with the ID, offset and size of every WEM
+    manifest: bool,
+}
+
+fn main() {
+    let args = Arguments::parse();
+
+    let file_buffer = fs::read(&args.soundbank)
+        .expect("Could not read input file");
+    let soundbank = parse_soundbank(&file_buffer)
+        .expect("Could not parse bnk");
+
+    fs::create_dir_all(&args.output)
+        .expect("Could not create output directory");
+
+    for (id, bytes) in soundbank.extract_media() {
+        fs::write(args.output.join(format!("{}.wem", id)), bytes)
+            .expect("Could not write WEM to output directory");
+    }
+
+    if args.manifest {
+        let mut manifest = String::from("id,offset,size\n");
+        for descriptor in soundbank.didx().iter().flat_map(|d| d.descriptors.iter()) {
+            manifest.push_str(&format!("{},{},{}\n", descriptor.id, descriptor.offset, descriptor.size));
+        }
+
+        fs::write(args.output.join("manifest.csv"), manifest)
+            .expect("Could not write manifest");
+    }
+}
@@ -0,0 +1,66 @@
+use std::fs;
+use std::path;
+
+use clap::Parser;
+use wwise_format::*;
+use wwise_analysis::dictionary::parse_dictionary;
+
+#[derive(Parser)]
+#[command(author, version, about, long_about = None)]
+struct Arguments {
+    /// The music soundbank to list the markers for
+    soundbank: path::PathBuf,
+
+    #[arg(short, long)]
+    /// Dictionary used to name the segments
+    dictionary: Option<path::PathBuf>,
+}
+
+fn main() {
+    let args = Arguments::parse();
+
+    let dictionary = args.dictionary.map(|p| {
+        let dictionary_file = fs::read_to_string(p)
+            .expect("Could not read dictionary");
+        parse_dictionary(&dictionary_file)
+    });
+
+    let file_buffer = fs::read(&args.soundbank)
+        .expect("Could not read input file");
+    let soundbank = parse_soundbank(&file_buffer)
+        .expect("Could not parse bnk");
+
+    println!("segment_id,segment_name,marker_id,position,name");
+    soundbank.visit(&mut |o| {
+        let segment = match &o.body {
+            HIRCObjectBody::MusicSegment(s) => s,
+            _ => return,
+        };
+
+        let segment_id = o.id.as_hash();
+        let segment_name = dictionary.as_ref()
+            .and_then(|d| d.get(&segment_id))
+            .map(|n| n.as_str())
+            .unwrap_or_default();
+
+        for marker in segment.markers.iter() {
+            println!(
+                "{},{},{},{},{}",
+                segment_id,
+                escape(segment_name),
+                marker.id,
+                // Wwise stores the position in milliseconds
+                marker.position / 1000.0,
+                escape(&marker.string.to_string_lossy()),
+            );
+        }
+    });
+}
+
+fn escape(field: &str) -> String {
+    if field.contains([',', '"', '\n']) {
+        format!("\"{}\"", field.replace('"', "\"\""))
+    } else {
+        field.to_string()
+    }
+}
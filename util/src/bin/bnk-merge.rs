@@ -0,0 +1,151 @@
+use std::fs;
+use std::path;
+use std::process;
+use std::collections;
+
+use clap::Parser;
+use wwise_format::*;
+
+#[derive(Parser)]
+#[command(author, version, about, long_about = None)]
+struct Arguments {
+    /// The soundbanks to combine, objects are kept in this order
+    #[arg(required = true)]
+    soundbanks: Vec<path::PathBuf>,
+
+    #[arg(short, long)]
+    /// Where to write the merged soundbank
+    output: path::PathBuf,
+
+    #[arg(long)]
+    /// Let later soundbanks replace objects with an ID that's already taken
+    /// instead of failing
+    overwrite: bool,
+
+    #[arg(long)]
+    /// Soundbank to take the BKHD from, defaults to the first input
+    header_from: Option<path::PathBuf>,
+}
+
+fn main() {
+    let args = Arguments::parse();
+
+    let soundbanks = args.soundbanks.iter()
+        .map(|p| (p, load(p)))
+        .collect::<Vec<_>>();
+
+    let header = match &args.header_from {
+        Some(p) => load(p),
+        None => soundbanks[0].1.clone(),
+    };
+
+    // Objects and sections are copied over as-is, so they have to share the
+    // layout and byte order of the bank they end up in
+    let first = &soundbanks[0].1;
+    let inputs = soundbanks.iter()
+        .map(|(p, s)| (p.as_path(), s))
+        .chain(args.header_from.as_deref().map(|p| (p, &header)));
+    for (path, soundbank) in inputs {
+        if soundbank.version() != first.version() || soundbank.endian != first.endian {
+            eprintln!(
+                "{:?} is a version {} {:?} endian soundbank, {:?} is version {} {:?} endian",
+                path,
+                soundbank.version(),
+                soundbank.endian,
+                soundbanks[0].0,
+                first.version(),
+                first.endian,
+            );
+            process::exit(1);
+        }
+    }
+    let bkhd = header.sections.into_iter()
+        .find(|s| matches!(s.body, SectionBody::BKHD(_)))
+        .expect("Soundbank needs a BKHD section");
+
+    let mut objects = Vec::<HIRCObject>::new();
+    let mut object_indices = collections::HashMap::new();
+    let mut media = Vec::<(u32, Vec<u8>)>::new();
+    let mut other_sections = Vec::<Section>::new();
+
+    for (path, soundbank) in soundbanks.iter() {
+        for object in soundbank.hirc().into_iter().flat_map(|h| h.objects.iter()) {
+            let id = object.id.as_hash();
+            match object_indices.get(&id) {
+                None => {
+                    object_indices.insert(id, objects.len());
+                    objects.push(object.clone());
+                },
+                Some(index) if args.overwrite => {
+                    eprintln!("Object {} from {:?} replaces an earlier definition", id, path);
+                    objects[*index] = object.clone();
+                },
+                Some(_) => {
+                    eprintln!("Object {} from {:?} is already defined, pass --overwrite to replace it", id, path);
+                    process::exit(1);
+                },
+            }
+        }
+
+        for (id, bytes) in soundbank.extract_media() {
+            match media.iter().find(|(existing_id, _)| *existing_id == id) {
+                None => media.push((id, bytes)),
+                Some((_, existing)) if *existing == bytes => {
+                    eprintln!("Dropping duplicate WEM {} from {:?}", id, path);
+                },
+                Some(_) => {
+                    eprintln!("Dropping WEM {} from {:?}, an earlier soundbank has a different WEM with this ID", id, path);
+                },
+            }
+        }
+
+        // Only one of every other section can be kept
+        for section in soundbank.sections.iter() {
+            match &section.body {
+                SectionBody::BKHD(_) | SectionBody::DIDX(_)
+                | SectionBody::DATA(_) | SectionBody::HIRC(_) => continue,
+                _ => {},
+            }
+
+            match other_sections.iter().any(|s| s.magic == section.magic) {
+                false => other_sections.push(section.clone()),
+                true => eprintln!(
+                    "Dropping {} section from {:?}, an earlier soundbank has one",
+                    String::from_utf8_lossy(&section.magic),
+                    path,
+                ),
+            }
+        }
+    }
+
+    let wem_alignment = match &bkhd.body {
+        SectionBody::BKHD(b) => b.wem_alignment,
+        _ => unreachable!(),
+    };
+
+    let mut sections = vec![
+        bkhd,
+        Section { magic: *b"HIRC", size: 0, body: SectionBody::HIRC(HIRCSection::new(objects)) },
+    ];
+    sections.extend(other_sections);
+
+    let mut soundbank = Soundbank { endian: header.endian, sections };
+    soundbank.replace_media(&media, wem_alignment);
+
+    if let Err(e) = prepare_soundbank(&mut soundbank) {
+        eprintln!("Could not prepare merged soundbank: {}", e);
+        process::exit(1);
+    }
+
+    let mut handle = fs::File::create(&args.output)
+        .expect("Could not acquire write file handle");
+    soundbank.to_writer(&mut handle)
+        .expect("Could not write soundbank to output file");
+}
+
+fn load(path: &path::Path) -> Soundbank {
+    let file_buffer = fs::read(path)
+        .expect("Could not read input file");
+    parse_soundbank_detect(&file_buffer)
+        .expect("Could not parse bnk")
+}
@@ -0,0 +1,105 @@
+use std::fs;
+use std::io;
+use std::path;
+use std::collections;
+
+use clap::Parser;
+use wwise_format::*;
+use wwise_analysis::bus::undefined_bus_routes;
+use wwise_analysis::label::get_type_label;
+use wwise_analysis::sources::sound_sources;
+
+#[derive(Parser)]
+#[command(author, version, about, long_about = None)]
+struct Arguments {
+    /// The soundbank to summarize
+    soundbank: path::PathBuf,
+
+    #[arg(long)]
+    /// Emit the summary as a single JSON object
+    json: bool,
+}
+
+fn main() {
+    let args = Arguments::parse();
+
+    let file = fs::File::open(&args.soundbank)
+        .expect("Could not open input file");
+    let total_size = file.metadata()
+        .expect("Could not read input file metadata")
+        .len();
+
+    // The DATA is skipped so the WEMs don't end up being read. The DIDX still
+    // describes the media so nothing relevant to the summary is lost.
+    let mut reader = SoundbankReader::new(io::BufReader::new(file));
+    let mut sections = vec![];
+    while let Some(header) = reader.next() {
+        let header = header.expect("Could not read section header");
+        if &header.magic != b"DATA" {
+            sections.push(reader.read_section(&header).expect("Could not parse bnk"));
+        }
+    }
+    let soundbank = Soundbank { endian: reader.endian(), sections };
+
+    let mut summary = serde_json::Map::new();
+    summary.insert("total_size".into(), total_size.into());
+
+    let mut plugins = collections::BTreeSet::new();
+    for section in soundbank.sections.iter() {
+        match &section.body {
+            SectionBody::BKHD(b) => {
+                summary.insert("version".into(), b.version.into());
+                summary.insert("bank_id".into(), b.bank_id.into());
+                summary.insert("language".into(), b.language_fnv_hash.into());
+                if let Some(name) = b.language_name() {
+                    summary.insert("language_name".into(), name.into());
+                }
+            },
+            SectionBody::PLAT(p) => {
+                summary.insert("platform".into(), p.string.to_string_lossy().into());
+            },
+            SectionBody::INIT(i) => {
+                plugins.extend(i.plugins.iter().map(|p| format!("{:?}", p.plugin_id)));
+            },
+            _ => {},
+        }
+    }
+
+    let mut object_counts = collections::BTreeMap::<&str, usize>::new();
+    soundbank.visit(&mut |o| {
+        *object_counts.entry(get_type_label(o)).or_default() += 1;
+
+        let sources = match &o.body {
+            HIRCObjectBody::Sound(s) => std::slice::from_ref(&s.bank_source_data),
+            HIRCObjectBody::MusicTrack(t) => t.sources.as_slice(),
+            _ => &[],
+        };
+        plugins.extend(sources.iter().map(|s| format!("{:?}", s.plugin)));
+    });
+
+    summary.insert("object_counts".into(), serde_json::to_value(&object_counts).unwrap());
+    let media = soundbank.media_stats();
+    summary.insert("wem_count".into(), media.count.into());
+    summary.insert("wem_bytes".into(), media.total_bytes.into());
+    summary.insert("plugins".into(), serde_json::to_value(&plugins).unwrap());
+
+    let mut source_storage = collections::BTreeMap::<String, usize>::new();
+    for source in sound_sources(&soundbank).values() {
+        *source_storage.entry(source.storage().to_string()).or_default() += 1;
+    }
+    summary.insert("source_storage".into(), serde_json::to_value(&source_storage).unwrap());
+
+    // Buses routed to but defined elsewhere, usually in the init bank
+    let undefined_buses = undefined_bus_routes(&soundbank).into_iter()
+        .map(|(_, bus)| bus)
+        .collect::<collections::BTreeSet<_>>();
+    summary.insert("undefined_buses".into(), serde_json::to_value(&undefined_buses).unwrap());
+
+    if args.json {
+        println!("{}", serde_json::Value::Object(summary));
+    } else {
+        for (key, value) in summary.iter() {
+            println!("{}: {}", key, value);
+        }
+    }
+}
@@ -0,0 +1,76 @@
+use std::fs;
+use std::path;
+
+use clap::Parser;
+use wwise_format::*;
+use wwise_analysis::bus::output_bus_id;
+use wwise_analysis::dictionary::parse_dictionary;
+use wwise_analysis::label::get_type_label;
+use wwise_analysis::sources::sound_sources;
+
+#[derive(Parser)]
+#[command(author, version, about, long_about = None)]
+struct Arguments {
+    /// The soundbank to list the HIRC objects of
+    soundbank: path::PathBuf,
+
+    #[arg(short, long)]
+    /// Dictionary used to name the objects
+    dictionary: Option<path::PathBuf>,
+}
+
+fn main() {
+    let args = Arguments::parse();
+
+    let dictionary = args.dictionary.map(|p| {
+        let dictionary_file = fs::read_to_string(p)
+            .expect("Could not read dictionary");
+        parse_dictionary(&dictionary_file)
+    });
+
+    let file_buffer = fs::read(&args.soundbank)
+        .expect("Could not read input file");
+    let soundbank = parse_soundbank(&file_buffer)
+        .expect("Could not parse bnk");
+
+    let sources = sound_sources(&soundbank);
+
+    println!("id,name,type,parent_id,output_bus_id,source_id,source_storage");
+    soundbank.visit(&mut |o| {
+        let id = o.id.as_hash();
+        let name = match &o.id {
+            ObjectId::String(s) => Some(s),
+            ObjectId::Hash(_) => dictionary.as_ref().and_then(|d| d.get(&id)),
+            ObjectId::Hash64(_) => None,
+        };
+
+        let parent_id = o.body.node_base_params()
+            .map(|p| p.direct_parent_id.as_hash())
+            .filter(|p| *p != 0);
+
+        let source = sources.get(&id);
+
+        println!(
+            "{},{},{},{},{},{},{}",
+            id,
+            escape(name.map(|n| n.as_str()).unwrap_or_default()),
+            get_type_label(o),
+            optional(parent_id),
+            optional(output_bus_id(o)),
+            optional(source.map(|s| s.source_id)),
+            source.map(|s| s.storage().to_string()).unwrap_or_default(),
+        );
+    });
+}
+
+fn optional(id: Option<u32>) -> String {
+    id.map(|i| i.to_string()).unwrap_or_default()
+}
+
+fn escape(field: &str) -> String {
+    if field.contains([',', '"', '\n']) {
+        format!("\"{}\"", field.replace('"', "\"\""))
+    } else {
+        field.to_string()
+    }
+}
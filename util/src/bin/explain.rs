@@ -0,0 +1,39 @@
+use std::fs;
+use std::path;
+
+use clap::Parser;
+use wwise_analysis::dictionary::parse_dictionary;
+use wwise_analysis::explain::explain;
+use wwise_format::ObjectId;
+
+#[derive(Parser)]
+#[command(author, version, about, long_about = None)]
+struct Arguments {
+    #[arg(short, long)]
+    dictionary: Option<path::PathBuf>,
+
+    #[arg(short, long)]
+    soundbank: path::PathBuf,
+
+    #[arg(short, long)]
+    /// The object to explain, either as its numeric ID in decimal or 0x
+    /// prefixed hex, or its name
+    object: ObjectId,
+}
+
+fn main() {
+    let args = Arguments::parse();
+
+    let dictionary = args.dictionary.map(|p| {
+        let dictionary_file = fs::read_to_string(p)
+            .expect("Could not read dictionary");
+        parse_dictionary(&dictionary_file)
+    });
+
+    let file_buffer = fs::read(&args.soundbank)
+        .expect("Could not read input file");
+    let soundbank = wwise_format::parse_soundbank(&file_buffer)
+        .expect("Could not parse bnk");
+
+    println!("{}", explain(&soundbank, args.object.as_hash(), dictionary.as_ref()));
+}
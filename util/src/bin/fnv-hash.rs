@@ -1,16 +1,53 @@
+use std::fs;
+use std::io;
+use std::path;
+
 use clap::Parser;
+use wwise_analysis::dictionary;
 use wwise_analysis::fnv;
 
 #[derive(Parser)]
 #[command(author, version, about, long_about = None)]
 struct Arguments {
-    #[arg(short, long)]
+    #[arg(short, long, conflicts_with = "file")]
     /// The input string that needs to be hashed
-    input: String,
+    input: Option<String>,
+
+    #[arg(short, long)]
+    /// File with one string per line to hash, stdin is read if neither this
+    /// nor --input is given
+    file: Option<path::PathBuf>,
+
+    #[arg(long)]
+    /// Print the hashes in hex
+    hex: bool,
+}
+
+impl Arguments {
+    fn format_hash(&self, hash: u32) -> String {
+        match self.hex {
+            true => format!("{:#010x}", hash),
+            false => hash.to_string(),
+        }
+    }
 }
 
 fn main() {
     let args = Arguments::parse();
 
-    println!("{}", fnv::create_hash(args.input.as_str()));
+    if let Some(input) = &args.input {
+        println!("{}", args.format_hash(fnv::create_hash(input)));
+        return;
+    }
+
+    let input = match &args.file {
+        Some(path) => fs::read_to_string(path)
+            .expect("Could not read input file"),
+        None => io::read_to_string(io::stdin())
+            .expect("Could not read stdin"),
+    };
+
+    for name in dictionary::names(&input) {
+        println!("{}\t{}", args.format_hash(fnv::create_hash(name)), name);
+    }
 }
@@ -0,0 +1,82 @@
+use std::fs;
+use std::path;
+use std::process;
+
+use clap::Parser;
+use clap::builder::PossibleValuesParser;
+use wwise_format::*;
+use wwise_analysis::label::{get_type_label, TYPE_LABELS};
+
+#[derive(Parser)]
+#[command(author, version, about, long_about = None)]
+struct Arguments {
+    #[arg(required = true)]
+    /// The soundbanks to dump
+    soundbanks: Vec<path::PathBuf>,
+
+    #[arg(long, value_delimiter = ',')]
+    /// Only dump these sections, for example HIRC,STMG
+    section: Vec<String>,
+
+    #[arg(long, value_delimiter = ',', value_parser = PossibleValuesParser::new(TYPE_LABELS))]
+    /// Only dump HIRC objects of these types
+    object_type: Vec<String>,
+
+    #[arg(long)]
+    /// Accept soundbanks with incorrect section and HIRC object sizes
+    repair: bool,
+}
+
+impl Arguments {
+    fn includes_section(&self, section: &Section) -> bool {
+        self.section.is_empty() || self.section.iter()
+            .any(|s| s.as_bytes().eq_ignore_ascii_case(&section.magic))
+    }
+
+    fn includes_object(&self, object: &HIRCObject) -> bool {
+        self.object_type.is_empty() || self.object_type.iter()
+            .any(|t| t == get_type_label(object))
+    }
+}
+
+fn main() {
+    let args = Arguments::parse();
+
+    let mut failed = false;
+    for path in args.soundbanks.iter() {
+        if let Err(e) = inspect(&args, path) {
+            eprintln!("{}: {}", path.display(), e);
+            failed = true;
+        }
+    }
+
+    if failed {
+        process::exit(1);
+    }
+}
+
+fn inspect(args: &Arguments, path: &path::Path) -> Result<(), Box<dyn std::error::Error>> {
+    let file_buffer = fs::read(path)
+        .map_err(|e| format!("Could not read file: {}", e))?;
+    let mut soundbank = match args.repair {
+        true => parse_soundbank_repair(&file_buffer).map(|(s, warnings)| {
+            for warning in warnings {
+                eprintln!("{}: {:#x}: {}", path.display(), warning.offset, warning.message);
+            }
+            s
+        }),
+        false => parse_soundbank(&file_buffer),
+    }.map_err(|e| format!("Could not parse soundbank: {}", e))?;
+
+    soundbank.sections.retain(|s| args.includes_section(s));
+    if let Some(hirc) = soundbank.hirc_mut() {
+        hirc.objects.retain(|o| args.includes_object(o));
+    }
+
+    let mut output = serde_json::Map::new();
+    output.insert("soundbank".into(), path.display().to_string().into());
+    output.insert("sections".into(), serde_json::to_value(&soundbank.sections)?);
+
+    println!("{}", serde_json::to_string_pretty(&serde_json::Value::Object(output))?);
+    Ok(())
+}
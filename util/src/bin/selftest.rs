@@ -0,0 +1,28 @@
+use std::fs;
+use std::path;
+
+use clap::Parser;
+use wwise_format::*;
+
+#[derive(Parser)]
+#[command(author, version, about, long_about = None)]
+/// Checks if a soundbank survives being parsed and written back unchanged
+struct Arguments {
+    /// The soundbank to test
+    soundbank: path::PathBuf,
+}
+
+fn main() {
+    let args = Arguments::parse();
+
+    let original = fs::read(&args.soundbank)
+        .expect("Could not read input file");
+
+    match Soundbank::verify_roundtrip(&original) {
+        Ok(()) => println!("identical"),
+        Err(e) => {
+            println!("{}", e);
+            std::process::exit(1);
+        },
+    }
+}